@@ -0,0 +1,339 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::width::char_width;
+
+/// Removes ANSI escape sequences, such as SGR color codes, from `text`.
+///
+/// Control sequences (`ESC [ ... final`), operating system commands (`ESC ] ... BEL` or
+/// `ESC ] ... ESC \`), character set designations (`ESC ( 0`) and two-character escapes are all
+/// removed.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::strip_ansi_codes;
+///
+/// assert_eq!(strip_ansi_codes("\x1b[1;31mError\x1b[0m: failed"), "Error: failed");
+/// ```
+pub fn strip_ansi_codes(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            stripped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            Some('(') | Some(')') => {
+                chars.next();
+            }
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    stripped
+}
+
+/// The distance, in columns, between the tab stops `sanitize` expands tabs to.
+const TAB_WIDTH: usize = 8;
+
+/// Makes `text` safe to measure and frame: `\r\n` line endings become `\n`, tabs are expanded
+/// with spaces to the next tab stop, and control characters other than newlines, such as lone
+/// carriage returns, backspaces or bells, are removed. Escape sequences are kept.
+pub(crate) fn sanitize(text: &str) -> String {
+    sanitize_with_tab_width(text, TAB_WIDTH)
+}
+
+/// Sanitizes `text` like `sanitize`, with tab stops every `tab_width` columns.
+pub(crate) fn sanitize_with_tab_width(text: &str, tab_width: usize) -> String {
+    let tab_width = tab_width.max(1);
+    let mut sanitized = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut column = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => {
+                sanitized.push(c);
+                copy_escape_sequence(&mut chars, &mut sanitized);
+            }
+            '\n' => {
+                sanitized.push(c);
+                column = 0;
+            }
+            '\t' => {
+                let spaces = tab_width - column % tab_width;
+                sanitized.push_str(&" ".repeat(spaces));
+                column += spaces;
+            }
+            c if c.is_control() => {}
+            c => {
+                sanitized.push(c);
+                column += char_width(c);
+            }
+        }
+    }
+
+    sanitized
+}
+
+/// The codes turning off each attribute tracked by `close_styles_per_line`: the foreground and
+/// background colors, bold, dim, italic, underline, blink, inverse, hidden and strikethrough.
+const SGR_CLOSE: [&str; 10] = ["39", "49", "22", "22", "23", "24", "25", "27", "28", "29"];
+
+/// Closes the SGR attributes left on at the end of each of `lines` and turns them on again at
+/// the start of the next, so styles applied before wrapping stay within their row and never
+/// leak into the border drawn around it.
+pub(crate) fn close_styles_per_line(lines: &mut [String]) {
+    // The parameters turning each attribute of `SGR_CLOSE` on, if it is on.
+    let mut on: [Option<String>; 10] = Default::default();
+
+    for line in lines.iter_mut() {
+        let reopen: Vec<&str> = on.iter().flatten().map(String::as_str).collect();
+        let mut styled = if reopen.is_empty() {
+            String::with_capacity(line.len())
+        } else {
+            format!("\x1b[{}m", reopen.join(";"))
+        };
+
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            styled.push(c);
+            if c != '\x1b' {
+                continue;
+            }
+            let mut sequence = String::new();
+            copy_escape_sequence(&mut chars, &mut sequence);
+            if let Some(params) = sequence.strip_prefix('[').and_then(|s| s.strip_suffix('m')) {
+                apply_sgr(params, &mut on);
+            }
+            styled.push_str(&sequence);
+        }
+
+        let mut close: Vec<&str> = (0..on.len())
+            .filter(|&i| on[i].is_some())
+            .map(|i| SGR_CLOSE[i])
+            .collect();
+        close.dedup();
+        if !close.is_empty() {
+            styled.push_str(&format!("\x1b[{}m", close.join(";")));
+        }
+        *line = styled;
+    }
+}
+
+/// Updates the attributes `on` with the parameters of an SGR sequence.
+fn apply_sgr(params: &str, on: &mut [Option<String>; 10]) {
+    let mut params = params.split(';');
+    while let Some(param) = params.next() {
+        let code = if param.is_empty() {
+            0
+        } else {
+            match param.parse::<u32>() {
+                Ok(code) => code,
+                Err(_) => continue,
+            }
+        };
+        match code {
+            0 => *on = Default::default(),
+            1..=9 => on[[2, 3, 4, 5, 6, 6, 7, 8, 9][code as usize - 1]] = Some(param.to_string()),
+            22 => {
+                on[2] = None;
+                on[3] = None;
+            }
+            23..=29 => {
+                let close = code.to_string();
+                if let Some(index) = SGR_CLOSE.iter().position(|&code| code == close) {
+                    on[index] = None;
+                }
+            }
+            30..=37 | 90..=97 => on[0] = Some(param.to_string()),
+            40..=47 | 100..=107 => on[1] = Some(param.to_string()),
+            39 => on[0] = None,
+            49 => on[1] = None,
+            38 | 48 => {
+                let mut color = vec![param.to_string()];
+                let count = match params.next() {
+                    Some("5") => {
+                        color.push("5".to_string());
+                        1
+                    }
+                    Some("2") => {
+                        color.push("2".to_string());
+                        3
+                    }
+                    _ => continue,
+                };
+                color.extend(params.by_ref().take(count).map(str::to_string));
+                on[(code == 48) as usize] = Some(color.join(";"));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Removes whitespace from the start of `text` if `start` is set, and from its end if `end` is
+/// set, like `str::trim_start` and `str::trim_end`, looking past escape sequences. The escape
+/// sequences among the whitespace are kept.
+pub(crate) fn trim_visible(text: &str, start: bool, end: bool) -> String {
+    // Each piece of `text` is an escape sequence or a single character.
+    let mut pieces: Vec<(bool, String)> = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let mut piece = c.to_string();
+        if c == '\x1b' {
+            copy_escape_sequence(&mut chars, &mut piece);
+        }
+        pieces.push((c == '\x1b', piece));
+    }
+
+    let visible = |&(escape, ref piece): &(bool, String)| !escape && !piece.trim().is_empty();
+    let first = pieces.iter().position(visible).unwrap_or(pieces.len());
+    let last = pieces.iter().rposition(visible).map_or(0, |last| last + 1);
+    pieces
+        .iter()
+        .enumerate()
+        .filter(|&(i, &(escape, _))| escape || !((start && i < first) || (end && i >= last)))
+        .map(|(_, (_, piece))| piece.as_str())
+        .collect()
+}
+
+/// Copies the rest of the escape sequence started by the `ESC` just read from `chars`.
+pub(crate) fn copy_escape_sequence(chars: &mut Peekable<Chars>, output: &mut String) {
+    match chars.next() {
+        Some('[') => {
+            output.push('[');
+            for c in chars.by_ref() {
+                output.push(c);
+                if ('\x40'..='\x7e').contains(&c) {
+                    break;
+                }
+            }
+        }
+        Some(']') => {
+            output.push(']');
+            while let Some(c) = chars.next() {
+                output.push(c);
+                if c == '\x07' {
+                    break;
+                }
+                if c == '\x1b' && chars.peek() == Some(&'\\') {
+                    output.push('\\');
+                    chars.next();
+                    break;
+                }
+            }
+        }
+        Some(c @ '(') | Some(c @ ')') => {
+            output.push(c);
+            output.extend(chars.next());
+        }
+        Some(c) => output.push(c),
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trimming_looks_past_escape_sequences() {
+        assert_eq!(
+            trim_visible("\x1b[1m  ab \x1b[22m ", true, false),
+            "\x1b[1mab \x1b[22m "
+        );
+        assert_eq!(
+            trim_visible("\x1b[1m  ab \x1b[22m ", false, true),
+            "\x1b[1m  ab\x1b[22m"
+        );
+        assert_eq!(
+            trim_visible(" \x1b[1m \x1b[22m ", true, true),
+            "\x1b[1m\x1b[22m"
+        );
+    }
+
+    #[test]
+    fn strips_every_kind_of_escape_sequence() {
+        assert_eq!(strip_ansi_codes("a\x1b[38;5;208mb\x1b[0mc"), "abc");
+        assert_eq!(
+            strip_ansi_codes("\x1b]8;;http://x\x07link\x1b]8;;\x07"),
+            "link"
+        );
+        assert_eq!(strip_ansi_codes("\x1b]0;title\x1b\\text"), "text");
+        assert_eq!(strip_ansi_codes("\x1b(0lqk\x1b(B"), "lqk");
+        assert_eq!(strip_ansi_codes("a\x1b7b\x1b8"), "ab");
+    }
+
+    #[test]
+    fn strips_unterminated_sequences_to_the_end() {
+        assert_eq!(strip_ansi_codes("a\x1b"), "a");
+        assert_eq!(strip_ansi_codes("a\x1b[31"), "a");
+        assert_eq!(strip_ansi_codes("a\x1b]unterminated"), "a");
+    }
+
+    #[test]
+    fn keeps_text_without_escapes() {
+        assert_eq!(
+            strip_ansi_codes("plain [text] ünïcode"),
+            "plain [text] ünïcode"
+        );
+    }
+
+    #[test]
+    fn sanitizing_keeps_character_set_designations_out_of_tab_stops() {
+        assert_eq!(sanitize("\x1b(0q\x1b(B\tx"), "\x1b(0q\x1b(B       x");
+    }
+
+    fn closed_per_line(lines: &[&str]) -> Vec<String> {
+        let mut lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        close_styles_per_line(&mut lines);
+        lines
+    }
+
+    #[test]
+    fn open_styles_are_closed_and_reopened_on_the_next_line() {
+        assert_eq!(
+            closed_per_line(&["\x1b[1;38;5;208mab", "cd\x1b[22m", "ef\x1b[0m", "gh"]),
+            [
+                "\x1b[1;38;5;208mab\x1b[39;22m",
+                "\x1b[38;5;208;1mcd\x1b[22m\x1b[39m",
+                "\x1b[38;5;208mef\x1b[0m",
+                "gh",
+            ]
+        );
+    }
+
+    #[test]
+    fn closed_styles_and_other_escapes_are_left_alone() {
+        assert_eq!(
+            closed_per_line(&["\x1b[4mab\x1b[24m", "\x1b]8;;x\x07cd", "\x1b[48;2;1;2;3m"]),
+            [
+                "\x1b[4mab\x1b[24m",
+                "\x1b]8;;x\x07cd",
+                "\x1b[48;2;1;2;3m\x1b[49m"
+            ]
+        );
+    }
+}
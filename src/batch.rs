@@ -0,0 +1,164 @@
+#[cfg(feature = "bumpalo")]
+use bumpalo::collections::Vec as BumpVec;
+#[cfg(feature = "bumpalo")]
+use bumpalo::Bump;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{default_options, TextBorderOptions};
+
+/// Creates a string for each message of `messages`, surrounded by a border and margin as
+/// specified by the provided `TextBorderOptions`, such as to frame every record of a report.
+///
+/// The options are compiled once into a `CompiledTheme` for the whole batch. With the `rayon`
+/// feature, the messages are rendered in parallel on the rayon thread pool. The boxes are
+/// returned in the order of the messages either way, so the output does not depend on how the
+/// work was scheduled.
+///
+/// # Arguments
+///
+/// * `messages` - The messages to be surrounded by borders.
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations, shared by every box. If `None`, the options returned by `default_options`
+///   on the calling thread are used, including scoped defaults.
+///
+/// # Returns
+///
+/// * A `Vec` of the boxes, one per message, in order.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::create_text_borders;
+///
+/// let records: Vec<String> = (1..=3).map(|i| format!("record {}", i)).collect();
+///
+/// assert_eq!(
+///     create_text_borders(&records, None),
+///     vec![
+///         "**********\n*record 1*\n**********",
+///         "**********\n*record 2*\n**********",
+///         "**********\n*record 3*\n**********",
+///     ]
+/// );
+/// ```
+pub fn create_text_borders<S: AsRef<str> + Sync>(
+    messages: &[S],
+    options: Option<TextBorderOptions>,
+) -> Vec<String> {
+    let theme = options.unwrap_or_else(default_options).compile();
+    let render = |message: &S| theme.render(message.as_ref());
+
+    #[cfg(feature = "rayon")]
+    let boxes = messages.par_iter().map(render).collect();
+    #[cfg(not(feature = "rayon"))]
+    let boxes = messages.iter().map(render).collect();
+    boxes
+}
+
+/// Creates a string for each message of `messages`, like `create_text_borders`, allocating the
+/// boxes and the list of them in `bump`, so thousands of short-lived boxes are freed at once
+/// with the arena rather than one by one. This function is available with the `bumpalo`
+/// feature.
+///
+/// The messages are rendered in order on the calling thread, even with the `rayon` feature.
+///
+/// # Arguments
+///
+/// * `bump` - The arena the boxes are allocated in.
+/// * `messages` - The messages to be surrounded by borders.
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations, shared by every box. If `None`, the options returned by `default_options`
+///   are used.
+///
+/// # Returns
+///
+/// * A `bumpalo::collections::Vec` of the boxes, one per message, in order.
+///
+/// # Examples
+///
+/// ```
+/// extern crate bumpalo;
+/// extern crate unicode_border;
+///
+/// use bumpalo::Bump;
+/// use unicode_border::create_text_borders_in;
+///
+/// fn main() {
+///     let mut bump = Bump::new();
+///     for batch in [["a", "b"], ["c", "d"]].iter() {
+///         {
+///             let boxes = create_text_borders_in(&bump, batch, None);
+///             assert_eq!(boxes.len(), 2);
+///             assert_eq!(boxes[0], format!("***\n*{}*\n***", batch[0]));
+///         }
+///         // Frees every box of the batch at once.
+///         bump.reset();
+///     }
+/// }
+/// ```
+#[cfg(feature = "bumpalo")]
+pub fn create_text_borders_in<'bump, S: AsRef<str>>(
+    bump: &'bump Bump,
+    messages: &[S],
+    options: Option<TextBorderOptions>,
+) -> BumpVec<'bump, &'bump str> {
+    let theme = options.unwrap_or_else(default_options).compile();
+    let mut boxes = BumpVec::with_capacity_in(messages.len(), bump);
+    boxes.extend(
+        messages
+            .iter()
+            .map(|message| theme.render_in(bump, message.as_ref())),
+    );
+    boxes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_text_border, scoped_default_options};
+
+    #[test]
+    fn boxes_are_returned_in_the_order_of_the_messages() {
+        let messages: Vec<String> = (0..200).map(|i| "x".repeat(i % 7)).collect();
+        let boxes = create_text_borders(&messages, None);
+        assert_eq!(boxes.len(), messages.len());
+        for (message, boxed) in messages.iter().zip(&boxes) {
+            assert_eq!(*boxed, create_text_border(message.as_str(), None));
+        }
+    }
+
+    #[test]
+    fn no_messages_give_no_boxes() {
+        let messages: [&str; 0] = [];
+        assert!(create_text_borders(&messages, None).is_empty());
+    }
+
+    #[test]
+    fn scoped_defaults_of_the_calling_thread_are_used() {
+        let _defaults = scoped_default_options(TextBorderOptions {
+            border_char: '#',
+            ..Default::default()
+        });
+        assert_eq!(
+            create_text_borders(&["a", "b"], None),
+            ["###\n#a#\n###", "###\n#b#\n###"]
+        );
+    }
+
+    #[cfg(feature = "bumpalo")]
+    #[test]
+    fn boxes_in_the_arena_match_the_allocated_ones() {
+        let bump = Bump::new();
+        let messages = ["one", "two\nlines", ""];
+        let options = TextBorderOptions {
+            margin_thickness: 1.into(),
+            ..Default::default()
+        };
+        let in_arena = create_text_borders_in(&bump, &messages, Some(options.clone()));
+        assert_eq!(
+            &in_arena[..],
+            &create_text_borders(&messages, Some(options))[..]
+        );
+    }
+}
@@ -0,0 +1,366 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use unicode_border::{
+    AmbiguousWidth, BorderStyle, Color, ColorMode, ColorSupport, Sides, TextBorderOptions, Width,
+};
+
+/// The name of the configuration file, relative to each XDG configuration directory.
+const CONFIG_FILE: &str = "unicode_border/config.toml";
+
+/// `Config` is the contents of a configuration file. Every key is optional, and keys that are
+/// left out keep their default value.
+///
+/// # Examples
+///
+/// ```toml
+/// border_style = "weighted"
+/// border_thickness = [1, 1, 1, 2]
+/// margin_thickness = 1
+/// border_color = "#5f87d7"
+/// title = "notes"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Config {
+    border_char: Option<char>,
+    border_style: Option<String>,
+    border_thickness: Option<Thickness>,
+    margin_thickness: Option<Thickness>,
+    prevent_trim: Option<bool>,
+    border_color: Option<String>,
+    background: Option<String>,
+    color_mode: Option<String>,
+    color_support: Option<String>,
+    title: Option<String>,
+    title_badge: Option<String>,
+    badge_color: Option<String>,
+    footer: Option<String>,
+    footer_status: Option<String>,
+    normalize: Option<bool>,
+    wrap_width: Option<usize>,
+    ambiguous_width: Option<String>,
+    target_width: Option<TargetWidth>,
+}
+
+/// A thickness given either once for every side, or in CSS order as `[top, right, bottom, left]`
+/// or as a shorthand string such as `"0 2"`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Thickness {
+    All(usize),
+    Sides([usize; 4]),
+    Shorthand(String),
+}
+
+impl Thickness {
+    fn sides(self) -> Result<Sides<usize>, String> {
+        match self {
+            Thickness::All(n) => Ok(Sides::all(n)),
+            Thickness::Sides([top, right, bottom, left]) => {
+                Ok(Sides::new(left, top, right, bottom))
+            }
+            Thickness::Shorthand(sides) => sides
+                .parse()
+                .map_err(|err| format!("invalid thickness {:?}: {}", sides, err)),
+        }
+    }
+}
+
+/// A target width given either as a number of columns, as `"terminal"` or as a percentage of
+/// the terminal width such as `"80%"`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum TargetWidth {
+    Columns(usize),
+    Named(String),
+}
+
+impl TargetWidth {
+    fn width(self) -> Result<Width, String> {
+        match self {
+            TargetWidth::Columns(columns) => Ok(Width::Columns(columns)),
+            TargetWidth::Named(name) if name == "terminal" => Ok(Width::Terminal),
+            TargetWidth::Named(name) => name
+                .strip_suffix('%')
+                .and_then(|percent| percent.parse::<u8>().ok())
+                .map(Width::Percent)
+                .ok_or_else(|| {
+                    format!(
+                        "invalid target_width {:?}, expected a number of columns, \"terminal\" or a percentage such as \"80%\"",
+                        name
+                    )
+                }),
+        }
+    }
+}
+
+/// Returns the first configuration file found in the XDG configuration directories.
+pub fn find() -> Option<PathBuf> {
+    let non_empty = |name: &str| env::var_os(name).filter(|value| !value.is_empty());
+
+    let config_home = non_empty("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| Path::new(&home).join(".config")));
+    let config_dirs = non_empty("XDG_CONFIG_DIRS").unwrap_or_else(|| "/etc/xdg".into());
+
+    config_home
+        .into_iter()
+        .chain(env::split_paths(&config_dirs))
+        .map(|dir| dir.join(CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+/// Reads the configuration file at `path` into a set of options.
+pub fn load(path: &Path) -> Result<TextBorderOptions, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let config: Config =
+        toml::from_str(&contents).map_err(|err| format!("{}: {}", path.display(), err))?;
+
+    config
+        .into_options()
+        .map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+impl Config {
+    fn into_options(self) -> Result<TextBorderOptions, String> {
+        let mut builder = TextBorderOptions::builder();
+
+        if let Some(c) = self.border_char {
+            builder = builder.border_char(c);
+        }
+        if let Some(style) = self.border_style {
+            builder = builder.border_style(parse_border_style(&style)?);
+        }
+        if let Some(thickness) = self.border_thickness {
+            builder = builder.border_thickness(thickness.sides()?);
+        }
+        if let Some(thickness) = self.margin_thickness {
+            builder = builder.margin_thickness(thickness.sides()?);
+        }
+        if let Some(prevent_trim) = self.prevent_trim {
+            builder = builder.prevent_trim(prevent_trim);
+        }
+        if let Some(color) = self.border_color {
+            builder = builder.border_color(parse_color(&color)?);
+        }
+        if let Some(color) = self.background {
+            builder = builder.background(parse_color(&color)?);
+        }
+        if let Some(mode) = self.color_mode {
+            builder = builder.color_mode(parse_color_mode(&mode)?);
+        }
+        if let Some(support) = self.color_support {
+            builder = builder.color_support(parse_color_support(&support)?);
+        }
+        if let Some(title) = self.title {
+            builder = builder.title(title);
+        }
+        if let Some(badge) = self.title_badge {
+            builder = builder.title_badge(badge);
+        }
+        if let Some(color) = self.badge_color {
+            builder = builder.badge_color(parse_color(&color)?);
+        }
+        if let Some(footer) = self.footer {
+            builder = builder.footer(footer);
+        }
+        if let Some(status) = self.footer_status {
+            builder = builder.footer_status(status);
+        }
+        if let Some(normalize) = self.normalize {
+            builder = builder.normalize(normalize);
+        }
+        if let Some(width) = self.wrap_width {
+            builder = builder.wrap_width(width);
+        }
+        if let Some(width) = self.ambiguous_width {
+            builder = builder.ambiguous_width(parse_ambiguous_width(&width)?);
+        }
+        if let Some(width) = self.target_width {
+            builder = builder.target_width(width.width()?);
+        }
+
+        builder.build().map_err(|err| err.to_string())
+    }
+}
+
+fn parse_border_style(value: &str) -> Result<BorderStyle, String> {
+    match value {
+        "char" => Ok(BorderStyle::Char),
+        "half-block" => Ok(BorderStyle::HalfBlock),
+        "weighted" => Ok(BorderStyle::Weighted),
+        "braille" => Ok(BorderStyle::Braille),
+        "brackets" => Ok(BorderStyle::Brackets),
+        _ => Err(format!(
+            "unknown border_style {:?}, expected one of \"char\", \"half-block\", \"weighted\", \"braille\" or \"brackets\"",
+            value
+        )),
+    }
+}
+
+fn parse_color_mode(value: &str) -> Result<ColorMode, String> {
+    match value {
+        "auto" => Ok(ColorMode::Auto),
+        "always" => Ok(ColorMode::Always),
+        "never" => Ok(ColorMode::Never),
+        _ => Err(format!(
+            "unknown color_mode {:?}, expected one of \"auto\", \"always\" or \"never\"",
+            value
+        )),
+    }
+}
+
+fn parse_color_support(value: &str) -> Result<ColorSupport, String> {
+    match value {
+        "basic" => Ok(ColorSupport::Basic),
+        "ansi256" => Ok(ColorSupport::Ansi256),
+        "truecolor" => Ok(ColorSupport::TrueColor),
+        _ => Err(format!(
+            "unknown color_support {:?}, expected one of \"basic\", \"ansi256\" or \"truecolor\"",
+            value
+        )),
+    }
+}
+
+fn parse_ambiguous_width(value: &str) -> Result<AmbiguousWidth, String> {
+    match value {
+        "narrow" => Ok(AmbiguousWidth::Narrow),
+        "wide" => Ok(AmbiguousWidth::Wide),
+        _ => Err(format!(
+            "unknown ambiguous_width {:?}, expected \"narrow\" or \"wide\"",
+            value
+        )),
+    }
+}
+
+/// Parses a color name such as `"bright-blue"`, a 256-color palette index such as `"208"`, or
+/// an RGB value such as `"#ff8700"`.
+fn parse_color(value: &str) -> Result<Color, String> {
+    let named = match value {
+        "black" => Some(Color::Black),
+        "red" => Some(Color::Red),
+        "green" => Some(Color::Green),
+        "yellow" => Some(Color::Yellow),
+        "blue" => Some(Color::Blue),
+        "magenta" => Some(Color::Magenta),
+        "cyan" => Some(Color::Cyan),
+        "white" => Some(Color::White),
+        "bright-black" => Some(Color::BrightBlack),
+        "bright-red" => Some(Color::BrightRed),
+        "bright-green" => Some(Color::BrightGreen),
+        "bright-yellow" => Some(Color::BrightYellow),
+        "bright-blue" => Some(Color::BrightBlue),
+        "bright-magenta" => Some(Color::BrightMagenta),
+        "bright-cyan" => Some(Color::BrightCyan),
+        "bright-white" => Some(Color::BrightWhite),
+        _ => None,
+    };
+    if let Some(color) = named {
+        return Ok(color);
+    }
+
+    if let Ok(index) = value.parse::<u8>() {
+        return Ok(Color::Ansi256(index));
+    }
+
+    let hex = value
+        .strip_prefix('#')
+        .filter(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()));
+    if let Some(hex) = hex {
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap_or(0);
+        return Ok(Color::Rgb(channel(0), channel(2), channel(4)));
+    }
+
+    Err(format!(
+        "invalid color {:?}, expected a color name such as \"red\" or \"bright-blue\", a palette index from 0 to 255, or \"#rrggbb\"",
+        value
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(toml: &str) -> Result<TextBorderOptions, String> {
+        toml::from_str::<Config>(toml)
+            .map_err(|err| err.to_string())?
+            .into_options()
+    }
+
+    #[test]
+    fn thicknesses_are_read_in_every_form() {
+        let opts = options("border_thickness = 2\nmargin_thickness = [1, 2, 3, 4]").unwrap();
+        assert_eq!(opts.border_thickness, Sides::all(2));
+        assert_eq!(opts.margin_thickness, Sides::new(4, 1, 2, 3));
+
+        let opts = options("margin_thickness = \"1 2 3 4\"").unwrap();
+        assert_eq!(opts.margin_thickness, Sides::new(4, 1, 2, 3));
+        let opts = options("margin_thickness = \"0 2\"").unwrap();
+        assert_eq!(opts.margin_thickness, Sides::symmetric(2, 0));
+        assert!(options("margin_thickness = \"1 2 3 4 5\"")
+            .unwrap_err()
+            .starts_with("invalid thickness \"1 2 3 4 5\": "));
+    }
+
+    #[test]
+    fn colors_are_read_by_name_index_or_hex() {
+        assert_eq!(parse_color("bright-blue"), Ok(Color::BrightBlue));
+        assert_eq!(parse_color("208"), Ok(Color::Ansi256(208)));
+        assert_eq!(parse_color("#0a0B0c"), Ok(Color::Rgb(10, 11, 12)));
+        for invalid in ["256", "#12345", "#12345g", "purple"] {
+            assert!(parse_color(invalid)
+                .unwrap_err()
+                .starts_with("invalid color"));
+        }
+    }
+
+    #[test]
+    fn target_widths_are_columns_terminal_or_percentages() {
+        assert_eq!(
+            options("target_width = 40").unwrap().target_width,
+            Some(Width::Columns(40))
+        );
+        assert_eq!(
+            options("target_width = \"terminal\"").unwrap().target_width,
+            Some(Width::Terminal)
+        );
+        assert_eq!(
+            options("target_width = \"80%\"").unwrap().target_width,
+            Some(Width::Percent(80))
+        );
+        assert!(options("target_width = \"wide\"")
+            .unwrap_err()
+            .starts_with("invalid target_width \"wide\""));
+    }
+
+    #[test]
+    fn unknown_keys_and_values_are_rejected() {
+        assert!(options("border_colour = \"red\"")
+            .unwrap_err()
+            .contains("border_colour"));
+        assert!(options("border_style = \"round\"")
+            .unwrap_err()
+            .starts_with("unknown border_style \"round\""));
+        assert!(options("color_mode = \"sometimes\"")
+            .unwrap_err()
+            .starts_with("unknown color_mode"));
+    }
+
+    #[test]
+    fn options_are_validated_by_the_builder() {
+        assert!(options("border_char = \"\\n\"").is_err());
+    }
+
+    #[test]
+    fn load_errors_name_the_file() {
+        let path = Path::new("/nonexistent/unicode_border.toml");
+        assert!(load(path)
+            .unwrap_err()
+            .starts_with("/nonexistent/unicode_border.toml: "));
+    }
+}
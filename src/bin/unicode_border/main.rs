@@ -0,0 +1,205 @@
+extern crate serde;
+extern crate toml;
+extern crate unicode_border;
+
+mod config;
+
+use std::env;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process;
+
+use unicode_border::{box_stream, create_text_border, Sides, Table, TextBorderOptions};
+
+const USAGE: &str = "\
+Usage: unicode_border [options] [message...]
+
+Surrounds the message, or standard input if no message is given, with a border.
+
+Options:
+  -c, --config <path>  Read border options from a TOML file. Defaults to the first
+                       unicode_border/config.toml found in $XDG_CONFIG_HOME (or
+                       ~/.config) and then $XDG_CONFIG_DIRS (or /etc/xdg).
+      --no-config      Ignore any configuration file and use the default options.
+      --border <sides> Set the border thickness, overriding the configuration file.
+      --margin <sides> Set the margin thickness, overriding the configuration file.
+                       Sides are given in CSS order: \"all\", \"vertical horizontal\",
+                       \"top horizontal bottom\" or \"top right bottom left\", such as
+                       --margin \"0 2\".
+      --csv            Read standard input as CSV and print it as a table, taking
+                       the first record as the header.
+  -h, --help           Print this help and exit.";
+
+/// The parsed command line.
+struct Args {
+    config: Option<PathBuf>,
+    no_config: bool,
+    border: Option<Sides<usize>>,
+    margin: Option<Sides<usize>>,
+    csv: bool,
+    message: Vec<String>,
+}
+
+/// Parses the arguments following the name of the program.
+fn parse_args(mut argv: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut args = Args {
+        config: None,
+        no_config: false,
+        border: None,
+        margin: None,
+        csv: false,
+        message: Vec::new(),
+    };
+
+    while let Some(arg) = argv.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            "-c" | "--config" => {
+                let path = argv
+                    .next()
+                    .ok_or_else(|| format!("{} requires a path", arg))?;
+                args.config = Some(PathBuf::from(path));
+            }
+            "--no-config" => args.no_config = true,
+            "--border" | "--margin" => {
+                let value = argv
+                    .next()
+                    .ok_or_else(|| format!("{} requires sides", arg))?;
+                let sides = parse_sides(&arg, &value)?;
+                if arg == "--border" {
+                    args.border = Some(sides);
+                } else {
+                    args.margin = Some(sides);
+                }
+            }
+            "--csv" => args.csv = true,
+            "--" => {
+                args.message.extend(argv.by_ref());
+            }
+            _ if arg.starts_with("--config=") => {
+                args.config = Some(PathBuf::from(&arg["--config=".len()..]));
+            }
+            _ if arg.starts_with("--border=") => {
+                args.border = Some(parse_sides("--border", &arg["--border=".len()..])?);
+            }
+            _ if arg.starts_with("--margin=") => {
+                args.margin = Some(parse_sides("--margin", &arg["--margin=".len()..])?);
+            }
+            _ if arg.starts_with('-') && arg != "-" => {
+                return Err(format!("unknown option {:?}\n\n{}", arg, USAGE));
+            }
+            _ => args.message.push(arg),
+        }
+    }
+
+    Ok(args)
+}
+
+fn parse_sides(option: &str, value: &str) -> Result<Sides<usize>, String> {
+    value
+        .parse()
+        .map_err(|err| format!("invalid {} {:?}: {}", option, value, err))
+}
+
+fn run() -> Result<(), String> {
+    let args = parse_args(env::args().skip(1))?;
+
+    let mut options = match (args.no_config, args.config) {
+        (true, _) => TextBorderOptions::default(),
+        (false, Some(path)) => config::load(&path)?,
+        (false, None) => match config::find() {
+            Some(path) => config::load(&path)?,
+            None => TextBorderOptions::default(),
+        },
+    };
+    if let Some(border) = args.border {
+        options.border_thickness = border;
+    }
+    if let Some(margin) = args.margin {
+        options.margin_thickness = margin;
+    }
+
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    let result = if args.csv {
+        let stdin = io::stdin();
+        let table = Table::from_csv(stdin.lock()).map_err(|err| err.to_string())?;
+        writeln!(stdout, "{}", table.render(Some(options)))
+    } else if args.message.is_empty() {
+        let stdin = io::stdin();
+        box_stream(stdin.lock(), &mut stdout, Some(options))
+    } else {
+        let message = args.message.join(" ");
+        writeln!(stdout, "{}", create_text_border(&message, Some(options)))
+    };
+
+    match result {
+        Err(err) if err.kind() != io::ErrorKind::BrokenPipe => Err(err.to_string()),
+        _ => Ok(()),
+    }
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("unicode_border: {}", err);
+        process::exit(2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn config_paths_are_read_in_both_forms() {
+        assert_eq!(
+            parse(&["-c", "a.toml"]).unwrap().config,
+            Some("a.toml".into())
+        );
+        assert_eq!(
+            parse(&["--config=b.toml"]).unwrap().config,
+            Some("b.toml".into())
+        );
+        assert!(parse(&["--no-config"]).unwrap().no_config);
+        assert_eq!(
+            parse(&["--config"]).err().unwrap(),
+            "--config requires a path"
+        );
+    }
+
+    #[test]
+    fn options_after_a_double_dash_are_part_of_the_message() {
+        let args = parse(&["hello", "--", "--csv", "-"]).unwrap();
+        assert_eq!(args.message, ["hello", "--csv", "-"]);
+        assert!(!args.csv);
+        assert_eq!(parse(&["-"]).unwrap().message, ["-"]);
+    }
+
+    #[test]
+    fn unknown_options_are_rejected_with_the_usage() {
+        let err = parse(&["--colour"]).err().unwrap();
+        assert!(err.starts_with("unknown option \"--colour\"\n\nUsage: "));
+    }
+
+    #[test]
+    fn sides_are_read_in_css_order_in_both_forms() {
+        let args = parse(&["--margin", "0 2", "--border=1 2 3 4"]).unwrap();
+        assert_eq!(args.margin, Some(Sides::symmetric(2, 0)));
+        assert_eq!(args.border, Some(Sides::new(4, 1, 2, 3)));
+        assert_eq!(
+            parse(&["--border"]).err().unwrap(),
+            "--border requires sides"
+        );
+        assert!(parse(&["--margin=a"])
+            .err()
+            .unwrap()
+            .starts_with("invalid --margin \"a\": "));
+    }
+}
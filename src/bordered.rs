@@ -0,0 +1,93 @@
+use crate::{create_text_border, default_options, TextBorderOptionsBuilder};
+
+/// `Bordered` surrounds text with a border in a method call, such as
+/// `"Hello".bordered()`. It is implemented for every type that can be viewed as a `str`,
+/// including `str` and `String`.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{Bordered, BorderStyle};
+///
+/// assert_eq!("Hi".bordered(), "****\n*Hi*\n****");
+///
+/// let boxed = "msg".bordered_with(|o| {
+///     o.border_style(BorderStyle::Weighted)
+///         .margin_thickness(1)
+///         .title("Hi")
+/// });
+///
+/// assert_eq!(boxed, "┌─┤ Hi ├─┐\n│        │\n│ msg    │\n│        │\n└────────┘");
+/// ```
+pub trait Bordered {
+    /// Creates a string containing the text surrounded by a border and margin, with the options
+    /// returned by `default_options`.
+    fn bordered(&self) -> String;
+
+    /// Creates a string containing the text surrounded by a border and margin, with the options
+    /// returned by `default_options` customized by `configure`.
+    ///
+    /// The options are used as `configure` returns them, as by `create_text_border`: they are
+    /// not validated by `TextBorderOptionsBuilder::build`.
+    ///
+    /// # Arguments
+    ///
+    /// * `configure` - A closure receiving a `TextBorderOptionsBuilder` starting from the
+    ///   default options, and returning it with its customizations.
+    ///
+    /// # Returns
+    ///
+    /// * A `String` containing the text surrounded by the specified border and margin.
+    fn bordered_with<F>(&self, configure: F) -> String
+    where
+        F: FnOnce(TextBorderOptionsBuilder) -> TextBorderOptionsBuilder;
+}
+
+impl<T: AsRef<str> + ?Sized> Bordered for T {
+    fn bordered(&self) -> String {
+        create_text_border(self.as_ref(), None)
+    }
+
+    fn bordered_with<F>(&self, configure: F) -> String
+    where
+        F: FnOnce(TextBorderOptionsBuilder) -> TextBorderOptionsBuilder,
+    {
+        let builder = TextBorderOptionsBuilder::from_options(default_options());
+        create_text_border(self.as_ref(), Some(configure(builder).into_options()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoped_default_options;
+    use crate::TextBorderOptions;
+
+    #[test]
+    fn str_and_string_are_bordered_alike() {
+        assert_eq!("Hi".bordered(), String::from("Hi").bordered());
+        assert_eq!("".bordered(), "**\n**\n**");
+    }
+
+    #[test]
+    fn bordered_with_starts_from_the_defaults() {
+        let _defaults = scoped_default_options(TextBorderOptions {
+            border_char: '#',
+            ..Default::default()
+        });
+        assert_eq!("Hi".bordered(), "####\n#Hi#\n####");
+        assert_eq!("Hi".bordered_with(|o| o), "####\n#Hi#\n####");
+        assert_eq!(
+            "Hi".bordered_with(|o| o.margin_thickness(1)),
+            "######\n#    #\n# Hi #\n#    #\n######"
+        );
+    }
+
+    #[test]
+    fn bordered_with_does_not_validate_the_options() {
+        assert_eq!(
+            "Hi".bordered_with(|o| o.border_char('\n')),
+            "\n\n\n\n\n\nHi\n\n\n\n\n\n"
+        );
+    }
+}
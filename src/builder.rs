@@ -0,0 +1,814 @@
+use std::ops::Range;
+
+use crate::width::is_printable;
+use crate::{
+    AmbiguousWidth, BlankLines, BorderLayers, BorderSpec, BorderStyle, Charset, Color, ColorMode,
+    ColorSupport, ConfigError, ContentSpec, LimitAction, Limits, Pattern, Sides, SpacingSpec,
+    StatusBar, Style, TextBorderOptions, Theme, Width, MAX_THICKNESS,
+};
+
+/// `TextBorderOptionsBuilder` builds a `TextBorderOptions`, starting from the defaults and
+/// validating the result.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, TextBorderOptions};
+///
+/// let options = TextBorderOptions::builder()
+///     .border_char('#')
+///     .margin_thickness((1, 0, 1, 0))
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(create_text_border("Hi", Some(options)), "######\n# Hi #\n######");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TextBorderOptionsBuilder {
+    options: TextBorderOptions,
+}
+
+impl TextBorderOptions {
+    /// Returns a `TextBorderOptionsBuilder` starting from `TextBorderOptions::default()`.
+    pub fn builder() -> TextBorderOptionsBuilder {
+        TextBorderOptionsBuilder::default()
+    }
+
+    /// Checks that the options can be rendered as configured. Rendering never fails, clamping
+    /// and ignoring what does not fit instead, so this reports options that would not render as
+    /// intended.
+    ///
+    /// # Errors
+    ///
+    /// * `ConfigError::InvalidBorderChar` - The border character, a border layer character or a
+    ///   corner character is not printable: a control character such as `'\n'` or `'\t'`, or a
+    ///   zero-width character such as a combining mark.
+    /// * `ConfigError::InvalidChar` - A character of `margin_pattern` is not printable, or a
+    ///   label, such as `title`, `footer`, a tab, a status bar segment or a key hint, holds a
+    ///   control character such as `'\n'`.
+    /// * `ConfigError::ThicknessTooLarge` - A border or margin thickness is larger than
+    ///   `MAX_THICKNESS`.
+    /// * `ConfigError::LimitExceeded` - A border or margin thickness is larger than allowed by
+    ///   `limits`, and their `on_exceed` action is `LimitAction::Error`.
+    /// * `ConfigError::ZeroWidth` - `wrap_width`, `max_width` or `target_width` is 0 columns.
+    /// * `ConfigError::ConflictingWidths` - `min_width` is larger than `max_width` or
+    ///   `target_width`, when both are known without a terminal.
+    /// * `ConfigError::InvalidAspectRatio` - `aspect_ratio` is not a finite number larger than 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_border::{ConfigError, TextBorderOptions, Width};
+    ///
+    /// let options = TextBorderOptions {
+    ///     min_width: Some(Width::Columns(40)),
+    ///     max_width: Some(Width::PercentOf(50, 60)),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     options.validate(),
+    ///     Err(ConfigError::ConflictingWidths { min: 40, max: 30 })
+    /// );
+    /// assert_eq!(
+    ///     TextBorderOptions::builder().wrap_width(0).build().unwrap_err(),
+    ///     ConfigError::ZeroWidth("wrap_width")
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut chars = vec![self.border_char];
+        match self.border_style {
+            BorderStyle::Corners(top_left, top_right, bottom_left, bottom_right) => {
+                chars.extend([top_left, top_right, bottom_left, bottom_right].iter());
+            }
+            BorderStyle::Custom(custom) => chars.extend(custom.to_array().iter()),
+            _ => {}
+        }
+        if let Some(layers) = &self.border_layers {
+            chars.extend(layers.left.iter().chain(&layers.top));
+            chars.extend(layers.right.iter().chain(&layers.bottom));
+        }
+        if let Some(&c) = chars.iter().find(|&&c| !is_printable(c)) {
+            return Err(ConfigError::InvalidBorderChar(c));
+        }
+        let pattern_chars = match &self.margin_pattern {
+            Some(Pattern::Checkerboard(even, odd)) => vec![*even, *odd],
+            Some(Pattern::Rows(chars)) => chars.clone(),
+            None => Vec::new(),
+        };
+        if let Some(&c) = pattern_chars.iter().find(|&&c| !is_printable(c)) {
+            return Err(ConfigError::InvalidChar {
+                option: "margin_pattern",
+                c,
+            });
+        }
+        self.validate_labels()?;
+
+        let (border, margin) = (self.border_thickness, self.margin_thickness);
+        let thicknesses = [
+            border.left,
+            border.top,
+            border.right,
+            border.bottom,
+            margin.left,
+            margin.top,
+            margin.right,
+            margin.bottom,
+        ];
+        if let Some(&thickness) = thicknesses.iter().find(|&&t| t > MAX_THICKNESS) {
+            return Err(ConfigError::ThicknessTooLarge(thickness));
+        }
+        if self.limits.on_exceed == LimitAction::Error {
+            self.limits.check_thickness(border, margin)?;
+        }
+
+        if self.wrap_width == Some(0) {
+            return Err(ConfigError::ZeroWidth("wrap_width"));
+        }
+        let widths = [
+            ("max_width", self.max_width),
+            ("target_width", self.target_width),
+        ];
+        for &(option, width) in &widths {
+            if width.and_then(fixed_columns) == Some(0) {
+                return Err(ConfigError::ZeroWidth(option));
+            }
+        }
+        if let Some(min) = self.min_width.and_then(fixed_columns) {
+            let max = widths
+                .iter()
+                .filter_map(|&(_, width)| width.and_then(fixed_columns))
+                .min();
+            if let Some(max) = max.filter(|&max| min > max) {
+                return Err(ConfigError::ConflictingWidths { min, max });
+            }
+        }
+
+        if self
+            .aspect_ratio
+            .is_some_and(|ratio| !ratio.is_finite() || ratio <= 0.0)
+        {
+            return Err(ConfigError::InvalidAspectRatio);
+        }
+
+        Ok(())
+    }
+}
+
+impl TextBorderOptions {
+    /// Checks that the labels set into the border and its rows hold no control characters,
+    /// such as `'\n'`, which would break the frame apart.
+    fn validate_labels(&self) -> Result<(), ConfigError> {
+        let mut labels: Vec<(&'static str, &str)> = Vec::new();
+        let optional = [
+            ("title", &self.title),
+            ("title_icon", &self.title_icon),
+            ("title_badge", &self.title_badge),
+            ("footer", &self.footer),
+            ("footer_status", &self.footer_status),
+            ("header", &self.header),
+            ("header_controls", &self.header_controls),
+        ];
+        for &(option, label) in &optional {
+            labels.extend(label.as_deref().map(|label| (option, label)));
+        }
+        labels.extend(self.tabs.iter().map(|tab| ("tabs", tab.as_str())));
+        if let Some(status_bar) = &self.status_bar {
+            for segment in &[&status_bar.left, &status_bar.center, &status_bar.right] {
+                labels.push(("status_bar", segment.as_str()));
+            }
+        }
+        for (key, action) in &self.key_hints {
+            labels.push(("key_hints", key.as_str()));
+            labels.push(("key_hints", action.as_str()));
+        }
+
+        for (option, label) in labels {
+            if let Some(c) = label.chars().find(|c| c.is_control()) {
+                return Err(ConfigError::InvalidChar { option, c });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TextBorderOptionsBuilder {
+    /// Creates a builder starting from `options`.
+    pub(crate) fn from_options(options: TextBorderOptions) -> Self {
+        TextBorderOptionsBuilder { options }
+    }
+
+    /// Returns the options built, without validating them.
+    pub(crate) fn into_options(self) -> TextBorderOptions {
+        self.options
+    }
+
+    /// Sets the character used to create the border when the style is `BorderStyle::Char`.
+    pub fn border_char(mut self, border_char: char) -> Self {
+        self.options.border_char = border_char;
+        self
+    }
+
+    /// Sets the `BorderStyle` selecting the characters used to draw the border.
+    pub fn border_style(mut self, border_style: BorderStyle) -> Self {
+        self.options.border_style = border_style;
+        self
+    }
+
+    /// Sets the border thickness of each side, from `Sides`, a tuple in the order (left, top,
+    /// right, bottom) or a single thickness for every side.
+    pub fn border_thickness(mut self, border_thickness: impl Into<Sides<usize>>) -> Self {
+        self.options.border_thickness = border_thickness.into();
+        self
+    }
+
+    /// Sets the margin thickness of each side, from `Sides`, a tuple in the order (left, top,
+    /// right, bottom) or a single thickness for every side.
+    pub fn margin_thickness(mut self, margin_thickness: impl Into<Sides<usize>>) -> Self {
+        self.options.margin_thickness = margin_thickness.into();
+        self
+    }
+
+    /// Sets the fields of the `BorderSpec` of the options.
+    pub fn border(mut self, border: BorderSpec) -> Self {
+        self.options = self.options.with_border(border);
+        self
+    }
+
+    /// Sets the fields of the `SpacingSpec` of the options.
+    pub fn spacing(mut self, spacing: SpacingSpec) -> Self {
+        self.options = self.options.with_spacing(spacing);
+        self
+    }
+
+    /// Sets the fields of the `ContentSpec` of the options.
+    pub fn content(mut self, content: ContentSpec) -> Self {
+        self.options = self.options.with_content(content);
+        self
+    }
+
+    /// Sets whether to prevent trimming whitespace from the message.
+    pub fn prevent_trim(mut self, prevent_trim: bool) -> Self {
+        self.options.prevent_trim = prevent_trim;
+        self
+    }
+
+    /// Sets the `Color` of the border characters.
+    pub fn border_color(mut self, border_color: Color) -> Self {
+        self.options.border_color = Some(border_color);
+        self
+    }
+
+    /// Sets the `Style` of the border characters, adding attributes such as `dim` to its color.
+    pub fn border_attributes(mut self, border_attributes: Style) -> Self {
+        self.options.border_attributes = Some(border_attributes);
+        self
+    }
+
+    /// Sets the `Color` painted behind the margins and message.
+    pub fn background(mut self, background: Color) -> Self {
+        self.options.background = Some(background);
+        self
+    }
+
+    /// Sets the `ColorMode` deciding whether colors are written.
+    pub fn color_mode(mut self, color_mode: ColorMode) -> Self {
+        self.options.color_mode = color_mode;
+        self
+    }
+
+    /// Sets the colors the output terminal can display instead of detecting them.
+    pub fn color_support(mut self, color_support: ColorSupport) -> Self {
+        self.options.color_support = Some(color_support);
+        self
+    }
+
+    /// Sets the `Charset` borders are drawn with.
+    pub fn charset(mut self, charset: Charset) -> Self {
+        self.options.charset = charset;
+        self
+    }
+
+    /// Sets the title set into the top border.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.options.title = Some(title.into());
+        self
+    }
+
+    /// Sets the `Style` of the title, in place of the title style of the theme.
+    pub fn title_style(mut self, title_style: Style) -> Self {
+        self.options.title_style = Some(title_style);
+        self
+    }
+
+    /// Sets the badge shown after the title.
+    pub fn title_badge(mut self, title_badge: impl Into<String>) -> Self {
+        self.options.title_badge = Some(title_badge.into());
+        self
+    }
+
+    /// Sets the `Color` of the title badge.
+    pub fn badge_color(mut self, badge_color: Color) -> Self {
+        self.options.badge_color = Some(badge_color);
+        self
+    }
+
+    /// Sets the left-aligned label set into the bottom border.
+    pub fn footer(mut self, footer: impl Into<String>) -> Self {
+        self.options.footer = Some(footer.into());
+        self
+    }
+
+    /// Sets the right-aligned status segment set into the bottom border.
+    pub fn footer_status(mut self, footer_status: impl Into<String>) -> Self {
+        self.options.footer_status = Some(footer_status.into());
+        self
+    }
+
+    /// Sets whether to normalize the message to Unicode Normalization Form C.
+    pub fn normalize(mut self, normalize: bool) -> Self {
+        self.options.normalize = normalize;
+        self
+    }
+
+    /// Sets the maximum width, in columns, of the message before lines are wrapped.
+    pub fn wrap_width(mut self, wrap_width: usize) -> Self {
+        self.options.wrap_width = Some(wrap_width);
+        self
+    }
+
+    /// Sets how many columns East Asian Ambiguous characters are measured as.
+    pub fn ambiguous_width(mut self, ambiguous_width: AmbiguousWidth) -> Self {
+        self.options.ambiguous_width = ambiguous_width;
+        self
+    }
+
+    /// Sets the `Limits` capping the thicknesses and the overall size of the box.
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.options.limits = limits;
+        self
+    }
+
+    /// Sets the `Width` the whole box should fit in, shrinking it if needed.
+    pub fn target_width(mut self, target_width: Width) -> Self {
+        self.options.target_width = Some(target_width);
+        self
+    }
+
+    /// Sets the minimum `Width` of the whole box, padding narrower messages.
+    pub fn min_width(mut self, min_width: Width) -> Self {
+        self.options.min_width = Some(min_width);
+        self
+    }
+
+    /// Sets the maximum `Width` of the whole box, wrapping longer lines.
+    pub fn max_width(mut self, max_width: Width) -> Self {
+        self.options.max_width = Some(max_width);
+        self
+    }
+
+    /// Sets the ratio of the width of the box to its height that the margins are grown to match.
+    pub fn aspect_ratio(mut self, aspect_ratio: f64) -> Self {
+        self.options.aspect_ratio = Some(aspect_ratio);
+        self
+    }
+
+    /// Sets whether spaces and tabs are made visible and trailing whitespace highlighted.
+    pub fn show_invisibles(mut self, show_invisibles: bool) -> Self {
+        self.options.show_invisibles = show_invisibles;
+        self
+    }
+
+    /// Adds a byte range of the message to highlight with `style`.
+    pub fn highlight(mut self, range: Range<usize>, style: Style) -> Self {
+        self.options.highlights.push((range, style));
+        self
+    }
+
+    /// Sets the characters of each border layer per side, in the order (left, top, right,
+    /// bottom), outermost first.
+    pub fn border_layers(mut self, border_layers: BorderLayers) -> Self {
+        self.options.border_layers = Some(border_layers);
+        self
+    }
+
+    /// Sets the pattern the margins are filled with.
+    pub fn margin_pattern(mut self, margin_pattern: Pattern) -> Self {
+        self.options.margin_pattern = Some(margin_pattern);
+        self
+    }
+
+    /// Sets the header row set inside the box, above the message.
+    pub fn header(mut self, header: impl Into<String>) -> Self {
+        self.options.header = Some(header.into());
+        self
+    }
+
+    /// Sets the decorations aligned right in the header row.
+    pub fn header_controls(mut self, header_controls: impl Into<String>) -> Self {
+        self.options.header_controls = Some(header_controls.into());
+        self
+    }
+
+    /// Adds a tab set into the top border.
+    pub fn tab(mut self, tab: impl Into<String>) -> Self {
+        self.options.tabs.push(tab.into());
+        self
+    }
+
+    /// Sets the index of the displayed tab.
+    pub fn active_tab(mut self, active_tab: usize) -> Self {
+        self.options.active_tab = Some(active_tab);
+        self
+    }
+
+    /// Sets the status bar row inside the bottom border.
+    pub fn status_bar(mut self, status_bar: StatusBar) -> Self {
+        self.options.status_bar = Some(status_bar);
+        self
+    }
+
+    /// Sets the key hints shown in a row inside the bottom border.
+    pub fn key_hints(mut self, key_hints: Vec<(String, String)>) -> Self {
+        self.options.key_hints = key_hints;
+        self
+    }
+
+    /// Sets whether blank lines of the message are kept, collapsed or removed.
+    pub fn blank_lines(mut self, blank_lines: BlankLines) -> Self {
+        self.options.blank_lines = blank_lines;
+        self
+    }
+
+    /// Sets whether the leading whitespace common to every line of the message is stripped.
+    pub fn dedent(mut self, dedent: bool) -> Self {
+        self.options.dedent = dedent;
+        self
+    }
+
+    /// Enables code mode, expanding tabs to tab stops every `tab_width` columns.
+    pub fn code_block(mut self, tab_width: usize) -> Self {
+        self.options.code_block = Some(tab_width);
+        self
+    }
+
+    /// Sets whether inline Markdown in the message is converted to styling.
+    pub fn markdown(mut self, markdown: bool) -> Self {
+        self.options.markdown = markdown;
+        self
+    }
+
+    /// Sets whether lines of a unified diff are colored by their prefix.
+    pub fn diff(mut self, diff: bool) -> Self {
+        self.options.diff = diff;
+        self
+    }
+
+    /// Sets the number of spaces nested JSON values are indented by.
+    pub fn json_indent(mut self, json_indent: usize) -> Self {
+        self.options.json_indent = json_indent;
+        self
+    }
+
+    /// Sets the theme styling the parts of the box by their role.
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.options.theme = Some(theme);
+        self
+    }
+
+    /// Sets the icon shown before the title.
+    pub fn title_icon(mut self, title_icon: impl Into<String>) -> Self {
+        self.options.title_icon = Some(title_icon.into());
+        self
+    }
+
+    /// Sets the number of columns the message is flowed into.
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.options.columns = columns;
+        self
+    }
+
+    /// Sets the number of spaces between columns.
+    pub fn column_gap(mut self, column_gap: usize) -> Self {
+        self.options.column_gap = column_gap;
+        self
+    }
+
+    /// Sets whether boxes are rendered in plain mode, with textual markers for screen readers.
+    pub fn plain(mut self, plain: bool) -> Self {
+        self.options.plain = plain;
+        self
+    }
+
+    /// Validates the configured options with `TextBorderOptions::validate` and returns them.
+    ///
+    /// # Errors
+    ///
+    /// * The first `ConfigError` found by `TextBorderOptions::validate`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_border::{ConfigError, Sides, TextBorderOptions};
+    ///
+    /// let result = TextBorderOptions::builder().border_char('\n').build();
+    ///
+    /// assert_eq!(result.unwrap_err(), ConfigError::InvalidBorderChar('\n'));
+    ///
+    /// let result = TextBorderOptions::builder()
+    ///     .margin_thickness(Sides::new(1, 0, usize::MAX, 0))
+    ///     .build();
+    ///
+    /// assert_eq!(result.unwrap_err(), ConfigError::ThicknessTooLarge(usize::MAX));
+    /// ```
+    pub fn build(self) -> Result<TextBorderOptions, ConfigError> {
+        self.options.validate()?;
+        Ok(self.options)
+    }
+}
+
+/// Returns the number of columns `width` stands for, if it does not depend on the terminal.
+fn fixed_columns(width: Width) -> Option<usize> {
+    match width {
+        Width::Terminal | Width::Percent(_) => None,
+        width => width.resolve(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BorderLayers, BorderStyle, ConfigError, Dimension, LimitAction, Limits, Pattern, Sides,
+        StatusBar, TextBorderOptions, Width, MAX_THICKNESS,
+    };
+
+    fn invalid_char(option: &'static str, c: char) -> Result<(), ConfigError> {
+        Err(ConfigError::InvalidChar { option, c })
+    }
+
+    #[test]
+    fn rejects_unprintable_border_chars() {
+        for &c in &['\n', '\t', '\x1b', '\u{301}', '\u{200b}'] {
+            assert_eq!(
+                TextBorderOptions::builder()
+                    .border_char(c)
+                    .build()
+                    .unwrap_err(),
+                ConfigError::InvalidBorderChar(c)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unprintable_corners_and_layers() {
+        let error = TextBorderOptions::builder()
+            .border_style(BorderStyle::Corners('+', '+', '\r', '+'))
+            .build()
+            .unwrap_err();
+        assert_eq!(error, ConfigError::InvalidBorderChar('\r'));
+        assert!(error.to_string().contains("U+000D"));
+
+        let layers: BorderLayers = Sides {
+            bottom: vec!['=', '\u{7}'],
+            ..Sides::all(vec!['#'])
+        };
+        assert_eq!(
+            TextBorderOptions::builder()
+                .border_layers(layers)
+                .build()
+                .unwrap_err(),
+            ConfigError::InvalidBorderChar('\u{7}')
+        );
+    }
+
+    #[test]
+    fn accepts_printable_border_chars() {
+        for &c in &['#', '█', '═', '中'] {
+            let options = TextBorderOptions::builder().border_char(c).build().unwrap();
+            assert_eq!(options.border_char, c);
+        }
+    }
+
+    #[test]
+    fn rejects_unprintable_margin_patterns() {
+        let options = TextBorderOptions {
+            margin_pattern: Some(Pattern::Checkerboard('░', '\t')),
+            ..Default::default()
+        };
+        assert_eq!(options.validate(), invalid_char("margin_pattern", '\t'));
+
+        let options = TextBorderOptions {
+            margin_pattern: Some(Pattern::Rows(vec!['.', '\u{301}'])),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.validate(),
+            invalid_char("margin_pattern", '\u{301}')
+        );
+
+        let options = TextBorderOptions {
+            margin_pattern: Some(Pattern::Rows(vec!['.', '中'])),
+            ..Default::default()
+        };
+        assert_eq!(options.validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_control_characters_in_labels() {
+        let cases: Vec<(&str, TextBorderOptions)> = vec![
+            (
+                "title",
+                TextBorderOptions {
+                    title: Some("two\nlines".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "title_badge",
+                TextBorderOptions {
+                    title_badge: Some("\r3".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "footer_status",
+                TextBorderOptions {
+                    footer_status: Some("1.2s\n".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "tabs",
+                TextBorderOptions {
+                    tabs: vec!["ok".to_string(), "bad\n".to_string()],
+                    ..Default::default()
+                },
+            ),
+            (
+                "status_bar",
+                TextBorderOptions {
+                    status_bar: Some(StatusBar {
+                        center: "a\nb".to_string(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            ),
+            (
+                "key_hints",
+                TextBorderOptions {
+                    key_hints: vec![("q".to_string(), "quit\n".to_string())],
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        for (option, options) in cases {
+            match options.validate() {
+                Err(ConfigError::InvalidChar { option: found, .. }) => assert_eq!(found, option),
+                other => panic!("{}: expected InvalidChar, got {:?}", option, other),
+            }
+        }
+    }
+
+    #[test]
+    fn builder_reports_the_first_invalid_label() {
+        let error = TextBorderOptions::builder()
+            .title("a\tb")
+            .footer("c\nd")
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            ConfigError::InvalidChar {
+                option: "title",
+                c: '\t'
+            }
+        );
+        assert!(error.to_string().contains("in title"));
+    }
+
+    #[test]
+    fn accepts_printable_labels() {
+        let options = TextBorderOptions {
+            title: Some("Ünïcode ⚠\u{fe0f}".to_string()),
+            footer: Some("done".to_string()),
+            tabs: vec!["one".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(options.validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_thicknesses_over_the_maximum() {
+        let options = TextBorderOptions {
+            margin_thickness: Sides::new(0, 0, MAX_THICKNESS + 1, 0),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.validate(),
+            Err(ConfigError::ThicknessTooLarge(MAX_THICKNESS + 1))
+        );
+
+        let options = TextBorderOptions {
+            border_thickness: Sides::all(MAX_THICKNESS),
+            ..options
+        };
+        assert_eq!(
+            options.validate(),
+            Err(ConfigError::ThicknessTooLarge(MAX_THICKNESS + 1))
+        );
+    }
+
+    #[test]
+    fn checks_thickness_limits_only_when_they_are_errors() {
+        let limits = Limits {
+            max_border_thickness: 1,
+            ..Default::default()
+        };
+        let options = TextBorderOptions {
+            border_thickness: Sides::all(2),
+            limits,
+            ..Default::default()
+        };
+        assert_eq!(options.validate(), Ok(()));
+
+        let options = TextBorderOptions {
+            limits: Limits {
+                on_exceed: LimitAction::Error,
+                ..limits
+            },
+            ..options
+        };
+        assert_eq!(
+            options.validate(),
+            Err(ConfigError::LimitExceeded {
+                dimension: Dimension::BorderThickness,
+                value: 2,
+                max: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_zero_widths() {
+        let options = TextBorderOptions {
+            max_width: Some(Width::Columns(0)),
+            ..Default::default()
+        };
+        assert_eq!(options.validate(), Err(ConfigError::ZeroWidth("max_width")));
+
+        let options = TextBorderOptions {
+            target_width: Some(Width::PercentOf(10, 5)),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.validate(),
+            Err(ConfigError::ZeroWidth("target_width"))
+        );
+
+        let options = TextBorderOptions {
+            max_width: Some(Width::Percent(0)),
+            ..Default::default()
+        };
+        assert_eq!(options.validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_min_widths_larger_than_the_smallest_maximum() {
+        let options = TextBorderOptions {
+            min_width: Some(Width::Columns(20)),
+            max_width: Some(Width::Columns(30)),
+            target_width: Some(Width::Columns(10)),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.validate(),
+            Err(ConfigError::ConflictingWidths { min: 20, max: 10 })
+        );
+
+        let options = TextBorderOptions {
+            target_width: Some(Width::Terminal),
+            ..options
+        };
+        assert_eq!(options.validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_invalid_aspect_ratios() {
+        for &ratio in &[0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let options = TextBorderOptions {
+                aspect_ratio: Some(ratio),
+                ..Default::default()
+            };
+            assert_eq!(options.validate(), Err(ConfigError::InvalidAspectRatio));
+        }
+
+        let options = TextBorderOptions {
+            aspect_ratio: Some(0.5),
+            ..Default::default()
+        };
+        assert_eq!(options.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{default_options, ColumnAlignment, Style, Table, TextBorderOptions};
+
+/// The names of the months, from January.
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// The short names of the days of the week, from Monday.
+const WEEKDAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+/// `Calendar` is a month of the Gregorian calendar laid out as a grid of weeks inside a box,
+/// such as for planners and login screens.
+///
+/// # Fields
+///
+/// * `year` - The year of the month.
+/// * `month` - The month, from 1 for January to 12 for December. Other values are clamped to
+///   that range.
+/// * `highlight` - An optional day of the month to highlight.
+/// * `highlight_style` - The `Style` painting the highlighted day. Defaults to inverse video.
+///   The day is only highlighted when colors are enabled.
+/// * `sunday_first` - A boolean flag indicating whether weeks start on Sunday rather than
+///   Monday.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{BorderStyle, Calendar, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     Calendar::new(2024, 2).render(Some(options)),
+///     "┌─┤ February 2024 ├──┐\n\
+///      │Mo Tu We Th Fr Sa Su│\n\
+///      ├────────────────────┤\n\
+///      │          1  2  3  4│\n\
+///      │ 5  6  7  8  9 10 11│\n\
+///      │12 13 14 15 16 17 18│\n\
+///      │19 20 21 22 23 24 25│\n\
+///      │26 27 28 29         │\n\
+///      └────────────────────┘"
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Calendar {
+    pub year: i32,
+    pub month: u32,
+    pub highlight: Option<u32>,
+    pub highlight_style: Style,
+    pub sunday_first: bool,
+}
+
+impl Calendar {
+    /// Creates a calendar of `month` of `year`, without a highlighted day.
+    pub fn new(year: i32, month: u32) -> Self {
+        Calendar {
+            year,
+            month,
+            highlight: None,
+            highlight_style: Style {
+                inverse: true,
+                ..Default::default()
+            },
+            sunday_first: false,
+        }
+    }
+
+    /// Returns the calendar with `day` highlighted.
+    pub fn highlight(mut self, day: u32) -> Self {
+        self.highlight = Some(day);
+        self
+    }
+
+    /// Returns the grid of the calendar as a `Table`, with the days of the week as its header
+    /// and one row per week.
+    pub fn table(&self) -> Table {
+        let month = self.month.clamp(1, 12);
+        let mut header: Vec<String> = WEEKDAYS.iter().map(|day| day.to_string()).collect();
+        // Monday is 0, unless weeks start on Sunday.
+        let mut offset = (weekday(self.year, month, 1) + 6) % 7;
+        if self.sunday_first {
+            header.rotate_right(1);
+            offset = (offset + 1) % 7;
+        }
+
+        let mut cells = vec![String::new(); offset];
+        for day in 1..=days_in_month(self.year, month) {
+            let text = day.to_string();
+            cells.push(if self.highlight == Some(day) {
+                self.highlight_style.paint(&text)
+            } else {
+                text
+            });
+        }
+        let rows = cells.chunks(7).map(<[String]>::to_vec).collect();
+
+        Table {
+            header,
+            rows,
+            alignments: vec![ColumnAlignment::Right; 7],
+            column_gap: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Renders the calendar inside a border.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+    ///   configurations. If `None`, the options returned by `default_options` are used. If they
+    ///   have no title, the month and year are used as the title.
+    ///
+    /// # Returns
+    ///
+    /// * A `String` containing the calendar surrounded by the specified border and margin.
+    pub fn render(&self, options: Option<TextBorderOptions>) -> String {
+        let mut options = options.unwrap_or_else(default_options);
+        if options.title.is_none() {
+            let month = MONTHS[self.month.clamp(1, 12) as usize - 1];
+            options.title = Some(format!("{} {}", month, self.year));
+        }
+        self.table().render(Some(options))
+    }
+}
+
+/// Returns the day of the week of a date, from 0 for Sunday to 6 for Saturday.
+fn weekday(year: i32, month: u32, day: u32) -> usize {
+    const OFFSETS: [i64; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let year = i64::from(year) - i64::from(month < 3);
+    let days = year + year.div_euclid(4) - year.div_euclid(100)
+        + year.div_euclid(400)
+        + OFFSETS[month as usize - 1]
+        + i64::from(day);
+    days.rem_euclid(7) as usize
+}
+
+/// Returns the number of days in a month.
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weekdays_of_known_dates() {
+        // Sunday is 0.
+        assert_eq!(weekday(2024, 1, 1), 1);
+        assert_eq!(weekday(2000, 2, 29), 2);
+        assert_eq!(weekday(1970, 1, 1), 4);
+        assert_eq!(weekday(1, 1, 1), 1);
+        assert_eq!(weekday(0, 12, 31), 0);
+        assert_eq!(weekday(-1, 12, 31), 5);
+    }
+
+    #[test]
+    fn leap_years_follow_the_gregorian_rules() {
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2023, 2), 28);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(-4, 2), 29);
+        assert_eq!(days_in_month(2023, 4), 30);
+        assert_eq!(days_in_month(2023, 12), 31);
+    }
+
+    #[test]
+    fn the_first_week_is_offset_to_the_first_weekday() {
+        let table = Calendar::new(2024, 9).table();
+        assert_eq!(table.rows[0].len(), 7);
+        assert_eq!(table.rows[0][5], "");
+        assert_eq!(table.rows[0][6], "1");
+        assert_eq!(table.rows.last().unwrap(), &["30"]);
+
+        let table = Calendar {
+            sunday_first: true,
+            ..Calendar::new(2024, 9)
+        }
+        .table();
+        assert_eq!(table.header[0], "Su");
+        assert_eq!(table.rows[0][0], "1");
+    }
+
+    #[test]
+    fn months_out_of_range_are_clamped() {
+        let january = Calendar::new(2024, 0);
+        assert_eq!(january.table(), Calendar::new(2024, 1).table());
+        assert!(january.render(None).contains("January 2024"));
+        assert!(Calendar::new(2024, 99)
+            .render(None)
+            .contains("December 2024"));
+    }
+
+    #[test]
+    fn the_highlighted_day_is_painted() {
+        let table = Calendar::new(2024, 9).highlight(2).table();
+        assert_eq!(table.rows[1][0], "\x1b[7m2\x1b[27m");
+        assert!(Calendar::new(2024, 9)
+            .highlight(31)
+            .table()
+            .rows
+            .iter()
+            .flatten()
+            .all(|cell| !cell.contains('\x1b')));
+    }
+}
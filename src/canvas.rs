@@ -0,0 +1,228 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::{render_grid, Grid, TextBorderOptions};
+
+/// Something placed on a `Canvas`.
+#[derive(Clone, Debug)]
+enum Item {
+    Grid(Grid),
+    Text(String),
+}
+
+/// An item placed at column `x` of row `y`, stacked at depth `z`.
+#[derive(Clone, Debug)]
+struct Placement {
+    x: isize,
+    y: isize,
+    z: i32,
+    item: Item,
+}
+
+/// `Canvas` composites boxes and raw text placed at (x, y) coordinates into a single block of
+/// text, for simple dashboards without a TUI library.
+///
+/// Items are drawn in order of their z-index, set with `Canvas::with_z`, so items with a higher
+/// z-index cover those below where they overlap. Items with the same z-index are drawn in the
+/// order they were placed. Positions may be negative or extend past the edges of the canvas;
+/// whatever falls outside is clipped.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::Canvas;
+///
+/// let mut canvas = Canvas::new(11, 3);
+/// canvas.place_box(0, 0, "CPU", None);
+/// canvas.place_box(6, 0, "MEM", None);
+///
+/// assert_eq!(canvas.render(), "***** *****\n*CPU* *MEM*\n***** *****");
+/// ```
+///
+/// Overlapping a pop-over onto a background panel:
+///
+/// ```
+/// use unicode_border::Canvas;
+///
+/// let mut canvas = Canvas::new(8, 3);
+/// canvas.place_box(3, -1, "pop", None).with_z(1);
+/// canvas.place_box(0, 0, "panel!", None);
+///
+/// assert_eq!(canvas.render(), "****pop*\n*pa*****\n********");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    placements: Vec<Placement>,
+}
+
+impl Canvas {
+    /// Creates an empty canvas of `width` columns by `height` rows.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            placements: Vec::new(),
+        }
+    }
+
+    /// Returns the width of the canvas in columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the canvas in rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Places `message`, surrounded by a border as configured by `options`, with its top-left
+    /// corner at column `x` of row `y`.
+    pub fn place_box<'a>(
+        &mut self,
+        x: isize,
+        y: isize,
+        message: impl Into<Cow<'a, str>>,
+        options: Option<TextBorderOptions>,
+    ) -> &mut Self {
+        self.place_grid(x, y, render_grid(message, options))
+    }
+
+    /// Places a `Grid` with its top-left cell at column `x` of row `y`.
+    pub fn place_grid(&mut self, x: isize, y: isize, grid: Grid) -> &mut Self {
+        self.placements.push(Placement {
+            x,
+            y,
+            z: 0,
+            item: Item::Grid(grid),
+        });
+        self
+    }
+
+    /// Places raw text starting at column `x` of row `y`, each line of the text on its own row.
+    /// Unlike a grid, only the characters of the text are drawn, so whatever lies past the end
+    /// of a short line stays visible.
+    pub fn place_text<'a>(
+        &mut self,
+        x: isize,
+        y: isize,
+        text: impl Into<Cow<'a, str>>,
+    ) -> &mut Self {
+        self.placements.push(Placement {
+            x,
+            y,
+            z: 0,
+            item: Item::Text(text.into().into_owned()),
+        });
+        self
+    }
+
+    /// Sets the z-index of the most recently placed item. Items are placed at z-index 0.
+    pub fn with_z(&mut self, z: i32) -> &mut Self {
+        if let Some(placement) = self.placements.last_mut() {
+            placement.z = z;
+        }
+        self
+    }
+
+    /// Composites every placed item into a `Grid` the size of the canvas.
+    pub fn to_grid(&self) -> Grid {
+        let mut grid = Grid::new(self.width, self.height);
+
+        let mut placements: Vec<&Placement> = self.placements.iter().collect();
+        placements.sort_by_key(|placement| placement.z);
+
+        for placement in placements {
+            match &placement.item {
+                Item::Grid(item) => grid.draw(placement.x, placement.y, item),
+                Item::Text(text) => grid.draw_text(placement.x, placement.y, text),
+            }
+        }
+
+        grid
+    }
+
+    /// Composites every placed item and returns the canvas as a string, one line per row.
+    pub fn render(&self) -> String {
+        self.to_grid().to_string()
+    }
+}
+
+impl fmt::Display for Canvas {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boxes_and_text_are_composited_in_order() {
+        let mut canvas = Canvas::new(6, 3);
+        canvas.place_box(0, 0, "ab", None).place_text(2, 1, "XY");
+
+        assert_eq!(canvas.render(), "****  \n*aXY  \n****  ");
+    }
+
+    #[test]
+    fn multi_line_text_starts_each_line_at_the_same_column() {
+        let mut canvas = Canvas::new(4, 2);
+        canvas.place_text(1, 0, "ab\ncd\nef");
+
+        assert_eq!(canvas.render(), " ab \n cd ");
+    }
+
+    #[test]
+    fn an_empty_canvas_is_blank() {
+        assert_eq!(Canvas::new(2, 2).render(), "  \n  ");
+        assert_eq!(Canvas::new(0, 0).to_string(), "");
+    }
+
+    #[test]
+    fn higher_placements_are_drawn_on_top_and_ties_keep_their_order() {
+        let mut canvas = Canvas::new(3, 1);
+        canvas
+            .place_text(0, 0, "aaa")
+            .with_z(2)
+            .place_text(0, 0, "bb")
+            .with_z(1)
+            .place_text(1, 0, "c")
+            .with_z(2);
+
+        assert_eq!(canvas.render(), "aca");
+    }
+
+    #[test]
+    fn with_z_before_any_placement_is_ignored() {
+        let mut canvas = Canvas::new(1, 1);
+        canvas.with_z(5).place_text(0, 0, "x");
+
+        assert_eq!(canvas.render(), "x");
+    }
+
+    #[test]
+    fn placements_are_clipped_at_every_edge() {
+        let mut canvas = Canvas::new(3, 2);
+        canvas
+            .place_box(-2, -1, "ab", None)
+            .place_text(2, 1, "xyz")
+            .place_text(isize::MIN, isize::MAX, "far away");
+
+        assert_eq!(
+            canvas.render(),
+            "b* 
+**x"
+        );
+    }
+
+    #[test]
+    fn wide_characters_cut_by_an_edge_become_spaces() {
+        let mut canvas = Canvas::new(3, 1);
+        canvas.place_text(-1, 0, "中a中");
+
+        assert_eq!(canvas.render(), " a ");
+    }
+}
@@ -0,0 +1,245 @@
+use crate::ColorSupport;
+
+/// `Color` is a terminal color: one of the 16 standard ANSI colors, an index into the
+/// 256-color palette, or a 24-bit RGB value.
+///
+/// The actual shade of the 16 standard colors is chosen by the terminal's palette. Colors the
+/// terminal cannot display are downgraded to the closest color it can, see `Color::downgrade`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    Ansi256(u8),
+    Rgb(u8, u8, u8),
+}
+
+const BASIC_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+    Color::BrightBlack,
+    Color::BrightRed,
+    Color::BrightGreen,
+    Color::BrightYellow,
+    Color::BrightBlue,
+    Color::BrightMagenta,
+    Color::BrightCyan,
+    Color::BrightWhite,
+];
+
+/// The RGB values xterm uses for the 16 standard colors, used to pick the closest one.
+const BASIC_PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// The channel values of the 6x6x6 color cube of the 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl Color {
+    /// Returns the closest color that can be displayed with `support`.
+    ///
+    /// RGB colors are mapped onto the 256-color palette, and 256-color indexes onto the
+    /// 16 standard colors, by nearest RGB distance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_border::{Color, ColorSupport};
+    ///
+    /// assert_eq!(Color::Rgb(255, 0, 0).downgrade(ColorSupport::Ansi256), Color::Ansi256(196));
+    /// assert_eq!(Color::Rgb(255, 0, 0).downgrade(ColorSupport::Basic), Color::BrightRed);
+    /// assert_eq!(Color::Ansi256(4).downgrade(ColorSupport::Basic), Color::Blue);
+    /// ```
+    pub fn downgrade(self, support: ColorSupport) -> Color {
+        match (self, support) {
+            (Color::Rgb(r, g, b), ColorSupport::Ansi256) => Color::Ansi256(rgb_to_ansi256(r, g, b)),
+            (Color::Rgb(r, g, b), ColorSupport::Basic) => nearest_basic((r, g, b)),
+            (Color::Ansi256(index), ColorSupport::Basic) => match index {
+                0..=15 => BASIC_COLORS[index as usize],
+                _ => nearest_basic(ansi256_to_rgb(index)),
+            },
+            (color, _) => color,
+        }
+    }
+
+    /// Returns the SGR parameters selecting this color, where `base` is 30 for the foreground
+    /// and 40 for the background.
+    pub(crate) fn sgr(self, base: u8) -> String {
+        match self {
+            Color::Ansi256(index) => format!("{};5;{}", base + 8, index),
+            Color::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+            basic => match BASIC_COLORS.iter().position(|&color| color == basic) {
+                Some(index @ 0..=7) => (base as usize + index).to_string(),
+                Some(index) => (base as usize + 60 + index - 8).to_string(),
+                None => String::new(),
+            },
+        }
+    }
+
+    /// Returns the RGB values this color is displayed with, taking the standard colors from the
+    /// xterm palette.
+    #[cfg(feature = "image")]
+    pub(crate) fn rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::Ansi256(index) => ansi256_to_rgb(index),
+            Color::Rgb(r, g, b) => (r, g, b),
+            basic => BASIC_COLORS
+                .iter()
+                .position(|&color| color == basic)
+                .map_or((0, 0, 0), |index| BASIC_PALETTE[index]),
+        }
+    }
+}
+
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    if r == g && g == b {
+        return match r {
+            0..=7 => 16,
+            249..=255 => 231,
+            level => 232 + ((level as u16 - 8) * 24 / 247) as u8,
+        };
+    }
+
+    let level = |value: u8| match value {
+        0..=47 => 0,
+        48..=114 => 1,
+        value => (value - 35) / 40,
+    };
+
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+fn ansi256_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => BASIC_PALETTE[index as usize],
+        16..=231 => {
+            let index = index - 16;
+            (
+                CUBE_LEVELS[(index / 36) as usize],
+                CUBE_LEVELS[(index / 6 % 6) as usize],
+                CUBE_LEVELS[(index % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+fn nearest_basic((r, g, b): (u8, u8, u8)) -> Color {
+    let distance = |&(pr, pg, pb): &(u8, u8, u8)| {
+        let channel = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        channel(r, pr) + channel(g, pg) + channel(b, pb)
+    };
+
+    BASIC_PALETTE
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, rgb)| distance(rgb))
+        .map_or(Color::White, |(index, _)| BASIC_COLORS[index])
+}
+
+/// Wraps `text` in the escape sequences that set its foreground `color`, restoring the
+/// default foreground afterwards.
+pub(crate) fn paint_foreground(text: &str, color: Color) -> String {
+    format!("\x1b[{}m{}\x1b[39m", color.sgr(30), text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sgr_parameters_cover_every_kind_of_color() {
+        assert_eq!(Color::Red.sgr(30), "31");
+        assert_eq!(Color::White.sgr(40), "47");
+        assert_eq!(Color::BrightBlack.sgr(30), "90");
+        assert_eq!(Color::BrightWhite.sgr(40), "107");
+        assert_eq!(Color::Ansi256(208).sgr(30), "38;5;208");
+        assert_eq!(Color::Rgb(1, 2, 3).sgr(40), "48;2;1;2;3");
+    }
+
+    #[test]
+    fn cube_colors_map_back_to_their_index() {
+        for index in 16..=231 {
+            let (r, g, b) = ansi256_to_rgb(index);
+            // Greys of the cube are mapped onto the finer grey ramp instead.
+            if r != g || g != b {
+                assert_eq!(rgb_to_ansi256(r, g, b), index);
+            }
+        }
+    }
+
+    #[test]
+    fn greys_map_onto_the_grey_ramp() {
+        assert_eq!(rgb_to_ansi256(0, 0, 0), 16);
+        assert_eq!(rgb_to_ansi256(255, 255, 255), 231);
+        assert_eq!(rgb_to_ansi256(8, 8, 8), 232);
+        assert_eq!(rgb_to_ansi256(248, 248, 248), 255);
+        for index in 232..=255 {
+            let (r, g, b) = ansi256_to_rgb(index);
+            let mapped = rgb_to_ansi256(r, g, b);
+            assert!((232..=255).contains(&mapped));
+            assert!(mapped.abs_diff(index) <= 1, "{} became {}", index, mapped);
+        }
+    }
+
+    #[test]
+    fn downgrading_keeps_colors_that_can_be_displayed() {
+        let colors = [Color::Cyan, Color::Ansi256(100), Color::Rgb(10, 20, 30)];
+        for &color in &colors {
+            assert_eq!(color.downgrade(ColorSupport::TrueColor), color);
+        }
+        assert_eq!(
+            Color::Ansi256(100).downgrade(ColorSupport::Ansi256),
+            Color::Ansi256(100)
+        );
+        assert_eq!(
+            Color::Ansi256(9).downgrade(ColorSupport::Basic),
+            Color::BrightRed
+        );
+        assert_eq!(
+            Color::Ansi256(232).downgrade(ColorSupport::Basic),
+            Color::Black
+        );
+        assert_eq!(
+            Color::Rgb(0, 0, 200).downgrade(ColorSupport::Basic),
+            Color::Blue
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use std::borrow::Cow;
+use std::fmt;
+
+#[cfg(feature = "bumpalo")]
+use bumpalo::Bump;
+
+use crate::render::{Renderer, Rows};
+use crate::width::with_ambiguous_width;
+use crate::{AmbiguousWidth, TextBorderOptions};
+
+/// `CompiledTheme` is a set of `TextBorderOptions` resolved once for rendering many messages,
+/// returned by `TextBorderOptions::compile`.
+///
+/// Compiling inspects the terminal for its color support, character set and width, picks the
+/// border characters and builds the escape sequences of the colors, so rendering a message
+/// only lays out its content. The terminal is inspected once, so a compiled theme does not
+/// follow later changes to it, such as a resize.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, BorderStyle, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     ..Default::default()
+/// };
+/// let theme = options.clone().compile();
+///
+/// for message in ["first", "second record"] {
+///     assert_eq!(theme.render(message), create_text_border(message, Some(options.clone())));
+/// }
+/// assert_eq!(theme.render("done"), "┌────┐\n│done│\n└────┘");
+/// ```
+#[derive(Clone)]
+pub struct CompiledTheme {
+    renderer: Renderer,
+    ambiguous_width: AmbiguousWidth,
+}
+
+impl TextBorderOptions {
+    /// Resolves the options once for rendering many messages with them.
+    ///
+    /// # Returns
+    ///
+    /// * A `CompiledTheme` rendering messages as `create_text_border` does with these options.
+    pub fn compile(self) -> CompiledTheme {
+        let ambiguous_width = self.ambiguous_width;
+        CompiledTheme {
+            renderer: with_ambiguous_width(ambiguous_width, || Renderer::new(self)),
+            ambiguous_width,
+        }
+    }
+}
+
+impl CompiledTheme {
+    /// Creates a string containing `message` surrounded by the border and margin of the theme.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The text message to be surrounded by a border.
+    ///
+    /// # Returns
+    ///
+    /// * A `String` containing the message surrounded by the border and margin.
+    pub fn render<'a>(&self, message: impl Into<Cow<'a, str>>) -> String {
+        self.render_rows(&message.into()).join_lines()
+    }
+
+    /// Creates a string containing `message` surrounded by the border and margin of the theme,
+    /// like `render`, allocated in `bump`. This method is available with the `bumpalo` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `bump` - The arena the string is allocated in.
+    /// * `message` - The text message to be surrounded by a border.
+    ///
+    /// # Returns
+    ///
+    /// * A `&str` in `bump` containing the message surrounded by the border and margin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate bumpalo;
+    /// extern crate unicode_border;
+    ///
+    /// use bumpalo::Bump;
+    /// use unicode_border::TextBorderOptions;
+    ///
+    /// fn main() {
+    ///     let bump = Bump::new();
+    ///     let theme = TextBorderOptions::default().compile();
+    ///
+    ///     assert_eq!(theme.render_in(&bump, "Hi"), "****\n*Hi*\n****");
+    /// }
+    /// ```
+    #[cfg(feature = "bumpalo")]
+    pub fn render_in<'bump>(&self, bump: &'bump Bump, message: &str) -> &'bump str {
+        self.render_rows(message).join_lines_in(bump)
+    }
+
+    /// Renders `message` into the rows of its box.
+    fn render_rows(&self, message: &str) -> Rows {
+        with_ambiguous_width(self.ambiguous_width, || {
+            let renderer = self.renderer.clone();
+            let lines = renderer.message_lines(message);
+            renderer.render(lines)
+        })
+    }
+}
+
+impl fmt::Debug for CompiledTheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CompiledTheme")
+            .field("options", self.renderer.options())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_text_border, BorderStyle};
+
+    #[test]
+    fn a_compiled_theme_renders_as_create_text_border() {
+        let options = TextBorderOptions {
+            border_style: BorderStyle::Weighted,
+            margin_thickness: 1.into(),
+            title: Some("T".into()),
+            ..Default::default()
+        };
+        let theme = options.clone().compile();
+        for message in ["", "one", "two\nlines", "wide 字"] {
+            assert_eq!(
+                theme.render(message),
+                create_text_border(message, Some(options.clone()))
+            );
+        }
+    }
+
+    #[test]
+    fn the_ambiguous_width_is_kept_with_the_theme() {
+        let options = TextBorderOptions {
+            ambiguous_width: AmbiguousWidth::Wide,
+            ..Default::default()
+        };
+        let theme = options.clone().compile();
+        assert_eq!(theme.render("±"), create_text_border("±", Some(options)));
+        assert_eq!(theme.render("±"), "****\n*±*\n****");
+    }
+
+    #[test]
+    fn debug_shows_the_options() {
+        let theme = TextBorderOptions::default().compile();
+        assert!(format!("{:?}", theme).starts_with("CompiledTheme { options: TextBorderOptions {"));
+    }
+}
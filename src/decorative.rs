@@ -0,0 +1,182 @@
+use std::borrow::Cow;
+
+use crate::width::char_width;
+use crate::{default_options, render_grid, BorderStyle, TextBorderOptions};
+
+/// `DecorativeBorder` draws a border whose every cell is picked from a palette of characters
+/// by a seeded generator, for playful banners that still come out the same on every run, so
+/// they can be checked in tests.
+///
+/// Each cell is picked from the seed and its position in the box alone, so the same seed,
+/// palette and options always give the same border, on any platform.
+///
+/// # Fields
+///
+/// * `palette` - The characters the border is drawn with. Characters wider than one column are
+///   left out, and an empty palette leaves the border drawn with `border_char`.
+/// * `seed` - The seed of the generator. Different seeds mix the palette differently.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::DecorativeBorder;
+///
+/// let decoration = DecorativeBorder {
+///     palette: vec!['*', '+', '~', 'o'],
+///     seed: 7,
+/// };
+///
+/// assert_eq!(
+///     decoration.render("Party!", None),
+///     "*o*~o~+~\n*Party!o\nooo~ooo+"
+/// );
+///
+/// let reseeded = DecorativeBorder {
+///     seed: 8,
+///     ..decoration
+/// };
+///
+/// assert_eq!(
+///     reseeded.render("Party!", None),
+///     "*~++*~*o\n~Party!+\n*o~+ooo*"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DecorativeBorder {
+    pub palette: Vec<char>,
+    pub seed: u64,
+}
+
+impl Default for DecorativeBorder {
+    fn default() -> Self {
+        DecorativeBorder {
+            palette: vec!['*', '+', '~', 'o'],
+            seed: 0,
+        }
+    }
+}
+
+impl DecorativeBorder {
+    /// Renders the input message surrounded by a decorative border.
+    ///
+    /// The box is laid out as with `BorderStyle::Char`, whatever the `border_style` and
+    /// `border_layers` of the options, and each cell drawn with `border_char` is then replaced
+    /// by a character of the palette.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to be surrounded by the border.
+    /// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+    ///   configurations. If `None`, the options returned by `default_options` are used.
+    ///
+    /// # Returns
+    ///
+    /// * A `String` containing the bordered message, without colors, like a `Grid`.
+    pub fn render<'a>(
+        &self,
+        message: impl Into<Cow<'a, str>>,
+        options: Option<TextBorderOptions>,
+    ) -> String {
+        let mut options = options.unwrap_or_else(default_options);
+        options.border_style = BorderStyle::Char;
+        options.border_layers = None;
+        let border_char = options.border_char;
+        let thickness = options.border_thickness;
+        let mut grid = render_grid(message, Some(options));
+
+        let palette: Vec<char> = self
+            .palette
+            .iter()
+            .copied()
+            .filter(|&c| char_width(c) == 1)
+            .collect();
+        if palette.is_empty() || char_width(border_char) != 1 {
+            return grid.to_string();
+        }
+
+        let (width, height) = (grid.width(), grid.height());
+        for y in 0..height {
+            let edge_row = y < thickness.top || y >= height.saturating_sub(thickness.bottom);
+            for x in 0..width {
+                let edge_column = x < thickness.left || x >= width.saturating_sub(thickness.right);
+                if (edge_row || edge_column) && grid.get(x, y) == Some(border_char) {
+                    let pick = mix(self.seed, x as u64, y as u64) % palette.len() as u64;
+                    grid.set(x, y, palette[pick as usize]);
+                }
+            }
+        }
+        grid.to_string()
+    }
+}
+
+/// Returns a well-mixed number for the cell at column `x` of row `y`, from the SplitMix64
+/// generator seeded by `seed` and the position.
+fn mix(seed: u64, x: u64, y: u64) -> u64 {
+    let mut z = seed
+        .wrapping_add(x.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+        .wrapping_add(y.wrapping_mul(0xc2b2_ae3d_27d4_eb4f));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_same_seed_gives_the_same_border() {
+        let decoration = DecorativeBorder::default();
+        assert_eq!(
+            decoration.render("Hi", None),
+            decoration.clone().render("Hi", None)
+        );
+    }
+
+    #[test]
+    fn only_border_cells_are_replaced() {
+        let decoration = DecorativeBorder {
+            palette: vec!['#'],
+            seed: 3,
+        };
+        let options = TextBorderOptions {
+            border_char: '*',
+            ..default_options()
+        };
+        assert_eq!(
+            decoration.render("a*b", Some(options)),
+            "#####\n#a*b#\n#####"
+        );
+    }
+
+    #[test]
+    fn wide_palette_characters_are_left_out() {
+        let decoration = DecorativeBorder {
+            palette: vec!['字', '#'],
+            seed: 1,
+        };
+        assert!(!decoration.render("Hi", None).contains('字'));
+    }
+
+    #[test]
+    fn without_a_usable_palette_the_border_char_is_kept() {
+        let plain = crate::render_grid("Hi", Some(default_options())).to_string();
+        for palette in [vec![], vec!['字']] {
+            let decoration = DecorativeBorder { palette, seed: 0 };
+            assert_eq!(decoration.render("Hi", None), plain);
+        }
+    }
+
+    #[test]
+    fn border_style_and_layers_are_ignored() {
+        let options = TextBorderOptions {
+            border_style: BorderStyle::Brackets,
+            ..default_options()
+        };
+        let decoration = DecorativeBorder::default();
+        assert_eq!(
+            decoration.render("Hi", Some(options)),
+            decoration.render("Hi", None)
+        );
+    }
+}
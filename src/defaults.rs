@@ -0,0 +1,153 @@
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::sync::RwLock;
+
+use crate::TextBorderOptions;
+
+static GLOBAL_DEFAULTS: RwLock<Option<TextBorderOptions>> = RwLock::new(None);
+
+thread_local! {
+    static SCOPED_DEFAULTS: RefCell<Option<TextBorderOptions>> = const { RefCell::new(None) };
+}
+
+/// Sets the options used by every thread when a function is called without explicit options,
+/// so an application can configure its house style once.
+///
+/// Scoped overrides made with `scoped_default_options` take precedence on their thread.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, reset_default_options, set_default_options, TextBorderOptions};
+///
+/// set_default_options(TextBorderOptions {
+///     border_char: '=',
+///     ..Default::default()
+/// });
+///
+/// assert_eq!(create_text_border("Hi", None), "====\n=Hi=\n====");
+///
+/// reset_default_options();
+///
+/// assert_eq!(create_text_border("Hi", None), "****\n*Hi*\n****");
+/// ```
+pub fn set_default_options(options: TextBorderOptions) {
+    *GLOBAL_DEFAULTS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(options);
+}
+
+/// Restores the global defaults set with `set_default_options` to `TextBorderOptions::default()`.
+pub fn reset_default_options() {
+    *GLOBAL_DEFAULTS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+}
+
+/// Returns the options used when a function is called without explicit options: the innermost
+/// scoped override on this thread, else the global defaults, else `TextBorderOptions::default()`.
+pub fn default_options() -> TextBorderOptions {
+    SCOPED_DEFAULTS
+        .with(|scoped| scoped.borrow().clone())
+        .or_else(|| {
+            GLOBAL_DEFAULTS
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone()
+        })
+        .unwrap_or_default()
+}
+
+/// Overrides the default options on the current thread until the returned guard is dropped.
+///
+/// Guards may be nested; dropping a guard restores whatever defaults were in effect when it
+/// was created.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, scoped_default_options, TextBorderOptions};
+///
+/// {
+///     let _guard = scoped_default_options(TextBorderOptions {
+///         border_char: '#',
+///         ..Default::default()
+///     });
+///
+///     assert_eq!(create_text_border("Hi", None), "####\n#Hi#\n####");
+/// }
+///
+/// assert_eq!(create_text_border("Hi", None), "****\n*Hi*\n****");
+/// ```
+pub fn scoped_default_options(options: TextBorderOptions) -> DefaultOptionsGuard {
+    let previous = SCOPED_DEFAULTS.with(|scoped| scoped.replace(Some(options)));
+
+    DefaultOptionsGuard {
+        previous,
+        _not_send: PhantomData,
+    }
+}
+
+/// Restores the previous thread-local default options when dropped.
+///
+/// Returned by `scoped_default_options`.
+#[must_use = "the scoped defaults are restored as soon as the guard is dropped"]
+pub struct DefaultOptionsGuard {
+    previous: Option<TextBorderOptions>,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for DefaultOptionsGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        SCOPED_DEFAULTS.with(|scoped| *scoped.borrow_mut() = previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::catch_unwind;
+
+    use super::*;
+
+    fn with_char(border_char: char) -> TextBorderOptions {
+        TextBorderOptions {
+            border_char,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn scoped_defaults_nest_and_are_restored() {
+        let outer = scoped_default_options(with_char('#'));
+        {
+            let _inner = scoped_default_options(with_char('+'));
+            assert_eq!(default_options().border_char, '+');
+        }
+        assert_eq!(default_options().border_char, '#');
+        drop(outer);
+        assert_ne!(default_options().border_char, '#');
+    }
+
+    #[test]
+    fn scoped_defaults_are_restored_after_a_panic() {
+        let _outer = scoped_default_options(with_char('#'));
+        let panicked = catch_unwind(|| {
+            let _inner = scoped_default_options(with_char('+'));
+            panic!("rendering failed");
+        });
+
+        assert!(panicked.is_err());
+        assert_eq!(default_options().border_char, '#');
+    }
+
+    #[test]
+    fn scoped_defaults_apply_to_this_thread_only() {
+        let _guard = scoped_default_options(with_char('#'));
+        let other = std::thread::spawn(|| default_options().border_char)
+            .join()
+            .unwrap();
+
+        assert_ne!(other, '#');
+    }
+}
@@ -0,0 +1,444 @@
+use std::fmt;
+use std::ops::Range;
+
+use crate::width::str_width;
+use crate::{create_text_border, default_options, Color, TextBorderOptions, Theme};
+
+/// `Severity` is how serious a `Diagnostic` is. It picks the label of the report and the color
+/// of its border.
+///
+/// # Variants
+///
+/// * `Error` - An error, with a red border.
+/// * `Warning` - A warning, with a yellow border.
+/// * `Note` - A note or hint, with a cyan border.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    /// Returns the color of the border of reports of this severity, taken from the success,
+    /// warning or error role of `theme` if there is one.
+    fn color(self, theme: Option<&Theme>) -> Color {
+        let themed = theme.and_then(|theme| match self {
+            Severity::Error => theme.error.foreground,
+            Severity::Warning => theme.warning.foreground,
+            Severity::Note => theme.accent.foreground,
+        });
+
+        themed.unwrap_or(match self {
+            Severity::Error => Color::Red,
+            Severity::Warning => Color::Yellow,
+            Severity::Note => Color::Cyan,
+        })
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        })
+    }
+}
+
+/// `Snippet` is a line of source code quoted by a `Diagnostic`, with the part it is about
+/// underlined.
+///
+/// # Fields
+///
+/// * `line_number` - The number of the line in its file, shown in the gutter.
+/// * `source` - The text of the line.
+/// * `span` - The byte range of `source` underlined with carets, widened to whole characters.
+///   An empty range points a single caret at its start.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Snippet {
+    pub line_number: usize,
+    pub source: String,
+    pub span: Range<usize>,
+}
+
+/// `Diagnostic` is a structured report, such as a compiler error, rendered inside a border
+/// colored by its severity.
+///
+/// # Fields
+///
+/// * `severity` - The `Severity` of the report.
+/// * `title` - A short summary, set into the top border after the severity.
+/// * `message` - The explanation, shown above the snippet.
+/// * `snippet` - An optional `Snippet` of source code the report is about.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{BorderStyle, ColorMode, Diagnostic, Severity, Snippet, TextBorderOptions};
+///
+/// let diagnostic = Diagnostic {
+///     severity: Severity::Error,
+///     title: "unknown key".to_string(),
+///     message: "expected one of `name`, `port`".to_string(),
+///     snippet: Some(Snippet {
+///         line_number: 3,
+///         source: "hots = 1".to_string(),
+///         span: 0..4,
+///     }),
+/// };
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     color_mode: ColorMode::Never,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     diagnostic.render(Some(options)),
+///     "┌─┤ error: unknown key ├───────┐\n\
+///      │expected one of `name`, `port`│\n\
+///      │                              │\n\
+///      │3 | hots = 1                  │\n\
+///      │  | ^^^^                      │\n\
+///      └──────────────────────────────┘"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub title: String,
+    pub message: String,
+    pub snippet: Option<Snippet>,
+}
+
+impl Diagnostic {
+    /// Renders the report inside a border.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+    ///   configurations. If `None`, the options returned by `default_options` are used. The
+    ///   title is replaced by the severity and title of the report, and the border is colored
+    ///   by the severity, or the matching role of the `theme`, unless `border_color` is set.
+    ///
+    /// # Returns
+    ///
+    /// * A `String` containing the report surrounded by the specified border and margin.
+    pub fn render(&self, options: Option<TextBorderOptions>) -> String {
+        let mut options = options.unwrap_or_else(default_options);
+        options.title = Some(format!("{}: {}", self.severity, self.title));
+        let color = self.severity.color(options.theme.as_ref());
+        options.border_color = options.border_color.or(Some(color));
+
+        let mut body = self.message.clone();
+        if let Some(snippet) = &self.snippet {
+            body.push_str("\n\n");
+            body.push_str(&snippet.render());
+        }
+
+        create_text_border(body, Some(options))
+    }
+
+    /// Renders the report as a reStructuredText admonition, such as `.. warning::`, picked by
+    /// its severity, so the same report can be shown in a terminal and in Sphinx
+    /// documentation. The title is the first paragraph of the admonition, in bold, followed by
+    /// the message and the snippet as a literal block. This method is available with the
+    /// `export` feature.
+    ///
+    /// # Returns
+    ///
+    /// * A `String` containing the admonition, its body indented by three spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_border::{Diagnostic, Severity, Snippet};
+    ///
+    /// let diagnostic = Diagnostic {
+    ///     severity: Severity::Warning,
+    ///     title: "unused key".to_string(),
+    ///     message: "`hots` is ignored".to_string(),
+    ///     snippet: Some(Snippet {
+    ///         line_number: 3,
+    ///         source: "hots = 1".to_string(),
+    ///         span: 0..4,
+    ///     }),
+    /// };
+    ///
+    /// assert_eq!(
+    ///     diagnostic.to_rst().lines().collect::<Vec<_>>(),
+    ///     [
+    ///         ".. warning::",
+    ///         "",
+    ///         "   **unused key**",
+    ///         "",
+    ///         "   `hots` is ignored",
+    ///         "",
+    ///         "   ::",
+    ///         "",
+    ///         "      3 | hots = 1",
+    ///         "        | ^^^^",
+    ///     ]
+    /// );
+    /// ```
+    #[cfg(feature = "export")]
+    pub fn to_rst(&self) -> String {
+        let mut paragraphs = Vec::new();
+        if !self.title.is_empty() {
+            let title = self.title.replace('\\', "\\\\").replace('*', "\\*");
+            paragraphs.push(format!("**{}**", title));
+        }
+        if !self.message.is_empty() {
+            paragraphs.push(self.message.clone());
+        }
+        if let Some(snippet) = &self.snippet {
+            paragraphs.push(format!("::\n\n{}", indent(&snippet.render(), RST_INDENT)));
+        }
+
+        // The directives of the severities are named as the severities are displayed.
+        let mut rst = format!(".. {}::", self.severity);
+        for paragraph in paragraphs {
+            rst.push_str("\n\n");
+            rst.push_str(&indent(&paragraph, RST_INDENT));
+        }
+        rst
+    }
+
+    /// Renders the report as an AsciiDoc admonition block, such as `[WARNING]`, picked by its
+    /// severity. Errors, for which AsciiDoc has no admonition, are rendered as `[CAUTION]`. The
+    /// title is the title of the block, followed by the message and the snippet as a literal
+    /// block. This method is available with the `export` feature.
+    ///
+    /// # Returns
+    ///
+    /// * A `String` containing the admonition block, delimited by `====` lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_border::{Diagnostic, Severity, Snippet};
+    ///
+    /// let diagnostic = Diagnostic {
+    ///     severity: Severity::Warning,
+    ///     title: "unused key".to_string(),
+    ///     message: "`hots` is ignored".to_string(),
+    ///     snippet: Some(Snippet {
+    ///         line_number: 3,
+    ///         source: "hots = 1".to_string(),
+    ///         span: 0..4,
+    ///     }),
+    /// };
+    ///
+    /// assert_eq!(
+    ///     diagnostic.to_asciidoc().lines().collect::<Vec<_>>(),
+    ///     [
+    ///         "[WARNING]",
+    ///         ".unused key",
+    ///         "====",
+    ///         "`hots` is ignored",
+    ///         "",
+    ///         "....",
+    ///         "3 | hots = 1",
+    ///         "  | ^^^^",
+    ///         "....",
+    ///         "====",
+    ///     ]
+    /// );
+    /// ```
+    #[cfg(feature = "export")]
+    pub fn to_asciidoc(&self) -> String {
+        let style = match self.severity {
+            Severity::Error => "CAUTION",
+            Severity::Warning => "WARNING",
+            Severity::Note => "NOTE",
+        };
+
+        let mut lines = vec![format!("[{}]", style)];
+        // A title line starting with a space or a dot would not be read as a title.
+        let title = self
+            .title
+            .trim_start_matches(|c: char| c == '.' || c.is_whitespace());
+        if !title.is_empty() {
+            lines.push(format!(".{}", title));
+        }
+        lines.push("====".to_string());
+
+        let mut paragraphs = Vec::new();
+        if !self.message.is_empty() {
+            paragraphs.push(self.message.clone());
+        }
+        if let Some(snippet) = &self.snippet {
+            paragraphs.push(format!("....\n{}\n....", snippet.render()));
+        }
+        lines.push(paragraphs.join("\n\n"));
+        lines.push("====".to_string());
+        lines.join("\n")
+    }
+}
+
+/// The indent of the body of a reStructuredText directive.
+#[cfg(feature = "export")]
+const RST_INDENT: &str = "   ";
+
+/// Returns `text` with every line that is not blank indented by `prefix`.
+#[cfg(feature = "export")]
+fn indent(text: &str, prefix: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.trim().is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", prefix, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl Snippet {
+    /// Returns the line of source after a gutter with its number, and a line of carets under
+    /// the span.
+    fn render(&self) -> String {
+        // The span is widened to the characters it starts and ends in.
+        let floor = |mut index: usize| {
+            index = index.min(self.source.len());
+            while !self.source.is_char_boundary(index) {
+                index -= 1;
+            }
+            index
+        };
+        let ceil = |mut index: usize| {
+            index = index.min(self.source.len());
+            while !self.source.is_char_boundary(index) {
+                index += 1;
+            }
+            index
+        };
+        let start = floor(self.span.start);
+        let end = ceil(self.span.end).max(start);
+
+        let number = self.line_number.to_string();
+        let gutter = " ".repeat(number.len());
+        let indent = str_width(&self.source[..start]);
+        let carets = str_width(&self.source[start..end]).max(1);
+
+        format!(
+            "{} | {}\n{} | {}{}",
+            number,
+            self.source,
+            gutter,
+            " ".repeat(indent),
+            "^".repeat(carets)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{strip_ansi_codes, Style};
+
+    fn snippet(source: &str, span: Range<usize>) -> Snippet {
+        Snippet {
+            line_number: 12,
+            source: source.to_string(),
+            span,
+        }
+    }
+
+    fn diagnostic(severity: Severity, title: &str, message: &str) -> Diagnostic {
+        Diagnostic {
+            severity,
+            title: title.to_string(),
+            message: message.to_string(),
+            snippet: None,
+        }
+    }
+
+    #[test]
+    fn carets_underline_the_span() {
+        assert_eq!(
+            snippet("let x = 1;", 4..5).render(),
+            "12 | let x = 1;\n   |     ^"
+        );
+        assert_eq!(snippet("字 = 1", 0..3).render(), "12 | 字 = 1\n   | ^^");
+    }
+
+    #[test]
+    fn empty_and_reversed_spans_show_a_single_caret() {
+        assert_eq!(snippet("abc", 1..1).render(), "12 | abc\n   |  ^");
+        assert_eq!(
+            snippet("abc", Range { start: 2, end: 1 }).render(),
+            "12 | abc\n   |   ^"
+        );
+    }
+
+    #[test]
+    fn spans_are_clamped_to_the_source_and_its_char_boundaries() {
+        assert_eq!(snippet("ab", 5..9).render(), "12 | ab\n   |   ^");
+        assert_eq!(snippet("a字", 2..3).render(), "12 | a字\n   |  ^^");
+    }
+
+    #[test]
+    fn severities_are_colored_by_the_theme_or_by_default() {
+        assert_eq!(Severity::Error.color(None), Color::Red);
+        assert_eq!(Severity::Warning.color(None), Color::Yellow);
+        assert_eq!(Severity::Note.color(None), Color::Cyan);
+
+        let theme = Theme {
+            accent: Style {
+                foreground: Some(Color::Magenta),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(Severity::Note.color(Some(&theme)), Color::Magenta);
+        assert_eq!(Severity::Error.color(Some(&theme)), Color::Red);
+    }
+
+    #[test]
+    fn the_title_replaces_the_options_title() {
+        let options = TextBorderOptions {
+            title: Some("ignored".into()),
+            ..Default::default()
+        };
+        let report = diagnostic(Severity::Note, "hint", "try this");
+        let rendered = strip_ansi_codes(&report.render(Some(options)));
+        assert!(rendered.contains("note: hint"));
+        assert!(!rendered.contains("ignored"));
+    }
+
+    #[cfg(feature = "export")]
+    #[test]
+    fn rst_titles_are_escaped_and_empty_parts_left_out() {
+        let report = diagnostic(Severity::Error, r"a*b\c", "");
+        assert_eq!(report.to_rst(), ".. error::\n\n   **a\\*b\\\\c**");
+        assert_eq!(diagnostic(Severity::Note, "", "").to_rst(), ".. note::");
+    }
+
+    #[cfg(feature = "export")]
+    #[test]
+    fn rst_bodies_are_indented_except_blank_lines() {
+        let report = diagnostic(Severity::Warning, "", "one\n\ntwo");
+        assert_eq!(report.to_rst(), ".. warning::\n\n   one\n\n   two");
+    }
+
+    #[cfg(feature = "export")]
+    #[test]
+    fn errors_are_asciidoc_cautions() {
+        assert_eq!(
+            diagnostic(Severity::Error, "bad", "oops").to_asciidoc(),
+            "[CAUTION]\n.bad\n====\noops\n===="
+        );
+    }
+
+    #[cfg(feature = "export")]
+    #[test]
+    fn asciidoc_titles_are_trimmed_or_left_out() {
+        let report = diagnostic(Severity::Note, " ..hint", "");
+        assert_eq!(report.to_asciidoc(), "[NOTE]\n.hint\n====\n\n====");
+        let report = diagnostic(Severity::Note, ". ", "body");
+        assert_eq!(report.to_asciidoc(), "[NOTE]\n====\nbody\n====");
+    }
+}
@@ -0,0 +1,43 @@
+use crate::color::paint_foreground;
+use crate::Color;
+
+/// Colors one line of a unified diff by its prefix: file headers bold, hunk headers cyan, added
+/// lines green and removed lines red. Other lines are returned as they are.
+pub(crate) fn color_diff_line(line: String) -> String {
+    if line.starts_with("+++") || line.starts_with("---") {
+        format!("\x1b[1m{}\x1b[22m", line)
+    } else if line.starts_with("@@") {
+        paint_foreground(&line, Color::Cyan)
+    } else if line.starts_with('+') {
+        paint_foreground(&line, Color::Green)
+    } else if line.starts_with('-') {
+        paint_foreground(&line, Color::Red)
+    } else {
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::color_diff_line;
+
+    fn colored(line: &str) -> String {
+        color_diff_line(line.to_string())
+    }
+
+    #[test]
+    fn lines_are_colored_by_their_prefix() {
+        assert_eq!(colored("+++ b/file"), "\x1b[1m+++ b/file\x1b[22m");
+        assert_eq!(colored("--- a/file"), "\x1b[1m--- a/file\x1b[22m");
+        assert_eq!(colored("@@ -1 +1 @@"), "\x1b[36m@@ -1 +1 @@\x1b[39m");
+        assert_eq!(colored("+added"), "\x1b[32m+added\x1b[39m");
+        assert_eq!(colored("-removed"), "\x1b[31m-removed\x1b[39m");
+    }
+
+    #[test]
+    fn context_lines_are_unchanged() {
+        assert_eq!(colored(" context"), " context");
+        assert_eq!(colored(""), "");
+        assert_eq!(colored("@ not a hunk"), "@ not a hunk");
+    }
+}
@@ -0,0 +1,300 @@
+#[cfg(feature = "bumpalo")]
+use bumpalo::Bump;
+
+use crate::render::{Renderer, Rows};
+use crate::width::with_ambiguous_width;
+use crate::{default_options, Style, TextBorderOptions};
+
+/// `Alignment` is how lines are placed across the width of a box.
+///
+/// # Variants
+///
+/// * `Left` - Lines start at the left margin. This is the default.
+/// * `Center` - Lines are centered, with any odd column of space on the right.
+/// * `Right` - Lines end at the right margin.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// `Region` is a named part of a `BorderedDocument`, such as its header, with its own
+/// alignment and style.
+///
+/// # Fields
+///
+/// * `lines` - The lines of content of the region. A region without lines is left out.
+/// * `alignment` - The `Alignment` of the lines of the region.
+/// * `style` - An optional `Style` painting the lines of the region.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Region {
+    pub lines: Vec<String>,
+    pub alignment: Alignment,
+    pub style: Option<Style>,
+}
+
+/// `BorderedDocument` accumulates the content of a box, so long-running tools can add to it and
+/// render it again at any time without rebuilding the message themselves.
+///
+/// The content is split into a header, a body and a footer, rendered in that order within a
+/// single border and separated by rules. Regions without lines are left out, along with their
+/// rules.
+///
+/// # Fields
+///
+/// * `options` - The `TextBorderOptions` the document is rendered with.
+/// * `header` - The `Region` at the top of the box.
+/// * `body` - The `Region` in the middle of the box, which `push_line` and `push_section` add
+///   to.
+/// * `footer` - The `Region` at the bottom of the box.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::BorderedDocument;
+///
+/// let mut document = BorderedDocument::new(None);
+/// document.push_line("step 1: ok");
+/// assert_eq!(document.render(), "************\n*step 1: ok*\n************");
+///
+/// document.push_line("step 2: ok");
+/// document.push_section("done\nin 2s");
+/// assert_eq!(
+///     document.render(),
+///     "************\n\
+///      *step 1: ok*\n\
+///      *step 2: ok*\n\
+///      *          *\n\
+///      *done      *\n\
+///      *in 2s     *\n\
+///      ************"
+/// );
+/// ```
+///
+/// With a centered header and a footer aligned right:
+///
+/// ```
+/// use unicode_border::{Alignment, BorderStyle, BorderedDocument, TextBorderOptions};
+///
+/// let mut document = BorderedDocument::new(Some(TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     ..Default::default()
+/// }));
+/// document.header.lines.push("Report".to_string());
+/// document.header.alignment = Alignment::Center;
+/// document.push_line("all 12 checks passed");
+/// document.footer.lines.push("v1.2".to_string());
+/// document.footer.alignment = Alignment::Right;
+///
+/// assert_eq!(
+///     document.render(),
+///     "┌────────────────────┐\n\
+///      │       Report       │\n\
+///      ├────────────────────┤\n\
+///      │all 12 checks passed│\n\
+///      ├────────────────────┤\n\
+///      │                v1.2│\n\
+///      └────────────────────┘"
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct BorderedDocument {
+    pub options: TextBorderOptions,
+    pub header: Region,
+    pub body: Region,
+    pub footer: Region,
+}
+
+impl BorderedDocument {
+    /// Creates an empty document.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+    ///   configurations. If `None`, the options returned by `default_options` are used.
+    pub fn new(options: Option<TextBorderOptions>) -> Self {
+        BorderedDocument {
+            options: options.unwrap_or_else(default_options),
+            header: Region::default(),
+            body: Region::default(),
+            footer: Region::default(),
+        }
+    }
+
+    /// Adds a line of content at the end of the body. Line breaks in `line` start new lines.
+    pub fn push_line(&mut self, line: impl Into<String>) -> &mut Self {
+        let line = line.into();
+        self.body.lines.extend(line.split('\n').map(str::to_string));
+        self
+    }
+
+    /// Adds a section of content at the end of the body, separated from the content before it,
+    /// if there is any, by a blank line.
+    pub fn push_section(&mut self, section: impl Into<String>) -> &mut Self {
+        if !self.body.lines.is_empty() {
+            self.body.lines.push(String::new());
+        }
+        self.push_line(section)
+    }
+
+    /// Removes the content of every region, keeping the options and the alignment and style of
+    /// the regions.
+    pub fn clear(&mut self) {
+        self.header.lines.clear();
+        self.body.lines.clear();
+        self.footer.lines.clear();
+    }
+
+    /// Renders the document inside a border.
+    ///
+    /// The options are applied as by `create_text_border`, except that the `max_width`,
+    /// `target_width`, `aspect_ratio` and `limits` of the box are not.
+    ///
+    /// # Returns
+    ///
+    /// * A `String` containing the content surrounded by the specified border and margin.
+    pub fn render(&self) -> String {
+        self.render_rows().join_lines()
+    }
+
+    /// Renders the document inside a border, like `render`, allocating the string in `bump`.
+    /// This method is available with the `bumpalo` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `bump` - The arena the string is allocated in.
+    ///
+    /// # Returns
+    ///
+    /// * A `&str` in `bump` containing the content surrounded by the specified border and
+    ///   margin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate bumpalo;
+    /// extern crate unicode_border;
+    ///
+    /// use bumpalo::Bump;
+    /// use unicode_border::BorderedDocument;
+    ///
+    /// fn main() {
+    ///     let bump = Bump::new();
+    ///     let mut document = BorderedDocument::new(None);
+    ///     document.push_line("Hi");
+    ///
+    ///     assert_eq!(document.render_in(&bump), document.render());
+    /// }
+    /// ```
+    #[cfg(feature = "bumpalo")]
+    pub fn render_in<'bump>(&self, bump: &'bump Bump) -> &'bump str {
+        self.render_rows().join_lines_in(bump)
+    }
+
+    fn render_rows(&self) -> Rows {
+        with_ambiguous_width(self.options.ambiguous_width, || {
+            Renderer::new(self.options.clone()).render_regions(&[
+                &self.header,
+                &self.body,
+                &self.footer,
+            ])
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_text_border, AmbiguousWidth};
+
+    #[test]
+    fn lines_are_split_and_sections_separated() {
+        let mut document = BorderedDocument::new(None);
+        document
+            .push_section("a")
+            .push_line("b\nc")
+            .push_section("d");
+        assert_eq!(document.body.lines, ["a", "b", "c", "", "d"]);
+    }
+
+    #[test]
+    fn an_empty_document_has_no_rows_of_content() {
+        let document = BorderedDocument::new(None);
+        assert_eq!(document.render(), "**\n**");
+    }
+
+    #[test]
+    fn clearing_keeps_the_layout_of_the_regions() {
+        let mut document = BorderedDocument::new(None);
+        document.header.alignment = Alignment::Right;
+        document.header.lines.push("h".to_string());
+        document.push_line("b");
+        document.clear();
+        assert!(document.header.lines.is_empty() && document.body.lines.is_empty());
+        assert_eq!(document.header.alignment, Alignment::Right);
+    }
+
+    #[test]
+    fn the_ambiguous_width_of_the_options_is_used() {
+        let options = TextBorderOptions {
+            ambiguous_width: AmbiguousWidth::Wide,
+            ..Default::default()
+        };
+        let mut document = BorderedDocument::new(Some(options.clone()));
+        document.push_line("±a");
+        assert_eq!(document.render(), create_text_border("±a", Some(options)));
+    }
+
+    fn document(header: &[&str], body: &[&str], footer: &[&str]) -> BorderedDocument {
+        let region = |lines: &[&str]| Region {
+            lines: lines.iter().map(|line| line.to_string()).collect(),
+            ..Default::default()
+        };
+        BorderedDocument {
+            header: region(header),
+            body: region(body),
+            footer: region(footer),
+            ..BorderedDocument::new(Some(TextBorderOptions {
+                border_style: crate::BorderStyle::Weighted,
+                ..Default::default()
+            }))
+        }
+    }
+
+    #[test]
+    fn empty_regions_are_left_out_with_their_rules() {
+        assert_eq!(document(&["h"], &[], &[]).render(), "┌─┐\n│h│\n└─┘");
+        assert_eq!(document(&[], &[], &["f"]).render(), "┌─┐\n│f│\n└─┘");
+        assert_eq!(
+            document(&["h"], &[], &["f"]).render(),
+            "┌─┐\n│h│\n├─┤\n│f│\n└─┘"
+        );
+    }
+
+    #[test]
+    fn odd_space_of_centered_lines_goes_right() {
+        let mut document = document(&["ab"], &["12345"], &[]);
+        document.header.alignment = Alignment::Center;
+        assert_eq!(
+            document.render(),
+            "┌─────┐\n│ ab  │\n├─────┤\n│12345│\n└─────┘"
+        );
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn region_styles_paint_their_lines_only() {
+        let mut document = document(&["h"], &["b"], &[]);
+        document.options.color_mode = crate::ColorMode::Always;
+        document.header.style = Some(Style {
+            bold: true,
+            ..Default::default()
+        });
+        let rendered = document.render();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert!(lines[1].contains("\x1b[1mh"));
+        assert!(!lines[3].contains("\x1b[1m"));
+    }
+}
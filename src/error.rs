@@ -0,0 +1,130 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{Dimension, MAX_THICKNESS};
+
+/// `ConfigError` describes why a set of options was rejected by `TextBorderOptions::validate`
+/// and `TextBorderOptionsBuilder::build`, or a message by `try_create_text_border`.
+///
+/// # Variants
+///
+/// * `InvalidBorderChar` - A border character is not printable: a control character, such as a
+///   newline or tab, or a zero-width character, such as a combining mark, which would break the
+///   border apart or misalign it.
+/// * `InvalidChar` - The named option holds a character it cannot be drawn with: a character
+///   of a margin pattern that is not printable, or a control character in a label, such as a
+///   newline in a title.
+/// * `ThicknessTooLarge` - A border or margin thickness is larger than `MAX_THICKNESS`.
+/// * `LimitExceeded` - A size is larger than allowed by the `Limits` of the options, and their
+///   `on_exceed` action is `LimitAction::Error`.
+/// * `ZeroWidth` - The named width option is 0 columns, leaving no room for the message.
+/// * `ConflictingWidths` - The minimum width of the box is larger than its maximum or target
+///   width.
+/// * `InvalidAspectRatio` - The aspect ratio is not a finite number larger than 0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConfigError {
+    InvalidBorderChar(char),
+    InvalidChar {
+        option: &'static str,
+        c: char,
+    },
+    ThicknessTooLarge(usize),
+    LimitExceeded {
+        dimension: Dimension,
+        value: usize,
+        max: usize,
+    },
+    ZeroWidth(&'static str),
+    ConflictingWidths {
+        min: usize,
+        max: usize,
+    },
+    InvalidAspectRatio,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::InvalidBorderChar(c) => write!(
+                f,
+                "invalid border character {:?} (U+{:04X}): control and zero-width characters cannot be used to draw a border",
+                c, *c as u32
+            ),
+            ConfigError::InvalidChar { option, c } => write!(
+                f,
+                "invalid character {:?} (U+{:04X}) in {}: it would break the border apart",
+                c, *c as u32, option
+            ),
+            ConfigError::ThicknessTooLarge(thickness) => write!(
+                f,
+                "thickness {} is too large: borders and margins can be at most {} cells thick",
+                thickness, MAX_THICKNESS
+            ),
+            ConfigError::LimitExceeded {
+                dimension,
+                value,
+                max,
+            } => write!(
+                f,
+                "{} {} exceeds the limit of {}",
+                dimension, value, max
+            ),
+            ConfigError::ZeroWidth(option) => write!(
+                f,
+                "{} is 0 columns, leaving no room for the message: set it to None to lift the limit",
+                option
+            ),
+            ConfigError::ConflictingWidths { min, max } => write!(
+                f,
+                "min_width of {} columns is larger than the maximum of {} columns: lower min_width or raise max_width and target_width",
+                min, max
+            ),
+            ConfigError::InvalidAspectRatio => write!(
+                f,
+                "aspect_ratio must be a finite number larger than 0, such as 1.0 for a square box"
+            ),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_name_the_problem() {
+        assert_eq!(
+            ConfigError::InvalidBorderChar('\t').to_string(),
+            "invalid border character '\\t' (U+0009): control and zero-width characters cannot be used to draw a border"
+        );
+        assert_eq!(
+            ConfigError::InvalidChar {
+                option: "footer",
+                c: '\n'
+            }
+            .to_string(),
+            "invalid character '\\n' (U+000A) in footer: it would break the border apart"
+        );
+        assert_eq!(
+            ConfigError::ThicknessTooLarge(2000).to_string(),
+            format!(
+                "thickness 2000 is too large: borders and margins can be at most {} cells thick",
+                MAX_THICKNESS
+            )
+        );
+        assert!(ConfigError::ZeroWidth("max_width")
+            .to_string()
+            .starts_with("max_width is 0 columns"));
+        assert!(ConfigError::ConflictingWidths { min: 9, max: 4 }
+            .to_string()
+            .starts_with("min_width of 9 columns is larger than the maximum of 4 columns"));
+    }
+
+    #[test]
+    fn is_an_error() {
+        let error: Box<dyn Error> = Box::new(ConfigError::InvalidAspectRatio);
+        assert!(error.to_string().contains("aspect_ratio"));
+    }
+}
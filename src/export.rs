@@ -0,0 +1,193 @@
+use crate::strip_ansi_codes;
+use crate::terminal::cp437_fallback;
+use crate::width::str_width;
+
+/// The characters of code page 437 from byte `0x80` to byte `0xFF`, in order.
+const CP437_HIGH: &str = "ÇüéâäàåçêëèïîìÄÅÉæÆôöòûùÿÖÜ¢£¥₧ƒáíóúñÑªº¿⌐¬½¼¡«»\
+░▒▓│┤╡╢╖╕╣║╗╝╜╛┐└┴┬├─┼╞╟╚╔╩╦╠═╬╧╨╤╥╙╘╒╓╫╪┘┌█▄▌▐▀αßΓπΣσµτΦΘΩδ∞φε∩≡±≥≤⌠⌡÷≈°∙·√ⁿ²■\u{a0}";
+
+/// `Sauce` is the metadata record appended to an ANSI art file, read by BBS-style viewers and
+/// archives.
+///
+/// # Fields
+///
+/// * `title` - The title of the piece, up to 35 characters.
+/// * `author` - The name or handle of its author, up to 20 characters.
+/// * `group` - The group the author belongs to, up to 20 characters.
+/// * `date` - The date it was made, as `CCYYMMDD`, such as `"20240131"`.
+///
+/// Longer fields are truncated, and characters outside code page 437 are written as `?`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Sauce {
+    pub title: String,
+    pub author: String,
+    pub group: String,
+    pub date: String,
+}
+
+/// Exports a rendered box as a classic ANSI art (`.ans`) file.
+///
+/// Characters are encoded in code page 437, falling back as `Charset::Cp437` does and to `?`
+/// for characters with no equivalent, and escape sequences are kept, so colors survive. Every
+/// line is padded with spaces to the width of the widest line and ended with `\r\n`, so the
+/// width recorded in the SAUCE record matches every line. The file ends with a reset, the
+/// end-of-file character `0x1A` and, if `sauce` is given, a SAUCE record.
+///
+/// # Arguments
+///
+/// * `rendered` - The rendered box, such as returned by `create_text_border`. Render it with
+///   `ColorMode::Always` to keep its colors.
+/// * `sauce` - An optional `Sauce` record describing the piece.
+///
+/// # Returns
+///
+/// * The bytes of the file.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, to_ansi_art, BorderStyle, Sauce, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     ..Default::default()
+/// };
+/// let rendered = create_text_border("Hi", Some(options));
+///
+/// assert_eq!(
+///     to_ansi_art(&rendered, None),
+///     b"\xda\xc4\xc4\xbf\r\n\xb3Hi\xb3\r\n\xc0\xc4\xc4\xd9\r\n\x1b[0m\x1a".to_vec()
+/// );
+///
+/// let sauce = Sauce {
+///     title: "Hi".to_string(),
+///     ..Default::default()
+/// };
+/// let art = to_ansi_art(&rendered, Some(&sauce));
+///
+/// assert_eq!(art.len(), 23 + 128);
+/// assert!(art[23..].starts_with(b"SAUCE00Hi "));
+/// ```
+pub fn to_ansi_art(rendered: &str, sauce: Option<&Sauce>) -> Vec<u8> {
+    // Lines are measured after the fallbacks, which may be narrower than what they replace.
+    let lines: Vec<String> = rendered.split('\n').map(to_cp437).collect();
+    let width = lines
+        .iter()
+        .map(|line| str_width(&strip_ansi_codes(line)))
+        .max()
+        .unwrap_or(0);
+
+    let mut art = Vec::with_capacity(rendered.len() + 2 * lines.len() + 128);
+    for line in &lines {
+        encode(line, &mut art);
+        let padding = width - str_width(&strip_ansi_codes(line));
+        art.extend(std::iter::repeat_n(b' ', padding));
+        art.extend_from_slice(b"\r\n");
+    }
+    art.extend_from_slice(b"\x1b[0m");
+
+    let file_size = art.len();
+    art.push(0x1a);
+    if let Some(sauce) = sauce {
+        sauce.write(file_size, width, lines.len(), &mut art);
+    }
+    art
+}
+
+impl Sauce {
+    /// Appends the 128-byte SAUCE record for a file of `file_size` bytes, before the end-of-file
+    /// character, drawn over `width` columns and `height` lines.
+    fn write(&self, file_size: usize, width: usize, height: usize, art: &mut Vec<u8>) {
+        let field = |text: &str, len: usize, art: &mut Vec<u8>| {
+            let mut encoded = Vec::new();
+            encode(&to_cp437(text), &mut encoded);
+            encoded.resize(len, b' ');
+            art.extend_from_slice(&encoded);
+        };
+        let word = |value: usize| (value.min(u16::MAX as usize) as u16).to_le_bytes();
+
+        art.extend_from_slice(b"SAUCE00");
+        field(&self.title, 35, art);
+        field(&self.author, 20, art);
+        field(&self.group, 20, art);
+        field(&self.date, 8, art);
+        art.extend_from_slice(&(file_size.min(u32::MAX as usize) as u32).to_le_bytes());
+        // Data type 1, character, and file type 1, ANSi.
+        art.extend_from_slice(&[1, 1]);
+        art.extend_from_slice(&word(width));
+        art.extend_from_slice(&word(height));
+        art.extend_from_slice(&[0, 0, 0, 0]);
+        // No comments and no flags.
+        art.extend_from_slice(&[0, 0]);
+        let mut font = b"IBM VGA".to_vec();
+        font.resize(22, 0);
+        art.extend_from_slice(&font);
+    }
+}
+
+/// Returns `text` with every character replaced by its fallback in code page 437, or by `?`
+/// if it has none, leaving escape sequences as they are.
+fn to_cp437(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            let c = if c.is_ascii() { c } else { cp437_fallback(c) };
+            if c.is_ascii() || CP437_HIGH.contains(c) {
+                c
+            } else {
+                '?'
+            }
+        })
+        .collect()
+}
+
+/// Appends `text`, made of characters of code page 437 as returned by `to_cp437`, encoded to
+/// `bytes`.
+fn encode(text: &str, bytes: &mut Vec<u8>) {
+    for c in text.chars() {
+        let byte = CP437_HIGH
+            .chars()
+            .position(|high| high == c)
+            .map_or(c as u8, |index| 0x80 + index as u8);
+        bytes.push(byte);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn characters_without_an_equivalent_become_question_marks() {
+        assert_eq!(to_cp437("╭é字\x1b[31m"), "┌é?\x1b[31m");
+        assert_eq!(
+            to_ansi_art("a\n字", None),
+            b"a\r\n?\r\n\x1b[0m\x1a".to_vec()
+        );
+    }
+
+    #[test]
+    fn lines_are_padded_to_the_widest_ignoring_escapes() {
+        assert_eq!(
+            to_ansi_art("\x1b[31mab\x1b[0m\nc", None),
+            b"\x1b[31mab\x1b[0m\r\nc \r\n\x1b[0m\x1a".to_vec()
+        );
+    }
+
+    #[test]
+    fn sauce_records_the_size_and_truncates_fields() {
+        let sauce = Sauce {
+            title: "t".repeat(40),
+            author: "é".to_string(),
+            ..Default::default()
+        };
+        let art = to_ansi_art("ab\nc", Some(&sauce));
+        let (file, record) = art.split_at(art.len() - 128);
+        assert_eq!(file.last(), Some(&0x1a));
+
+        assert_eq!(&record[7..42], "t".repeat(35).as_bytes());
+        assert_eq!(&record[42..44], b"\x82 ");
+        let size = u32::from_le_bytes([record[90], record[91], record[92], record[93]]);
+        assert_eq!(size as usize, file.len() - 1);
+        assert_eq!(&record[96..100], &[2, 0, 2, 0]);
+    }
+}
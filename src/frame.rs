@@ -0,0 +1,110 @@
+use std::cmp::Ordering;
+
+/// Returns the lines of the box `next` that differ from the lines of the box `previous` in the
+/// same place, with their indices, including the lines below the end of `previous`.
+///
+/// # Arguments
+///
+/// * `previous` - The box rendered last time, such as by `create_text_border`.
+/// * `next` - The box to render now.
+///
+/// # Returns
+///
+/// * A `Vec` of the index and text of each line to redraw, in order.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{changed_lines, create_text_border};
+///
+/// let previous = create_text_border("Step 1/3\nok", None);
+/// let next = create_text_border("Step 2/3\nok", None);
+///
+/// assert_eq!(changed_lines(&previous, &next), vec![(1, "*Step 2/3*")]);
+/// ```
+pub fn changed_lines<'a>(previous: &str, next: &'a str) -> Vec<(usize, &'a str)> {
+    let previous: Vec<&str> = previous.split('\n').collect();
+
+    next.split('\n')
+        .enumerate()
+        .filter(|&(i, line)| previous.get(i) != Some(&line))
+        .collect()
+}
+
+/// Returns the escape sequences redrawing the box `previous` as the box `next` in place,
+/// rewriting only the lines that changed, to avoid flicker when a status box is reprinted
+/// repeatedly.
+///
+/// The cursor is expected at the start of the line below `previous`, as it is after writing
+/// `previous` followed by a newline, and is left at the start of the line below `next`. Lines
+/// of `previous` below the end of `next` are cleared.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, redraw};
+///
+/// let previous = create_text_border("1", None);
+/// let next = create_text_border("2", None);
+///
+/// assert_eq!(redraw(&previous, &next), "\x1b[2A\r*2*\x1b[K\x1b[2B\r");
+/// ```
+pub fn redraw(previous: &str, next: &str) -> String {
+    let previous_height = previous.split('\n').count();
+    let next_height = next.split('\n').count();
+    let mut escapes = String::new();
+    let mut row = previous_height;
+
+    let mut move_to = |escapes: &mut String, target: usize| {
+        match target.cmp(&row) {
+            Ordering::Less => escapes.push_str(&format!("\x1b[{}A", row - target)),
+            Ordering::Greater => escapes.push_str(&format!("\x1b[{}B", target - row)),
+            Ordering::Equal => {}
+        }
+        row = target;
+    };
+
+    let changed = changed_lines(previous, next);
+    for &(i, line) in changed.iter().filter(|&&(i, _)| i < previous_height) {
+        move_to(&mut escapes, i);
+        escapes.push_str(&format!("\r{}\x1b[K", line));
+    }
+    for i in next_height..previous_height {
+        move_to(&mut escapes, i);
+        escapes.push_str("\r\x1b[K");
+    }
+
+    move_to(&mut escapes, previous_height.min(next_height));
+    for &(_, line) in changed.iter().filter(|&&(i, _)| i >= previous_height) {
+        escapes.push_str(&format!("\r{}\x1b[K\n", line));
+    }
+    escapes.push('\r');
+    escapes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_boxes_have_nothing_to_redraw() {
+        assert!(changed_lines("a\nb", "a\nb").is_empty());
+        assert_eq!(redraw("a\nb", "a\nb"), "\r");
+    }
+
+    #[test]
+    fn lines_below_a_shorter_box_are_cleared() {
+        assert_eq!(changed_lines("a\nb\nc", "a\nx"), vec![(1, "x")]);
+        assert_eq!(
+            redraw("a\nb\nc", "a\nx"),
+            "\x1b[2A\rx\x1b[K\x1b[1B\r\x1b[K\r"
+        );
+    }
+
+    #[test]
+    fn lines_below_a_taller_box_are_written_after_it() {
+        assert_eq!(changed_lines("a", "a\nb\nc"), vec![(1, "b"), (2, "c")]);
+        assert_eq!(redraw("a", "a\nb\nc"), "\rb\x1b[K\n\rc\x1b[K\n\r");
+        assert_eq!(redraw("a", "x\nb"), "\x1b[1A\rx\x1b[K\x1b[1B\rb\x1b[K\n\r");
+    }
+}
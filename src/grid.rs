@@ -0,0 +1,264 @@
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::width::{char_width, with_ambiguous_width};
+use crate::{default_options, render_to_lines, strip_ansi_codes, TextBorderOptions};
+
+/// `Grid` is a rectangle of character cells, one per terminal column, such as a rendered box.
+///
+/// A double-width character occupies its own cell and the cell to its right, which holds
+/// `Grid::CONTINUATION`. Grids hold characters only; colors and other escape sequences are
+/// not kept.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::render_grid;
+///
+/// let mut grid = render_grid("Hi", None);
+///
+/// assert_eq!((grid.width(), grid.height()), (4, 3));
+/// assert_eq!(grid.get(1, 1), Some('H'));
+///
+/// grid.set(2, 1, '!');
+/// assert_eq!(grid.to_string(), "****\n*H!*\n****");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid {
+    width: usize,
+    rows: Vec<Vec<char>>,
+}
+
+impl Grid {
+    /// The placeholder held by the cell to the right of a double-width character.
+    pub const CONTINUATION: char = '\0';
+
+    /// Creates a grid of `width` by `height` cells filled with spaces.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            rows: vec![vec![' '; width]; height],
+        }
+    }
+
+    /// Creates a grid from lines of text. Escape sequences are dropped and lines shorter than the
+    /// widest one are padded with spaces.
+    pub fn from_lines<S: AsRef<str>>(lines: &[S]) -> Self {
+        let rows: Vec<Vec<char>> = lines
+            .iter()
+            .map(|line| {
+                let mut row = Vec::new();
+                for c in strip_ansi_codes(line.as_ref()).chars() {
+                    row.push(c);
+                    if char_width(c) > 1 {
+                        row.push(Self::CONTINUATION);
+                    }
+                }
+                row
+            })
+            .collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+        let mut grid = Self::new(width, rows.len());
+        for (target, row) in grid.rows.iter_mut().zip(rows) {
+            target[..row.len()].copy_from_slice(&row);
+        }
+        grid
+    }
+
+    /// Returns the width of the grid in cells.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the grid in cells.
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the character at column `x` of row `y`, or `None` if it is outside the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<char> {
+        self.rows.get(y)?.get(x).copied()
+    }
+
+    /// Sets the cell at column `x` of row `y` to `c`. Cells outside the grid are ignored.
+    ///
+    /// A double-width character also claims the cell to its right, and overwriting either half
+    /// of a double-width character blanks its other half.
+    pub fn set(&mut self, x: usize, y: usize, c: char) {
+        let width = self.width;
+        let Some(row) = self.rows.get_mut(y) else {
+            return;
+        };
+        if x >= width {
+            return;
+        }
+
+        let wide = char_width(c) > 1;
+        if wide && x + 1 >= width {
+            return;
+        }
+
+        if x > 0 && row[x] == Self::CONTINUATION {
+            row[x - 1] = ' ';
+        }
+        let end = if wide { x + 1 } else { x };
+        if end + 1 < width && row[end + 1] == Self::CONTINUATION {
+            row[end + 1] = ' ';
+        }
+
+        row[x] = c;
+        if wide {
+            row[x + 1] = Self::CONTINUATION;
+        }
+    }
+
+    /// Draws `other` onto this grid with its top-left cell at column `x` of row `y`.
+    ///
+    /// The position may be negative or extend past the edges; cells falling outside this grid
+    /// are clipped, and a double-width character cut in half by an edge leaves a space.
+    pub fn draw(&mut self, x: isize, y: isize, other: &Grid) {
+        for (dy, row) in other.rows.iter().enumerate() {
+            for (dx, &c) in row.iter().enumerate() {
+                if c != Self::CONTINUATION {
+                    self.put(
+                        x.saturating_add_unsigned(dx),
+                        y.saturating_add_unsigned(dy),
+                        c,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Draws `text` onto this grid starting at column `x` of row `y`, each line of the text on
+    /// its own row. Escape sequences are dropped and cells are clipped as in `Grid::draw`.
+    pub fn draw_text(&mut self, x: isize, y: isize, text: &str) {
+        for (dy, line) in strip_ansi_codes(text).lines().enumerate() {
+            let mut column = x;
+            for c in line.chars() {
+                self.put(column, y.saturating_add_unsigned(dy), c);
+                column = column.saturating_add_unsigned(char_width(c));
+            }
+        }
+    }
+
+    /// Sets the cell at a possibly out-of-bounds position, clipping `c` to the grid.
+    fn put(&mut self, x: isize, y: isize, c: char) {
+        let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) else {
+            // Only the right half of a double-width character starting just left of the grid
+            // is inside it.
+            if x == -1 && y >= 0 && char_width(c) > 1 {
+                self.set(0, y as usize, ' ');
+            }
+            return;
+        };
+
+        if char_width(c) > 1 && x + 1 == self.width {
+            self.set(x, y, ' ');
+        } else {
+            self.set(x, y, c);
+        }
+    }
+
+    /// Returns the rows of the grid.
+    pub fn rows(&self) -> &[Vec<char>] {
+        &self.rows
+    }
+
+    /// Consumes the grid, returning its rows.
+    pub fn into_rows(self) -> Vec<Vec<char>> {
+        self.rows
+    }
+
+    /// Returns each row of the grid as a `String`, without continuation cells.
+    pub fn to_lines(&self) -> Vec<String> {
+        self.rows
+            .iter()
+            .map(|row| row.iter().filter(|&&c| c != Self::CONTINUATION).collect())
+            .collect()
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_lines().join("\n"))
+    }
+}
+
+/// Renders the input message surrounded by a border and margin, like `create_text_border`, into
+/// a `Grid` that can be composited onto other character canvases.
+///
+/// # Arguments
+///
+/// * `message` - The message to be surrounded by a border, such as a `&str` or a `String`.
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations. If `None`, the options returned by `default_options` are used.
+///
+/// # Returns
+///
+/// * A `Grid` holding the bordered message, without colors.
+pub fn render_grid<'a>(
+    message: impl Into<Cow<'a, str>>,
+    options: Option<TextBorderOptions>,
+) -> Grid {
+    let options = options.unwrap_or_else(default_options);
+
+    with_ambiguous_width(options.ambiguous_width, || {
+        Grid::from_lines(&render_to_lines(message, Some(options)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_are_padded_to_the_widest() {
+        let grid = Grid::from_lines(&["\x1b[1mab\x1b[0m", "中", ""]);
+
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid.get(1, 1), Some(Grid::CONTINUATION));
+        assert_eq!(grid.to_lines(), ["ab", "中", "  "]);
+    }
+
+    #[test]
+    fn out_of_bounds_cells_are_ignored() {
+        let mut grid = Grid::new(2, 1);
+        grid.set(2, 0, 'x');
+        grid.set(0, 1, 'x');
+
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, 1), None);
+        assert_eq!(grid.to_string(), "  ");
+    }
+
+    #[test]
+    fn wide_characters_that_do_not_fit_are_not_set() {
+        let mut grid = Grid::new(3, 1);
+        grid.set(2, 0, '中');
+        assert_eq!(grid.to_string(), "   ");
+
+        grid.set(1, 0, '中');
+        assert_eq!(grid.to_string(), " 中");
+    }
+
+    #[test]
+    fn overwriting_half_of_a_wide_character_clears_the_other_half() {
+        let mut grid = Grid::new(4, 1);
+        grid.set(0, 0, '中');
+        grid.set(1, 0, 'x');
+        assert_eq!(grid.to_string(), " x  ");
+
+        grid.set(2, 0, '中');
+        grid.set(1, 0, '文');
+        assert_eq!(grid.to_string(), " 文 ");
+    }
+
+    #[test]
+    fn empty_grids_render_as_nothing() {
+        assert_eq!(Grid::new(0, 0).to_string(), "");
+        assert_eq!(Grid::from_lines::<&str>(&[]).height(), 0);
+    }
+}
@@ -0,0 +1,270 @@
+use std::borrow::Cow;
+
+use font8x8::{UnicodeFonts, BASIC_FONTS, BLOCK_FONTS, BOX_FONTS, GREEK_FONTS, LATIN_FONTS};
+use png::{BitDepth, ColorType, Encoder};
+
+use crate::ansi::copy_escape_sequence;
+use crate::width::char_width;
+use crate::{default_options, render_to_lines, Color, ColorMode, ColorSupport, TextBorderOptions};
+
+/// The number of pixels each pixel of the 8x8 font is scaled to.
+const SCALE: usize = 2;
+
+/// The width and height, in pixels, of a cell of the image.
+const CELL: usize = 8 * SCALE;
+
+/// The colors text and the image are drawn with where the box sets none.
+const FOREGROUND: (u8, u8, u8) = (229, 229, 229);
+const BACKGROUND: (u8, u8, u8) = (30, 30, 30);
+
+/// Creates a PNG image of `message` surrounded by a border and margin as specified by the
+/// provided `TextBorderOptions`, drawn with an embedded 8x8 monospace font.
+///
+/// The image looks the same wherever it is shown, unlike text in fonts that may not be
+/// monospace. Colors of the box are kept, as if written to a true-color terminal. Characters
+/// the font has no glyph for, such as Braille, are drawn as `?`. This function is available
+/// with the `image` feature.
+///
+/// # Arguments
+///
+/// * `message` - The text message to be surrounded by a border.
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations. If `None`, the options returned by `default_options` are used. The
+///   `color_mode` and `color_support` are ignored.
+///
+/// # Returns
+///
+/// * The bytes of a PNG image, one cell of 16 by 16 pixels per column and line of the box,
+///   with a blank cell of padding around it.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::create_border_png;
+///
+/// let png = create_border_png("Hi", None);
+///
+/// assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+/// ```
+pub fn create_border_png<'a>(
+    message: impl Into<Cow<'a, str>>,
+    options: Option<TextBorderOptions>,
+) -> Vec<u8> {
+    let mut options = options.unwrap_or_else(default_options);
+    options.color_mode = ColorMode::Always;
+    options.color_support = Some(ColorSupport::TrueColor);
+
+    let rows: Vec<Vec<Cell>> = render_to_lines(message, Some(options))
+        .iter()
+        .map(|line| cells(line))
+        .collect();
+    let columns = rows
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.width).sum::<usize>())
+        .max()
+        .unwrap_or(0);
+
+    let width = (columns + 2) * CELL;
+    let height = (rows.len() + 2) * CELL;
+    let mut pixels = Canvas {
+        width,
+        data: [BACKGROUND.0, BACKGROUND.1, BACKGROUND.2].repeat(width * height),
+    };
+
+    for (y, row) in rows.iter().enumerate() {
+        let mut x = 0;
+        for cell in row {
+            pixels.draw(x + 1, y + 1, cell);
+            x += cell.width;
+        }
+    }
+
+    let mut png = Vec::new();
+    {
+        let mut encoder = Encoder::new(&mut png, width as u32, height as u32);
+        encoder.set_color(ColorType::Rgb);
+        encoder.set_depth(BitDepth::Eight);
+        let mut writer = encoder
+            .write_header()
+            .expect("writing a PNG header into memory cannot fail");
+        writer
+            .write_image_data(&pixels.data)
+            .expect("writing PNG data into memory cannot fail");
+    }
+    png
+}
+
+/// One character of the box with the colors it is drawn with.
+struct Cell {
+    c: char,
+    width: usize,
+    foreground: Option<(u8, u8, u8)>,
+    background: Option<(u8, u8, u8)>,
+}
+
+/// The RGB pixels of the image, row by row.
+struct Canvas {
+    width: usize,
+    data: Vec<u8>,
+}
+
+impl Canvas {
+    /// Draws `cell` with its top left corner at cell `x` of row `y`.
+    fn draw(&mut self, x: usize, y: usize, cell: &Cell) {
+        let glyph = glyph(cell.c);
+        let foreground = cell.foreground.unwrap_or(FOREGROUND);
+        let background = cell.background.unwrap_or(BACKGROUND);
+
+        for py in 0..CELL {
+            for px in 0..cell.width * CELL {
+                let lit = px < CELL && glyph[py / SCALE] & (1 << (px / SCALE)) != 0;
+                let (r, g, b) = if lit { foreground } else { background };
+                let offset = 3 * ((y * CELL + py) * self.width + x * CELL + px);
+                self.data[offset..offset + 3].copy_from_slice(&[r, g, b]);
+            }
+        }
+    }
+}
+
+/// Returns the glyph of `c` in the embedded font, one byte per row with the leftmost pixel in
+/// the lowest bit.
+fn glyph(c: char) -> [u8; 8] {
+    BASIC_FONTS
+        .get(c)
+        .or_else(|| BOX_FONTS.get(c))
+        .or_else(|| BLOCK_FONTS.get(c))
+        .or_else(|| LATIN_FONTS.get(c))
+        .or_else(|| GREEK_FONTS.get(c))
+        .or_else(|| BASIC_FONTS.get('?'))
+        .unwrap_or([0; 8])
+}
+
+/// Splits a rendered line into its characters, following the SGR color codes it contains.
+fn cells(line: &str) -> Vec<Cell> {
+    let mut cells = Vec::new();
+    let (mut foreground, mut background) = (None, None);
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            let width = char_width(c);
+            if width > 0 {
+                cells.push(Cell {
+                    c,
+                    width,
+                    foreground,
+                    background,
+                });
+            }
+            continue;
+        }
+
+        let mut sequence = String::new();
+        copy_escape_sequence(&mut chars, &mut sequence);
+        if let Some(params) = sequence.strip_prefix('[').and_then(|s| s.strip_suffix('m')) {
+            apply_sgr(params, &mut foreground, &mut background);
+        }
+    }
+    cells
+}
+
+/// Updates the current colors with the parameters of an SGR sequence.
+fn apply_sgr(
+    sequence: &str,
+    foreground: &mut Option<(u8, u8, u8)>,
+    background: &mut Option<(u8, u8, u8)>,
+) {
+    let mut params = sequence
+        .split(';')
+        .map(|param| param.parse::<u32>().unwrap_or(0));
+
+    while let Some(param) = params.next() {
+        let (target, color) = match param {
+            0 => {
+                *foreground = None;
+                *background = None;
+                continue;
+            }
+            39 => (&mut *foreground, None),
+            49 => (&mut *background, None),
+            30..=37 => (&mut *foreground, Some(Color::Ansi256(param as u8 - 30))),
+            90..=97 => (&mut *foreground, Some(Color::Ansi256(param as u8 - 82))),
+            40..=47 => (&mut *background, Some(Color::Ansi256(param as u8 - 40))),
+            100..=107 => (&mut *background, Some(Color::Ansi256(param as u8 - 92))),
+            38 | 48 => {
+                let color = match params.next() {
+                    Some(5) => params.next().map(|index| Color::Ansi256(index as u8)),
+                    Some(2) => {
+                        let mut channel = || params.next().unwrap_or(0) as u8;
+                        Some(Color::Rgb(channel(), channel(), channel()))
+                    }
+                    _ => None,
+                };
+                let target = if param == 38 {
+                    &mut *foreground
+                } else {
+                    &mut *background
+                };
+                (target, color)
+            }
+            _ => continue,
+        };
+        *target = color.map(Color::rgb);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Rgb = Option<(u8, u8, u8)>;
+
+    fn colors(line: &str) -> Vec<(char, Rgb, Rgb)> {
+        cells(line)
+            .into_iter()
+            .map(|cell| (cell.c, cell.foreground, cell.background))
+            .collect()
+    }
+
+    #[test]
+    fn images_are_sized_by_the_cells_with_a_frame() {
+        let png = create_border_png("字", None);
+        let decoder = png::Decoder::new(std::io::Cursor::new(png));
+        let info = decoder.read_info().unwrap().info().clone();
+        // The box is 4 columns and 3 rows, framed by a cell on each side.
+        assert_eq!((info.width, info.height), (6 * 16, 5 * 16));
+    }
+
+    #[test]
+    fn colors_follow_the_escape_sequences() {
+        let red = Color::Ansi256(1).rgb();
+        assert_eq!(
+            colors("a\x1b[31mb\x1b[48;2;1;2;3mc\x1b[mD"),
+            [
+                ('a', None, None),
+                ('b', Some(red), None),
+                ('c', Some(red), Some((1, 2, 3))),
+                ('D', None, None),
+            ]
+        );
+        assert_eq!(
+            colors("\x1b[38;5;1;49mx\x1b[39m\x1b]8;;http://x\x07y\x1b(0q"),
+            [('x', Some(red), None), ('y', None, None), ('q', None, None)]
+        );
+    }
+
+    #[test]
+    fn wide_characters_span_two_cells() {
+        let cells = cells("字a");
+        assert_eq!(
+            cells.iter().map(|cell| cell.width).collect::<Vec<_>>(),
+            [2, 1]
+        );
+    }
+
+    #[test]
+    fn characters_without_a_glyph_are_drawn_as_question_marks() {
+        assert_eq!(glyph('字'), glyph('?'));
+        assert_ne!(glyph('─'), glyph('?'));
+    }
+}
@@ -0,0 +1,106 @@
+use serde::Serialize;
+use serde_json::ser::{PrettyFormatter, Serializer};
+use serde_json::Value;
+
+use crate::{create_text_border, default_options, TextBorderOptions};
+
+/// Creates a string containing `value` pretty-printed as JSON, surrounded by a border and margin
+/// as specified by the provided `TextBorderOptions`.
+///
+/// Nested values are indented by `json_indent` spaces per level. This function is available
+/// with the `json` feature.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value to be pretty-printed and surrounded by a border.
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations. If `None`, the options returned by `default_options` are used.
+///
+/// # Returns
+///
+/// * A `String` containing the pretty-printed JSON surrounded by the specified border and
+///   margin.
+///
+/// # Examples
+///
+/// ```
+/// extern crate serde_json;
+///
+/// use serde_json::json;
+/// use unicode_border::create_json_border;
+///
+/// assert_eq!(
+///     create_json_border(&json!({ "ok": true }), None),
+///     "**************\n*{           *\n*  \"ok\": true*\n*}           *\n**************"
+/// );
+/// ```
+pub fn create_json_border(value: &Value, options: Option<TextBorderOptions>) -> String {
+    let options = options.unwrap_or_else(default_options);
+    let indent = " ".repeat(options.json_indent);
+
+    let mut json = Vec::new();
+    let mut serializer =
+        Serializer::with_formatter(&mut json, PrettyFormatter::with_indent(indent.as_bytes()));
+    value
+        .serialize(&mut serializer)
+        .expect("serializing a JSON value into memory cannot fail");
+
+    create_text_border(
+        String::from_utf8(json).expect("serde_json writes UTF-8"),
+        Some(options),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::create_json_border;
+    use crate::{Sides, TextBorderOptions};
+
+    fn unframed(json_indent: usize) -> Option<TextBorderOptions> {
+        Some(TextBorderOptions {
+            border_thickness: Sides::all(0),
+            json_indent,
+            prevent_trim: true,
+            ..Default::default()
+        })
+    }
+
+    fn trimmed(bordered: String) -> Vec<String> {
+        bordered
+            .lines()
+            .map(|line| line.trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn nested_values_are_indented_by_json_indent() {
+        assert_eq!(
+            trimmed(create_json_border(&json!({ "a": [1] }), unframed(4))),
+            ["{", "    \"a\": [", "        1", "    ]", "}"]
+        );
+        assert_eq!(
+            trimmed(create_json_border(&json!({ "a": [1] }), unframed(0))),
+            ["{", "\"a\": [", "1", "]", "}"]
+        );
+    }
+
+    #[test]
+    fn empty_values_stay_on_one_line() {
+        assert_eq!(create_json_border(&json!({}), unframed(2)), "{}");
+        assert_eq!(create_json_border(&json!([]), unframed(2)), "[]");
+        assert_eq!(
+            create_json_border(&json!(null), None),
+            "******\n*null*\n******"
+        );
+    }
+
+    #[test]
+    fn strings_are_escaped() {
+        assert_eq!(
+            create_json_border(&json!("a\nb\t\"é\""), unframed(2)),
+            "\"a\\nb\\t\\\"é\\\"\""
+        );
+    }
+}
@@ -1,80 +1,1273 @@
+#[cfg(feature = "bumpalo")]
+extern crate bumpalo;
+#[cfg(feature = "csv")]
+extern crate csv;
+#[cfg(feature = "eyre")]
+extern crate eyre;
+#[cfg(feature = "image")]
+extern crate font8x8;
+#[cfg(feature = "image")]
+extern crate png;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "json")]
+extern crate serde_json;
+#[cfg(feature = "syntax")]
+extern crate syntect;
+#[cfg(feature = "terminal")]
+extern crate terminal_size;
+extern crate unicode_linebreak;
+extern crate unicode_normalization;
+extern crate unicode_width;
+
+mod ansi;
+mod batch;
+mod bordered;
+mod builder;
+#[cfg(feature = "tables")]
+mod calendar;
+mod canvas;
+mod color;
+mod compiled;
+mod decorative;
+mod defaults;
+mod diagnostic;
+mod diff;
+mod document;
+mod error;
+#[cfg(feature = "export")]
+mod export;
+mod frame;
+mod grid;
+#[cfg(feature = "image")]
+mod image;
+#[cfg(feature = "json")]
+mod json;
+mod limits;
+mod line_builder;
+mod lines;
+mod markdown;
+#[cfg(feature = "export")]
+mod org;
+mod paginate;
+mod panic;
+mod pattern;
+mod preprocess;
+mod presets;
+#[cfg(all(feature = "serde", feature = "tables"))]
+mod record;
+mod registry;
+mod render;
+#[cfg(feature = "eyre")]
+mod report;
+mod sides;
+mod sparkline;
+mod spec;
+mod status;
+mod stream;
+mod style;
+#[cfg(feature = "syntax")]
+mod syntax;
+#[cfg(feature = "tables")]
+mod table;
+mod terminal;
+mod testing;
+mod theme;
+mod tree;
+mod unbox;
+mod width;
+mod wrap;
+mod writer;
+
+pub use ansi::strip_ansi_codes;
+pub use batch::create_text_borders;
+#[cfg(feature = "bumpalo")]
+pub use batch::create_text_borders_in;
+pub use bordered::Bordered;
+pub use builder::TextBorderOptionsBuilder;
+#[cfg(feature = "tables")]
+pub use calendar::Calendar;
+pub use canvas::Canvas;
+pub use color::Color;
+pub use compiled::CompiledTheme;
+pub use decorative::DecorativeBorder;
+pub use defaults::{
+    default_options, reset_default_options, scoped_default_options, set_default_options,
+    DefaultOptionsGuard,
+};
+pub use diagnostic::{Diagnostic, Severity, Snippet};
+pub use document::{Alignment, BorderedDocument, Region};
+pub use error::ConfigError;
+#[cfg(feature = "export")]
+pub use export::{to_ansi_art, Sauce};
+pub use frame::{changed_lines, redraw};
+pub use grid::{render_grid, Grid};
+#[cfg(feature = "image")]
+pub use image::create_border_png;
+#[cfg(feature = "json")]
+pub use json::create_json_border;
+pub use limits::{Dimension, LimitAction, Limits};
+pub use lines::LineStyle;
+#[cfg(feature = "export")]
+pub use org::create_org_block;
+pub use paginate::paginate;
+pub use panic::install_panic_hook;
+pub use pattern::Pattern;
+pub use preprocess::BlankLines;
+#[cfg(all(feature = "serde", feature = "tables"))]
+pub use record::RecordError;
+pub use registry::{create_text_border_themed, register_theme, theme, unregister_theme};
+#[cfg(feature = "eyre")]
+pub use report::{install_eyre_hook, BorderedHandler};
+pub use sides::{ParseSidesError, Sides};
+pub use sparkline::sparkline;
+pub use spec::{BorderSpec, ContentSpec, SpacingSpec};
+pub use status::StatusBar;
+pub use stream::{box_stream, render_content_only, stream_text_border};
+pub use style::Style;
+#[cfg(feature = "syntax")]
+pub use syntax::create_highlighted_border;
+#[cfg(feature = "tables")]
+pub use table::{ColumnAlignment, Table};
+pub use terminal::{terminal_width, Charset, ColorMode, ColorSupport};
+#[doc(hidden)]
+pub use testing::bordered_diff;
+pub use theme::Theme;
+pub use tree::Tree;
+pub use unbox::{reflow, unbox, Unboxed};
+pub use width::{AmbiguousWidth, Width};
+pub use writer::BorderedWriter;
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use ansi::sanitize;
+use lines::{corner, Corner};
+use render::Renderer;
+use width::with_ambiguous_width;
+
+/// The largest border or margin thickness, in cells, that is rendered on any side.
+///
+/// Larger thicknesses are capped to this value when rendering, and rejected by
+/// `TextBorderOptionsBuilder::build`, so that a huge size cannot overflow the width arithmetic
+/// or attempt an absurd allocation.
+pub const MAX_THICKNESS: usize = 1024;
+
+/// The largest content width, in columns, of a box whose width is fixed up front, as with
+/// `stream_text_border` and `BorderedWriter`. Larger widths are capped to this value, as are
+/// the `min_width` and `target_width` a box is widened to, the `column_gap` between columns and
+/// the tab width of a `code_block`.
+pub const MAX_CONTENT_WIDTH: usize = 1 << 16;
+
+/// `BorderStyle` selects the set of characters used to draw the border.
+///
+/// # Variants
+///
+/// * `Char` - Every border cell, corners included, uses `border_char`.
+/// * `HalfBlock` - Half-block edges (`▄ ▀ ▐ ▌`) with quadrant corners (`▗ ▖ ▝ ▘`). The blocks
+///   hug the message, producing a thinner, tighter frame than full box-drawing lines in many fonts.
+/// * `Weighted` - Box-drawing lines whose weight is selected by each side's `border_thickness`
+///   instead of repeating rows and columns: 1 draws light lines (`─ │ ┌`), 2 double lines
+///   (`═ ║ ╔`) and 3 or more heavy lines (`━ ┃ ┏`). Every side is at most one cell thick and
+///   corners join sides of different weights (`╓`, `┍`, ...).
+/// * `Braille` - Experimental. Braille dot patterns (`⣀ ⠉ ⢸ ⡇`) draw a sub-cell line along the
+///   inner edge of each border cell, for very dense dashboards. Rendering depends heavily on
+///   the font.
+/// * `Brackets` - Only the four corners are drawn, as corner brackets (`⌜ ⌝ ⌞ ⌟`), leaving the
+///   edges open. Popular for quote cards and screenshots.
+/// * `Corners` - Like `Brackets`, with custom corner characters in the order top-left, top-right,
+///   bottom-left, bottom-right.
+/// * `Lines` - Box-drawing lines with a `LineStyle` chosen for each side, or `None` to leave the
+///   side out, overriding `border_thickness`. Corners join sides of different styles (`┍ ┑`,
+///   `╓`, ...).
+/// * `Custom` - A complete set of `BorderChars`, including the junctions joining rules to the
+///   sides.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, BorderStyle, LineStyle, Sides, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::HalfBlock,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(create_text_border("Hi", Some(options)), "▗▄▄▖\n▐Hi▌\n▝▀▀▘");
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     border_thickness: Sides::new(2, 1, 2, 3),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(create_text_border("Hi", Some(options)), "╓──╖\n║Hi║\n┗━━┛");
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Brackets,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(create_text_border("Hi", Some(options)), "⌜  ⌝\n Hi \n⌞  ⌟");
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Lines(Sides {
+///         top: Some(LineStyle::Heavy),
+///         bottom: None,
+///         ..Sides::all(Some(LineStyle::Light))
+///     }),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(create_text_border("Hi", Some(options)), "┍━━┑\n│Hi│");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BorderStyle {
+    #[default]
+    Char,
+    HalfBlock,
+    Weighted,
+    Braille,
+    Brackets,
+    Corners(char, char, char, char),
+    Lines(Sides<Option<LineStyle>>),
+    Custom(BorderChars),
+}
+
+/// `BorderChars` is a complete set of characters for drawing boxes, for use with
+/// `BorderStyle::Custom`, so a custom character set is used consistently by the border, the
+/// rules dividing a box into regions, such as the header of a `Table`, and the labels set into
+/// the border.
+///
+/// # Fields
+///
+/// * `top_left` - The top-left corner.
+/// * `top` - The top edge.
+/// * `top_right` - The top-right corner.
+/// * `left` - The left edge.
+/// * `right` - The right edge.
+/// * `bottom_left` - The bottom-left corner.
+/// * `bottom` - The bottom edge.
+/// * `bottom_right` - The bottom-right corner.
+/// * `tee_left` - The junction joining a rule to the left edge (`├`). It also closes a label
+///   set into the top or bottom edge.
+/// * `tee_right` - The junction joining a rule to the right edge (`┤`). It also opens a label
+///   set into the top or bottom edge.
+/// * `cross` - The junction where a rule crosses a vertical line (`┼`).
+///
+/// The default is the light box-drawing set, `┌ ─ ┐ │ │ └ ─ ┘ ├ ┤ ┼`.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, BorderChars, BorderStyle, BorderedDocument};
+/// use unicode_border::TextBorderOptions;
+///
+/// let chars = BorderChars {
+///     top_left: '╭',
+///     top_right: '╮',
+///     bottom_left: '╰',
+///     bottom_right: '╯',
+///     tee_left: '╞',
+///     tee_right: '╡',
+///     ..Default::default()
+/// };
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Custom(chars),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border(
+///         "there",
+///         Some(TextBorderOptions {
+///             title: Some("Hi".to_string()),
+///             ..options.clone()
+///         })
+///     ),
+///     "╭─╡ Hi ╞─╮\n│there   │\n╰────────╯"
+/// );
+///
+/// let mut document = BorderedDocument::new(Some(options));
+/// document.header.lines.push("Hi".to_string());
+/// document.push_line("there");
+///
+/// assert_eq!(document.render(), "╭─────╮\n│Hi   │\n╞─────╡\n│there│\n╰─────╯");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BorderChars {
+    pub top_left: char,
+    pub top: char,
+    pub top_right: char,
+    pub left: char,
+    pub right: char,
+    pub bottom_left: char,
+    pub bottom: char,
+    pub bottom_right: char,
+    pub tee_left: char,
+    pub tee_right: char,
+    pub cross: char,
+}
+
+impl Default for BorderChars {
+    fn default() -> Self {
+        BorderChars::lines(Sides::all(LineStyle::Light))
+    }
+}
+
+impl BorderChars {
+    /// Returns box-drawing characters with a line style per side, and the corners joining them.
+    /// The junctions follow the style of the top side.
+    pub fn lines(sides: Sides<LineStyle>) -> Self {
+        let Sides {
+            left,
+            top,
+            right,
+            bottom,
+        } = sides;
+        let (tee_right, tee_left) = top.label_delimiters();
+        BorderChars {
+            top_left: corner(Corner::TopLeft, top, left),
+            top: top.horizontal(),
+            top_right: corner(Corner::TopRight, top, right),
+            left: left.vertical(),
+            right: right.vertical(),
+            bottom_left: corner(Corner::BottomLeft, bottom, left),
+            bottom: bottom.horizontal(),
+            bottom_right: corner(Corner::BottomRight, bottom, right),
+            tee_left,
+            tee_right,
+            cross: top.cross(),
+        }
+    }
+
+    /// Returns characters using `c` for every part of a border.
+    fn uniform(c: char) -> Self {
+        BorderChars {
+            top_left: c,
+            top: c,
+            top_right: c,
+            left: c,
+            right: c,
+            bottom_left: c,
+            bottom: c,
+            bottom_right: c,
+            tee_left: c,
+            tee_right: c,
+            cross: c,
+        }
+    }
+
+    /// Returns the characters in order, corners and edges first.
+    pub(crate) fn to_array(self) -> [char; 11] {
+        [
+            self.top_left,
+            self.top,
+            self.top_right,
+            self.left,
+            self.right,
+            self.bottom_left,
+            self.bottom,
+            self.bottom_right,
+            self.tee_left,
+            self.tee_right,
+            self.cross,
+        ]
+    }
+
+    /// Returns the stand-ins for these characters with `charset`.
+    pub(crate) fn with_charset(self, charset: Charset) -> Self {
+        let fallback = |c: char| charset.fallback(c);
+        BorderChars {
+            top_left: fallback(self.top_left),
+            top: fallback(self.top),
+            top_right: fallback(self.top_right),
+            left: fallback(self.left),
+            right: fallback(self.right),
+            bottom_left: fallback(self.bottom_left),
+            bottom: fallback(self.bottom),
+            bottom_right: fallback(self.bottom_right),
+            tee_left: fallback(self.tee_left),
+            tee_right: fallback(self.tee_right),
+            cross: fallback(self.cross),
+        }
+    }
+}
+
+/// The characters opening and closing the labels set into the top and bottom borders, if
+/// labels are delimited.
+#[derive(Clone, Copy)]
+pub(crate) struct LabelDelimiters {
+    pub(crate) top: Option<(char, char)>,
+    pub(crate) bottom: Option<(char, char)>,
+}
+
+impl LabelDelimiters {
+    /// Returns the stand-ins for these delimiters with `charset`. Delimiters falling back to `+`
+    /// become `|`, so labels read as `-| title |-`.
+    pub(crate) fn with_charset(self, charset: Charset) -> Self {
+        let delimiters = |delimiters: Option<(char, char)>| {
+            delimiters.map(|(left, right)| {
+                let delimiter = |c: char| match charset.fallback(c) {
+                    '+' => '|',
+                    c => c,
+                };
+                (delimiter(left), delimiter(right))
+            })
+        };
+        LabelDelimiters {
+            top: delimiters(self.top),
+            bottom: delimiters(self.bottom),
+        }
+    }
+}
+
+/// Returns the line style of a `BorderStyle::Weighted` side `thickness` cells thick.
+fn weight(thickness: usize) -> LineStyle {
+    match thickness {
+        0 | 1 => LineStyle::Light,
+        2 => LineStyle::Double,
+        _ => LineStyle::Heavy,
+    }
+}
+
+impl BorderStyle {
+    pub(crate) fn chars(&self, border_char: char, border_thickness: Sides<usize>) -> BorderChars {
+        match *self {
+            BorderStyle::Char => BorderChars::uniform(border_char),
+            BorderStyle::HalfBlock => BorderChars {
+                top_left: '▗',
+                top: '▄',
+                top_right: '▖',
+                left: '▐',
+                right: '▌',
+                bottom_left: '▝',
+                bottom: '▀',
+                bottom_right: '▘',
+                tee_left: '▐',
+                tee_right: '▌',
+                cross: '▄',
+            },
+            BorderStyle::Weighted => BorderChars::lines(border_thickness.map(weight)),
+            BorderStyle::Lines(sides) => {
+                BorderChars::lines(sides.map(|side| side.unwrap_or(LineStyle::Light)))
+            }
+            BorderStyle::Braille => BorderChars {
+                top_left: '⢀',
+                top: '⣀',
+                top_right: '⡀',
+                left: '⢸',
+                right: '⡇',
+                bottom_left: '⠈',
+                bottom: '⠉',
+                bottom_right: '⠁',
+                tee_left: '⢸',
+                tee_right: '⡇',
+                cross: '⣀',
+            },
+            BorderStyle::Brackets => {
+                BorderStyle::Corners('⌜', '⌝', '⌞', '⌟').chars(border_char, border_thickness)
+            }
+            BorderStyle::Corners(top_left, top_right, bottom_left, bottom_right) => BorderChars {
+                top_left,
+                top_right,
+                bottom_left,
+                bottom_right,
+                ..BorderChars::uniform(' ')
+            },
+            BorderStyle::Custom(chars) => chars,
+        }
+    }
+
+    /// Returns the delimiters of the labels set into the border drawn with this style.
+    pub(crate) fn label_delimiters(&self, border_thickness: Sides<usize>) -> LabelDelimiters {
+        let lines = |top: LineStyle, bottom: LineStyle| LabelDelimiters {
+            top: Some(top.label_delimiters()),
+            bottom: Some(bottom.label_delimiters()),
+        };
+        match *self {
+            BorderStyle::Weighted => lines(
+                weight(border_thickness.top),
+                weight(border_thickness.bottom),
+            ),
+            BorderStyle::Lines(sides) => {
+                let sides = sides.map(|side| side.unwrap_or(LineStyle::Light));
+                lines(sides.top, sides.bottom)
+            }
+            BorderStyle::Custom(chars) => LabelDelimiters {
+                top: Some((chars.tee_right, chars.tee_left)),
+                bottom: Some((chars.tee_right, chars.tee_left)),
+            },
+            _ => LabelDelimiters {
+                top: None,
+                bottom: None,
+            },
+        }
+    }
+}
+
+/// The characters of each layer of a border, per side, outermost layer first.
+pub type BorderLayers = Sides<Vec<char>>;
+
 /// `TextBorderOptions` is a structure used to specify the configuration for text borders.
 ///
 /// # Fields
 ///
-/// * `border_char` - The character used to create the border.
-/// * `border_thickness` - A tuple specifying the border thickness in the order (left, top, right, bottom).
-/// * `margin_thickness` - A tuple specifying the margin thickness in the order (left, top, right, bottom).
+/// * `border_char` - The character used to create the border when `border_style` is `BorderStyle::Char`.
+///   Double-width characters such as `＃` are supported: horizontal edges repeat them half as
+///   often, and the interior is widened by a column where needed to keep the edges aligned.
+/// * `border_style` - The `BorderStyle` selecting the characters used to draw the border.
+/// * `border_thickness` - The `Sides` specifying the border thickness of each side.
+/// * `margin_thickness` - The `Sides` specifying the margin thickness of each side.
 /// * `prevent_trim` - A boolean flag indicating whether to prevent trimming whitespace from the message.
+/// * `border_color` - An optional `Color` for the border characters.
+/// * `background` - An optional `Color` painted behind the margins and message. Every line is padded
+///   to the width of the box so the painted area is a solid rectangle.
+/// * `color_mode` - The `ColorMode` deciding whether colors are written at all. The default,
+///   `ColorMode::Auto`, respects `NO_COLOR` and leaves colors out when output is piped. Colors
+///   are only written with the `color` feature.
+/// * `color_support` - The colors the output terminal can display. Colors are downgraded to fit.
+///   If `None`, the support is detected from the environment with `ColorSupport::detect`.
+/// * `charset` - The `Charset` borders are drawn with. `Charset::Ascii` downgrades box-drawing
+///   characters to `+`, `-` and `|` for terminals that cannot display them, `Charset::Cp437`
+///   keeps to the characters legacy Windows consoles can display, `Charset::DecGraphics` draws
+///   lines with the VT100 alternate character set, and `Charset::Auto` picks one when
+///   `Charset::detect` guesses the terminal needs it. Message text is left as it is.
+/// * `title` - An optional title set into the outermost row of the top border. The box is widened
+///   if needed to fit it. Ignored when the top border thickness is 0.
+/// * `title_icon` - An optional icon, such as an emoji, shown before the title. Emoji presentation
+///   sequences such as `⚠️`, a character followed by U+FE0F, are measured as two columns, so
+///   the corners of the box stay aligned.
+/// * `title_badge` - An optional badge, such as a counter or a level, shown after the title.
+/// * `badge_color` - An optional `Color` for the badge, so it stands out from the title.
+/// * `footer` - An optional label set into the outermost row of the bottom border, aligned left.
+///   Ignored, like `footer_status`, when the bottom border thickness is 0.
+/// * `footer_status` - An optional status segment, such as an elapsed time or a version, set into
+///   the outermost row of the bottom border and aligned right, independently of `footer`.
+/// * `normalize` - A boolean flag indicating whether to normalize the message to Unicode
+///   Normalization Form C before measuring it, so decomposed input such as `e` followed by a
+///   combining accent renders and compares the same as its precomposed form.
+/// * `wrap_width` - An optional maximum width, in columns, of the message. Longer lines are wrapped
+///   following the Unicode Line Breaking Algorithm (UAX #14): no break after an opening bracket
+///   or at a no-break space, and breaks between CJK characters. URLs are kept whole where they
+///   fit, and split after a `/` where they do not. Other words wider than the limit are split.
+///   Continuation lines of list items, such as `- item` or `1. item`, are indented past the
+///   marker.
+/// * `ambiguous_width` - The `AmbiguousWidth` deciding whether East Asian Ambiguous characters are
+///   measured as one or two columns. Set it to `AmbiguousWidth::Wide` for CJK terminals configured
+///   with ambiguous=wide.
+/// * `limits` - The `Limits` capping the thicknesses and the overall size of the box. By default
+///   only `MAX_THICKNESS` applies.
+/// * `target_width` - An optional `Width` the whole box should fit in, such as the width of the
+///   terminal. A box that would be wider is shrunk responsively: the left and right margins are
+///   reduced first, then the message is wrapped, and lines that still do not fit are truncated.
+/// * `min_width` - An optional minimum `Width` of the whole box. Narrower messages are padded on
+///   the right, so short messages still produce a reasonably sized panel.
+/// * `max_width` - An optional maximum `Width` of the whole box. Longer lines are wrapped to fit,
+///   keeping the margins. Unlike `target_width`, nothing is truncated.
+/// * `aspect_ratio` - An optional ratio of the width of the box to its height, such as `1.0` for
+///   a square badge. The margins are grown evenly on opposite sides until the box approximates
+///   the ratio, assuming terminal cells twice as tall as they are wide.
+/// * `show_invisibles` - A boolean flag enabling a diagnostic mode where spaces are shown as `·`
+///   and tabs as `→`, and trailing whitespace is highlighted in inverse video when colors are
+///   written. Whitespace is never trimmed in this mode, which helps when boxing test failure
+///   diffs where whitespace matters.
+/// * `highlights` - Byte ranges of the message, each paired with the `Style` it is highlighted
+///   with, such as the matches in a search result snippet. The escape sequences inserted take up
+///   no width. Highlights are left out when colors are not written, and apply to functions
+///   taking the message as a single string, such as `create_text_border`.
+/// * `border_layers` - Optional characters for each layer of a border thicker than 1, given per
+///   side, outermost layer first. Each layer is drawn as
+///   a ring of its own. Layers without a character use `border_char`. Only applies to
+///   `BorderStyle::Char`.
+/// * `margin_pattern` - An optional `Pattern` filling the margins, and the padding to the right
+///   of lines shorter than the longest, in place of spaces.
+/// * `header` - An optional title bar row inside the box, above the top margin, aligned left
+///   and separated from the message by a rule.
+/// * `header_controls` - Optional decorations such as `[-] [x]` aligned right in the header row,
+///   as in the title bar of a window. Either of `header` and `header_controls` adds the row.
+/// * `tabs` - Labels set as tabs into the outermost row of the top border, such as
+///   `┌─[ Logs ]──[ Stats ]──┐`, to show which of several outputs is displayed. Tabs take the
+///   place of the title and are ignored when the top border thickness is 0.
+/// * `active_tab` - The optional index into `tabs` of the displayed tab, shown in inverse video
+///   when colors are written.
+/// * `status_bar` - An optional `StatusBar` row inside the box, below the bottom margin, with
+///   segments aligned left, centered and aligned right.
+/// * `key_hints` - The key hints, as pairs of a key and what it does, shown in a row inside the
+///   box below the status bar, such as `q quit · / search`. Hints that do not fit the width of
+///   the box are left out, from the last, and replaced by `…`.
+/// * `blank_lines` - The `BlankLines` deciding whether blank lines of the message are kept,
+///   collapsed to one or removed.
+/// * `dedent` - A boolean flag indicating whether to strip the leading whitespace common to
+///   every line of the message that is not blank, so indented raw string literals render flush
+///   inside the box. It applies to functions taking the whole message, such as
+///   `create_text_border`, and not to streamed lines.
+/// * `code_block` - An optional tab width enabling code mode, for framing source snippets
+///   precisely: tabs are expanded to tab stops every so many columns, indentation is preserved
+///   exactly and lines are never trimmed, so the box is as wide as the longest expanded line.
+///   Tab widths are capped to `MAX_CONTENT_WIDTH`.
+/// * `markdown` - A boolean flag indicating whether to convert simple inline Markdown in the
+///   message, `**bold**`, `*italic*` and `` `code` ``, to bold, italic and inverse video. When
+///   colors are not written, the markers are only removed.
+/// * `diff` - A boolean flag enabling diff mode, where lines of a unified diff are colored by
+///   their prefix when colors are written: added lines (`+`) green, removed lines (`-`) red,
+///   hunk headers (`@@`) cyan and file headers (`+++`, `---`) bold. Lines are never trimmed, so
+///   context lines keep their leading space.
+/// * `json_indent` - The number of spaces each level of nesting is indented by when
+///   `create_json_border` pretty-prints JSON.
+/// * `theme` - An optional `Theme` styling the border, title, badge and message by their role.
+///   Colors set directly, such as `border_color`, take precedence.
+/// * `columns` - The number of columns the message is flowed into, newspaper style, filling
+///   each column top to bottom before the next so they end up of roughly equal height. With
+///   `max_width`, lines are wrapped to fit a column. The default, 1, leaves the message as it is.
+/// * `column_gap` - The number of spaces between columns, capped to `MAX_CONTENT_WIDTH`.
+/// * `plain` - A boolean flag enabling plain mode, for screen readers, which would otherwise
+///   read out every box-drawing character: the box is replaced by a `=== title ===` header,
+///   the message indented by two spaces and a `--- footer ---` line. Margins, widths and the
+///   rows inside the border are left out. It applies to functions rendering a whole box, such
+///   as `create_text_border` and `BorderedDocument::render`.
+/// * `title_style` - An optional `Style` for the title, such as bold white on a dim gray
+///   border, in place of the title style of the theme. It applies to the title text only, so
+///   the icon, the badge and the border keep their own colors, and it leaves the width of the
+///   box unchanged. Ignored, like other colors, when `color_mode` leaves colors out.
+/// * `border_attributes` - An optional `Style` for the border characters, adding attributes
+///   such as `dim`, `bold`, `underline` or `inverse` to the border color, such as a dim border
+///   so the content stands out. Its foreground is used when `border_color` is not set. Ignored,
+///   like other colors, when `color_mode` leaves colors out.
 ///
 /// # Examples
 ///
 /// ```
+/// use unicode_border::{BorderStyle, Sides, TextBorderOptions};
+///
 /// let options = TextBorderOptions {
 ///     border_char: '#',
-///     border_thickness: (2, 2, 2, 2),
-///     margin_thickness: (1, 1, 1, 1),
+///     border_style: BorderStyle::Char,
+///     border_thickness: Sides::all(2),
+///     margin_thickness: Sides::all(1),
 ///     prevent_trim: true,
+///     ..Default::default()
 /// };
 /// ```
+///
+/// Painting the interior of a multi-line message, with the `color` feature:
+///
+/// ```
+/// # #[cfg(feature = "color")]
+/// # {
+/// use unicode_border::{create_text_border, Color, ColorMode, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     background: Some(Color::Blue),
+///     color_mode: ColorMode::Always,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("Hello\nHi", Some(options)),
+///     "*******\n*\x1b[44mHello\x1b[49m*\n*\x1b[44mHi   \x1b[49m*\n*******"
+/// );
+/// # }
+/// ```
+///
+/// Adding a title with a badge:
+///
+/// ```
+/// use unicode_border::{create_text_border, BorderStyle, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     title: Some("Errors".to_string()),
+///     title_badge: Some("(3)".to_string()),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("disk full", Some(options)),
+///     "┌─┤ Errors (3) ├─┐\n│disk full       │\n└────────────────┘"
+/// );
+/// ```
+///
+/// Adding a footer and a right-aligned status:
+///
+/// ```
+/// use unicode_border::{create_text_border, BorderStyle, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     footer: Some("done".to_string()),
+///     footer_status: Some("1.2s".to_string()),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("Built 12 crates", Some(options)),
+///     "┌───────────────────┐\n│Built 12 crates    │\n└─┤ done ├─┤ 1.2s ├─┘"
+/// );
+/// ```
+///
+/// Normalizing decomposed input:
+///
+/// ```
+/// use unicode_border::{create_text_border, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     normalize: true,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(create_text_border("Cafe\u{301}", Some(options)), "******\n*Café*\n******");
+/// ```
+///
+/// Wrapping long lines:
+///
+/// ```
+/// use unicode_border::{create_text_border, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     wrap_width: Some(10),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("Call foo(bar baz) now", Some(options)),
+///     "**********\n*Call    *\n*foo(bar *\n*baz) now*\n**********"
+/// );
+///
+/// let options = TextBorderOptions {
+///     wrap_width: Some(26),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("Docs: https://example.com/guide", Some(options)),
+///     "***************************\n*Docs:                    *\n*https://example.com/guide*\n***************************"
+/// );
+/// ```
+///
+/// Shrinking to fit a target width:
+///
+/// ```
+/// use unicode_border::{create_text_border, Sides, TextBorderOptions, Width};
+///
+/// let options = TextBorderOptions {
+///     margin_thickness: Sides::symmetric(2, 0),
+///     target_width: Some(Width::Columns(12)),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("Hello", Some(options.clone())),
+///     "***********\n*  Hello  *\n***********"
+/// );
+/// assert_eq!(
+///     create_text_border("Hello, World!", Some(options)),
+///     "********\n*Hello,*\n*World!*\n********"
+/// );
+/// ```
+///
+/// Constraining the width of the box:
+///
+/// ```
+/// use unicode_border::{create_text_border, TextBorderOptions, Width};
+///
+/// let options = TextBorderOptions {
+///     min_width: Some(Width::Columns(8)),
+///     max_width: Some(Width::Columns(10)),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(create_text_border("Hi", Some(options.clone())), "********\n*Hi    *\n********");
+/// assert_eq!(
+///     create_text_border("Hello there, World", Some(options)),
+///     "********\n*Hello *\n*there,*\n*World *\n********"
+/// );
+/// ```
+///
+/// Padding to a square:
+///
+/// ```
+/// use unicode_border::{create_text_border, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     aspect_ratio: Some(1.0),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(create_text_border("OK", Some(options)), "******\n* OK *\n******");
+/// ```
+///
+/// Showing invisible characters:
+///
+/// ```
+/// use unicode_border::{create_text_border, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     show_invisibles: true,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(create_text_border("a\tb ", Some(options)), "******\n*a→b·*\n******");
+/// ```
+///
+/// Highlighting part of the message, with the `color` feature:
+///
+/// ```
+/// # #[cfg(feature = "color")]
+/// # {
+/// use unicode_border::{create_text_border, ColorMode, Style, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     color_mode: ColorMode::Always,
+///     highlights: vec![(4..9, Style { underline: true, ..Default::default() })],
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("the match", Some(options)),
+///     "***********\n*the \x1b[4mmatch\x1b[24m*\n***********"
+/// );
+/// # }
+/// ```
+///
+/// Drawing a border of two layers:
+///
+/// ```
+/// use unicode_border::{create_text_border, Sides, TextBorderOptions};
+///
+/// let layer = vec!['#', '+'];
+/// let options = TextBorderOptions {
+///     border_thickness: Sides::all(2),
+///     border_layers: Some(Sides::all(layer)),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("OK", Some(options)),
+///     "######\n#++++#\n#+OK+#\n#++++#\n######"
+/// );
+/// ```
+///
+/// Showing tabs along the top border:
+///
+/// ```
+/// use unicode_border::{create_text_border, BorderStyle, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     tabs: vec!["Logs".to_string(), "Stats".to_string()],
+///     active_tab: Some(0),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("3 errors", Some(options)),
+///     "┌─[ Logs ]──[ Stats ]─┐\n│3 errors             │\n└─────────────────────┘"
+/// );
+/// ```
+///
+/// Dedenting an indented raw string literal:
+///
+/// ```
+/// use unicode_border::{create_text_border, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     dedent: true,
+///     ..Default::default()
+/// };
+/// let message = "
+///     fn main() {
+///         run();
+///     }
+/// ";
+///
+/// assert_eq!(
+///     create_text_border(message, Some(options)),
+///     "*************\n*fn main() {*\n*    run(); *\n*}          *\n*************"
+/// );
+/// ```
+///
+/// Framing a code snippet indented with tabs:
+///
+/// ```
+/// use unicode_border::{create_text_border, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     code_block: Some(4),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("\tif ok {\n\t\tgo()\n\t}", Some(options)),
+///     "**************\n*    if ok { *\n*        go()*\n*    }       *\n**************"
+/// );
+/// ```
+///
+/// Rendering inline Markdown, with the `color` feature:
+///
+/// ```
+/// # #[cfg(feature = "color")]
+/// # {
+/// use unicode_border::{create_text_border, ColorMode, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     color_mode: ColorMode::Always,
+///     markdown: true,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("**Done** in `1s`", Some(options)),
+///     "************\n*\x1b[1mDone\x1b[22m in \x1b[7m1s\x1b[27m*\n************"
+/// );
+/// # }
+/// ```
+///
+/// Coloring a diff, with the `color` feature:
+///
+/// ```
+/// # #[cfg(feature = "color")]
+/// # {
+/// use unicode_border::{create_text_border, ColorMode, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     color_mode: ColorMode::Always,
+///     diff: true,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("-old\n+new", Some(options)),
+///     "******\n*\x1b[31m-old\x1b[39m*\n*\x1b[32m+new\x1b[39m*\n******"
+/// );
+/// # }
+/// ```
+///
+/// Showing an icon before the title:
+///
+/// ```
+/// use unicode_border::{create_text_border, BorderStyle, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     title: Some("Warning".to_string()),
+///     title_icon: Some("\u{26a0}\u{fe0f}".to_string()),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("disk almost full", Some(options)),
+///     "┌─┤ \u{26a0}\u{fe0f} Warning ├─┐\n│disk almost full│\n└────────────────┘"
+/// );
+/// ```
+///
+/// Drawing the border with ASCII characters only:
+///
+/// ```
+/// use unicode_border::{create_text_border, BorderStyle, Charset, Sides, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     charset: Charset::Ascii,
+///     title: Some("Log".to_string()),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("ready to deploy", Some(options)),
+///     "+-| Log |-------+\n|ready to deploy|\n+---------------+"
+/// );
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     border_thickness: Sides::all(3),
+///     charset: Charset::Cp437,
+///     title: Some("Log".to_string()),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("ready to deploy", Some(options)),
+///     "┌─┤ Log ├───────┐\n│ready to deploy│\n└───────────────┘"
+/// );
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     charset: Charset::DecGraphics,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("OK", Some(options)),
+///     "\x1b(0lqqk\x1b(B\n\x1b(0x\x1b(BOK\x1b(0x\x1b(B\n\x1b(0mqqj\x1b(B"
+/// );
+/// ```
+///
+/// Wrapping a list, keeping continuation lines under the text of each item:
+///
+/// ```
+/// use unicode_border::{create_text_border, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     wrap_width: Some(14),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("- install the package\n10. run the tests", Some(options)),
+///     "***************\n\
+///      *- install the*\n\
+///      *  package    *\n\
+///      *10. run the  *\n\
+///      *    tests    *\n\
+///      ***************"
+/// );
+/// ```
+///
+/// Flowing a message into two columns:
+///
+/// ```
+/// use unicode_border::{create_text_border, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     columns: 2,
+///     column_gap: 3,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("alpha\nbeta\ngamma\ndelta\nepsilon", Some(options)),
+///     "*****************\n\
+///      *alpha   delta  *\n\
+///      *beta    epsilon*\n\
+///      *gamma          *\n\
+///      *****************"
+/// );
+/// ```
+///
+/// Rendering a box in plain mode, for screen readers:
+///
+/// ```
+/// use unicode_border::{create_text_border, BorderStyle, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     title: Some("Build".to_string()),
+///     footer: Some("3 warnings".to_string()),
+///     plain: true,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("compiled 12 crates\nfinished in 4s", Some(options)),
+///     "=== Build ===\n  compiled 12 crates\n  finished in 4s\n--- 3 warnings ---"
+/// );
+/// ```
+///
+/// Styling the title independently of the border, with the `color` feature:
+///
+/// ```
+/// # #[cfg(feature = "color")]
+/// # {
+/// use unicode_border::{create_text_border, BorderStyle, Color, ColorMode, Style};
+/// use unicode_border::{strip_ansi_codes, ColorSupport, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     border_color: Some(Color::BrightBlack),
+///     color_mode: ColorMode::Always,
+///     color_support: Some(ColorSupport::Basic),
+///     title: Some("Build".to_string()),
+///     title_style: Some(Style {
+///         foreground: Some(Color::BrightWhite),
+///         bold: true,
+///         ..Default::default()
+///     }),
+///     ..Default::default()
+/// };
+///
+/// let bordered = create_text_border("compiled", Some(options));
+///
+/// assert!(bordered.contains(" \x1b[97;1mBuild\x1b[39;22m "));
+/// assert_eq!(
+///     strip_ansi_codes(&bordered),
+///     "┌─┤ Build ├─┐\n│compiled   │\n└───────────┘"
+/// );
+/// # }
+/// ```
+///
+/// Dimming the border so the content stands out, with the `color` feature:
+///
+/// ```
+/// # #[cfg(feature = "color")]
+/// # {
+/// use unicode_border::{create_text_border, ColorMode, Style, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     color_mode: ColorMode::Always,
+///     border_attributes: Some(Style {
+///         dim: true,
+///         ..Default::default()
+///     }),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("Hi", Some(options)),
+///     "\x1b[2m****\x1b[22m\n\x1b[2m*\x1b[22mHi\x1b[2m*\x1b[22m\n\x1b[2m****\x1b[22m"
+/// );
+/// # }
+/// ```
+///
+/// Use `TextBorderOptions::builder()` to have the options validated before use.
+///
+/// The fields drawing the border, sizing the box and preparing the message are grouped into a
+/// `BorderSpec`, a `SpacingSpec` and a `ContentSpec`, taken from options with `border`,
+/// `spacing` and `content`, and applied to other options with `with_border`, `with_spacing`
+/// and `with_content`.
+#[derive(Clone, Debug)]
 pub struct TextBorderOptions {
-    border_char: char,
-    border_thickness: (usize, usize, usize, usize),
-    margin_thickness: (usize, usize, usize, usize),
-    prevent_trim: bool,
+    pub border_char: char,
+    pub border_style: BorderStyle,
+    pub border_thickness: Sides<usize>,
+    pub margin_thickness: Sides<usize>,
+    pub prevent_trim: bool,
+    pub border_color: Option<Color>,
+    pub background: Option<Color>,
+    pub color_mode: ColorMode,
+    pub color_support: Option<ColorSupport>,
+    pub charset: Charset,
+    pub title: Option<String>,
+    pub title_badge: Option<String>,
+    pub badge_color: Option<Color>,
+    pub footer: Option<String>,
+    pub footer_status: Option<String>,
+    pub normalize: bool,
+    pub wrap_width: Option<usize>,
+    pub ambiguous_width: AmbiguousWidth,
+    pub limits: Limits,
+    pub target_width: Option<Width>,
+    pub min_width: Option<Width>,
+    pub max_width: Option<Width>,
+    pub aspect_ratio: Option<f64>,
+    pub show_invisibles: bool,
+    pub highlights: Vec<(Range<usize>, Style)>,
+    pub border_layers: Option<BorderLayers>,
+    pub margin_pattern: Option<Pattern>,
+    pub header: Option<String>,
+    pub header_controls: Option<String>,
+    pub tabs: Vec<String>,
+    pub active_tab: Option<usize>,
+    pub status_bar: Option<StatusBar>,
+    pub key_hints: Vec<(String, String)>,
+    pub blank_lines: BlankLines,
+    pub dedent: bool,
+    pub code_block: Option<usize>,
+    pub markdown: bool,
+    pub diff: bool,
+    pub json_indent: usize,
+    pub theme: Option<Theme>,
+    pub title_icon: Option<String>,
+    pub columns: usize,
+    pub column_gap: usize,
+    pub plain: bool,
+    pub title_style: Option<Style>,
+    pub border_attributes: Option<Style>,
 }
 
 impl Default for TextBorderOptions {
     fn default() -> Self {
         Self {
             border_char: '*',
-            border_thickness: (1, 1, 1, 1),
-            margin_thickness: (0, 0, 0, 0),
+            border_style: BorderStyle::Char,
+            border_thickness: Sides::all(1),
+            margin_thickness: Sides::all(0),
             prevent_trim: false,
+            border_color: None,
+            background: None,
+            color_mode: ColorMode::Auto,
+            color_support: None,
+            charset: Charset::Unicode,
+            title: None,
+            title_badge: None,
+            badge_color: None,
+            footer: None,
+            footer_status: None,
+            normalize: false,
+            wrap_width: None,
+            ambiguous_width: AmbiguousWidth::Narrow,
+            limits: Limits::default(),
+            target_width: None,
+            min_width: None,
+            max_width: None,
+            aspect_ratio: None,
+            show_invisibles: false,
+            highlights: Vec::new(),
+            border_layers: None,
+            margin_pattern: None,
+            header: None,
+            header_controls: None,
+            tabs: Vec::new(),
+            active_tab: None,
+            status_bar: None,
+            key_hints: Vec::new(),
+            blank_lines: BlankLines::Keep,
+            dedent: false,
+            code_block: None,
+            markdown: false,
+            diff: false,
+            json_indent: 2,
+            theme: None,
+            title_icon: None,
+            columns: 1,
+            column_gap: 2,
+            plain: false,
+            title_style: None,
+            border_attributes: None,
         }
     }
 }
 
 impl TextBorderOptions {
-    fn create_border_line(&self, message: &str) -> String {
-        self.border_char.to_string().repeat(
-            message.len()
-                + self.border_thickness.0
-                + self.border_thickness.2
-                + self.margin_thickness.0
-                + self.margin_thickness.2,
-        )
-    }
-
-    fn create_margin_line(&self, message: &str) -> String {
-        format!(
-            "{}{}{}",
-            self.border_char.to_string().repeat(self.border_thickness.0),
-            " ".repeat(message.len() + self.margin_thickness.0 + self.margin_thickness.2),
-            self.border_char.to_string().repeat(self.border_thickness.2)
-        )
-    }
-
-    fn create_message_line(&self, message: &str) -> String {
-        format!(
-            "{}{}{}{}{}",
-            self.border_char.to_string().repeat(self.border_thickness.0),
-            " ".repeat(self.margin_thickness.0),
-            message,
-            " ".repeat(self.margin_thickness.2),
-            self.border_char.to_string().repeat(self.border_thickness.2)
-        )
+    /// Returns the width, in columns, left for the message in a box `total_width` columns wide,
+    /// once the left and right borders and margins are taken off, so content can be wrapped or
+    /// truncated beforehand to fit a box exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_border::{create_text_border, Sides, TextBorderOptions};
+    ///
+    /// let options = TextBorderOptions {
+    ///     margin_thickness: Sides::symmetric(1, 0),
+    ///     ..Default::default()
+    /// };
+    /// let width = options.content_width_for(10);
+    ///
+    /// assert_eq!(width, 6);
+    /// assert_eq!(create_text_border("x".repeat(width), Some(options)).find('\n'), Some(10));
+    /// ```
+    pub fn content_width_for(&self, total_width: usize) -> usize {
+        with_ambiguous_width(self.ambiguous_width, || {
+            Renderer::new(self.clone()).content_width_for(total_width)
+        })
     }
 }
 
 /// Creates a string containing the input message, surrounded by a border and margin
 /// as specified by the provided `TextBorderOptions`.
 ///
+/// Rendering never panics, whatever the message and options: thicknesses are capped to
+/// `MAX_THICKNESS` and every width is computed with saturating arithmetic.
+///
 /// # Arguments
 ///
-/// * `message` - The message (`&str`) to be surrounded by a border.
+/// * `message` - The message to be surrounded by a border, such as a `&str` or a `String`. Messages spanning several lines
+///   are framed as a block, each line padded to the width of the longest.
 /// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
-///               configurations. If `None`, default options are used.
+///   configurations. If `None`, the options returned by `default_options` are used.
 ///
 /// # Returns
 ///
@@ -83,39 +1276,260 @@ impl TextBorderOptions {
 /// # Examples
 ///
 /// ```
+/// use unicode_border::{create_text_border, BorderStyle, Sides, TextBorderOptions, MAX_THICKNESS};
+///
 /// let message = "Hello, World!";
 ///
 /// let options = TextBorderOptions {
 ///     border_char: '#',
-///     border_thickness: (2, 2, 2, 2),
-///     margin_thickness: (1, 1, 1, 1),
+///     border_style: BorderStyle::Char,
+///     border_thickness: Sides::all(2),
+///     margin_thickness: Sides::all(1),
 ///     prevent_trim: true,
+///     ..Default::default()
 /// };
 ///
 /// let bordered_text = create_text_border(message, Some(options));
 /// println!("{}", bordered_text);
+///
+/// let options = TextBorderOptions {
+///     border_thickness: Sides::all(0),
+///     margin_thickness: Sides::new(usize::MAX, 0, 0, 0),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(create_text_border("Hi", Some(options)).len(), MAX_THICKNESS + 2);
+/// ```
+pub fn create_text_border<'a>(
+    message: impl Into<Cow<'a, str>>,
+    options: Option<TextBorderOptions>,
+) -> String {
+    let opts = options.unwrap_or_else(default_options);
+    let message = message.into();
+
+    with_ambiguous_width(opts.ambiguous_width, || {
+        let renderer = Renderer::new(opts);
+        let lines = renderer.message_lines(&message);
+        renderer.render(lines).join_lines()
+    })
+}
+
+/// Creates a string containing the input message, surrounded by a border and margin, like
+/// `create_text_border`, unless the box would exceed the `limits` of the options and their
+/// `on_exceed` action is `LimitAction::Error`.
+///
+/// # Arguments
+///
+/// * `message` - The message to be surrounded by a border, such as a `&str` or a `String`.
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations. If `None`, the options returned by `default_options` are used.
+///
+/// # Returns
+///
+/// * The bordered message, or `ConfigError::LimitExceeded` naming the first size over its limit.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{
+///     try_create_text_border, ConfigError, Dimension, LimitAction, Limits, TextBorderOptions,
+/// };
+///
+/// let options = TextBorderOptions {
+///     limits: Limits {
+///         max_width: 8,
+///         on_exceed: LimitAction::Error,
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(try_create_text_border("Hello", Some(options.clone())).unwrap(), "*******\n*Hello*\n*******");
+/// assert_eq!(
+///     try_create_text_border("Hello, World!", Some(options)).unwrap_err(),
+///     ConfigError::LimitExceeded {
+///         dimension: Dimension::Width,
+///         value: 15,
+///         max: 8,
+///     }
+/// );
+/// ```
+pub fn try_create_text_border<'a>(
+    message: impl Into<Cow<'a, str>>,
+    options: Option<TextBorderOptions>,
+) -> Result<String, ConfigError> {
+    let opts = options.unwrap_or_else(default_options);
+    let action = opts.limits.on_exceed;
+    if action == LimitAction::Error {
+        opts.limits
+            .check_thickness(opts.border_thickness, opts.margin_thickness)?;
+    }
+
+    let message = message.into();
+
+    let lines = with_ambiguous_width(opts.ambiguous_width, || {
+        let renderer = Renderer::new(opts);
+        let lines = renderer.message_lines(&message);
+        renderer.render_limited(lines, action)
+    })?;
+    Ok(lines.join_lines())
+}
+
+/// Renders the input message surrounded by a border and margin, like `create_text_border`, but
+/// returns each line of the output separately, without line endings.
+///
+/// # Arguments
+///
+/// * `message` - The message to be surrounded by a border, such as a `&str` or a `String`.
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations. If `None`, the options returned by `default_options` are used.
+///
+/// # Returns
+///
+/// * A `Vec<String>` with one entry per output line, top border first.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::render_to_lines;
+///
+/// assert_eq!(render_to_lines("Hi", None), vec!["****", "*Hi*", "****"]);
+///
+/// let owned = String::from("Hi");
+/// assert_eq!(render_to_lines(&owned, None), render_to_lines(owned, None));
+/// ```
+pub fn render_to_lines<'a>(
+    message: impl Into<Cow<'a, str>>,
+    options: Option<TextBorderOptions>,
+) -> Vec<String> {
+    let opts = options.unwrap_or_else(default_options);
+    let message = message.into();
+
+    with_ambiguous_width(opts.ambiguous_width, || {
+        let renderer = Renderer::new(opts);
+        let lines = renderer.message_lines(&message);
+        renderer.render(lines).into_lines()
+    })
+}
+
+/// Creates a string containing the given lines, surrounded by a border and margin, like
+/// `create_text_border` does for a message spanning several lines, without having to join the
+/// lines into a single string first.
+///
+/// # Arguments
+///
+/// * `lines` - The lines to be surrounded by a border, e.g. a `&[&str]` or a `Vec<String>`.
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations. If `None`, the options returned by `default_options` are used.
+///
+/// # Returns
+///
+/// * A `String` containing the lines surrounded by the specified border and margin.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, create_text_border_from_lines};
+///
+/// assert_eq!(
+///     create_text_border_from_lines(&["Hello", "Hi"], None),
+///     create_text_border("Hello\nHi", None)
+/// );
 /// ```
-pub fn create_text_border(message: &str, options: Option<TextBorderOptions>) -> String {
-    let opts = options.unwrap_or_default();
+pub fn create_text_border_from_lines<I, S>(lines: I, options: Option<TextBorderOptions>) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let opts = options.unwrap_or_else(default_options);
 
-    let output_message = if opts.prevent_trim {
-        message.to_string()
-    } else {
-        message.trim().to_string()
-    };
+    let mut message_lines = Vec::new();
+    for line in lines {
+        match line.as_ref() {
+            "" => message_lines.push(String::new()),
+            line => message_lines.extend(line.lines().map(str::to_string)),
+        }
+    }
 
-    let horizontal_border = opts.create_border_line(&output_message);
-    let margin_line = opts.create_margin_line(&output_message);
+    with_ambiguous_width(opts.ambiguous_width, || {
+        Renderer::new(opts).render(message_lines).join_lines()
+    })
+}
 
-    let mut bordered_message = Vec::new();
+/// Creates a string containing the input bytes, surrounded by a border and margin, like
+/// `create_text_border`, for boxing output that may not be valid UTF-8, such as the output of a
+/// subprocess.
+///
+/// Invalid UTF-8 sequences are replaced with `U+FFFD`, `\r\n` line endings become `\n`, tabs
+/// are expanded to 8-column tab stops, and other control characters, such as lone carriage
+/// returns or backspaces, are removed. Escape sequences are kept, and stripped as usual when
+/// colors are disabled.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes to be surrounded by a border.
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations. If `None`, the options returned by `default_options` are used.
+///
+/// # Returns
+///
+/// * A `String` containing the sanitized text surrounded by the specified border and margin.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::create_text_border_bytes;
+///
+/// assert_eq!(
+///     create_text_border_bytes(b"ok\r\nbad \xff\x08", None),
+///     "*******\n*ok   *\n*bad \u{FFFD}*\n*******"
+/// );
+/// ```
+pub fn create_text_border_bytes(bytes: &[u8], options: Option<TextBorderOptions>) -> String {
+    let opts = options.unwrap_or_else(default_options);
+    let message = with_ambiguous_width(opts.ambiguous_width, || {
+        sanitize(&String::from_utf8_lossy(bytes))
+    });
 
-    bordered_message.extend(vec![horizontal_border.clone(); opts.border_thickness.1]);
-    bordered_message.extend(vec![margin_line.clone(); opts.margin_thickness.1]);
+    create_text_border(message, Some(opts))
+}
 
-    bordered_message.push(opts.create_message_line(&output_message));
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    bordered_message.extend(vec![margin_line.clone(); opts.margin_thickness.3]);
-    bordered_message.extend(vec![horizontal_border; opts.border_thickness.3]);
+    #[test]
+    fn bytes_are_sanitized_before_framing() {
+        assert_eq!(
+            create_text_border_bytes(b"", None),
+            create_text_border("", None)
+        );
+        assert_eq!(
+            create_text_border_bytes(b"a\tb\rc\x07", None),
+            "************\n*a       bc*\n************"
+        );
+        assert_eq!(
+            create_text_border_bytes(b"\xe5\xad", None),
+            create_text_border("\u{FFFD}", None)
+        );
+    }
 
-    bordered_message.join("\n")
+    #[test]
+    fn escape_sequences_in_bytes_follow_the_color_mode() {
+        let options = |color_mode| {
+            Some(TextBorderOptions {
+                color_mode,
+                ..Default::default()
+            })
+        };
+        assert_eq!(
+            create_text_border_bytes(b"\x1b[1mhi\x1b[0m", options(ColorMode::Never)),
+            "****\n*hi*\n****"
+        );
+        #[cfg(feature = "color")]
+        assert!(
+            create_text_border_bytes(b"\x1b[1mhi\x1b[0m", options(ColorMode::Always))
+                .contains("\x1b[1mhi")
+        );
+    }
 }
@@ -0,0 +1,274 @@
+use std::fmt;
+
+use crate::{ConfigError, Sides, MAX_THICKNESS};
+
+/// `Limits` caps the size of a box, protecting services that render boxes with sizes supplied
+/// by untrusted users.
+///
+/// # Fields
+///
+/// * `max_border_thickness` - The largest border thickness allowed on any side.
+/// * `max_margin_thickness` - The largest margin thickness allowed on any side.
+/// * `max_width` - The largest width, in columns, of the whole box.
+/// * `max_height` - The largest height, in rows, of the whole box.
+/// * `on_exceed` - The `LimitAction` taken when a limit is exceeded.
+///
+/// Thicknesses are clamped to their limit. The border and margin are never shrunk to fit
+/// `max_width` or `max_height`: the message is cut instead, lines beyond the height limit being
+/// dropped and the remaining lines truncated to the width limit.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, Limits, Sides, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_thickness: Sides::new(1, 1, 1, 100),
+///     limits: Limits {
+///         max_border_thickness: 1,
+///         max_width: 6,
+///         max_height: 4,
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("Hello, World!\nHow are you?\nBye", Some(options)),
+///     "******\n*Hell*\n*How *\n******"
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+    pub max_border_thickness: usize,
+    pub max_margin_thickness: usize,
+    pub max_width: usize,
+    pub max_height: usize,
+    pub on_exceed: LimitAction,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_border_thickness: MAX_THICKNESS,
+            max_margin_thickness: MAX_THICKNESS,
+            max_width: usize::MAX,
+            max_height: usize::MAX,
+            on_exceed: LimitAction::Clamp,
+        }
+    }
+}
+
+impl Limits {
+    /// Returns an error if a border or margin thickness exceeds its limit.
+    pub(crate) fn check_thickness(
+        &self,
+        border_thickness: Sides<usize>,
+        margin_thickness: Sides<usize>,
+    ) -> Result<(), ConfigError> {
+        check(
+            Dimension::BorderThickness,
+            border_thickness.largest(),
+            self.max_border_thickness.min(MAX_THICKNESS),
+        )?;
+        check(
+            Dimension::MarginThickness,
+            margin_thickness.largest(),
+            self.max_margin_thickness.min(MAX_THICKNESS),
+        )
+    }
+}
+
+/// Returns an error if `value` is larger than `max`.
+pub(crate) fn check(dimension: Dimension, value: usize, max: usize) -> Result<(), ConfigError> {
+    if value > max {
+        Err(ConfigError::LimitExceeded {
+            dimension,
+            value,
+            max,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// `LimitAction` selects what happens when a box exceeds its `Limits`.
+///
+/// # Variants
+///
+/// * `Clamp` - The box is shrunk to fit the limits.
+/// * `Error` - `TextBorderOptionsBuilder::build` and `try_create_text_border` return
+///   `ConfigError::LimitExceeded`. Functions that cannot fail, such as `create_text_border`,
+///   clamp instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LimitAction {
+    #[default]
+    Clamp,
+    Error,
+}
+
+/// `Dimension` names the size that exceeded its limit in a `ConfigError::LimitExceeded`.
+///
+/// # Variants
+///
+/// * `BorderThickness` - The thickness of a side of the border.
+/// * `MarginThickness` - The thickness of a side of the margin.
+/// * `Width` - The width of the whole box.
+/// * `Height` - The height of the whole box.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dimension {
+    BorderThickness,
+    MarginThickness,
+    Width,
+    Height,
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Dimension::BorderThickness => "border thickness",
+            Dimension::MarginThickness => "margin thickness",
+            Dimension::Width => "width",
+            Dimension::Height => "height",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_text_border, try_create_text_border, TextBorderOptions};
+
+    fn options(limits: Limits) -> TextBorderOptions {
+        TextBorderOptions {
+            limits,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn thickness_limits_are_checked() {
+        let limits = Limits {
+            max_border_thickness: 1,
+            max_margin_thickness: 2,
+            ..Default::default()
+        };
+
+        assert_eq!(limits.check_thickness(Sides::all(1), Sides::all(2)), Ok(()));
+        assert_eq!(
+            limits.check_thickness(Sides::new(0, 2, 0, 0), Sides::all(0)),
+            Err(ConfigError::LimitExceeded {
+                dimension: Dimension::BorderThickness,
+                value: 2,
+                max: 1,
+            })
+        );
+        assert_eq!(
+            limits.check_thickness(Sides::all(0), Sides::new(0, 0, 0, 3)),
+            Err(ConfigError::LimitExceeded {
+                dimension: Dimension::MarginThickness,
+                value: 3,
+                max: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn thickness_limits_never_exceed_max_thickness() {
+        let limits = Limits {
+            max_border_thickness: usize::MAX,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            limits.check_thickness(Sides::all(MAX_THICKNESS + 1), Sides::all(0)),
+            Err(ConfigError::LimitExceeded {
+                dimension: Dimension::BorderThickness,
+                value: MAX_THICKNESS + 1,
+                max: MAX_THICKNESS,
+            })
+        );
+    }
+
+    #[test]
+    fn error_action_rejects_oversized_boxes() {
+        let limits = Limits {
+            max_width: 4,
+            on_exceed: LimitAction::Error,
+            ..Default::default()
+        };
+        assert_eq!(
+            try_create_text_border("Hello", Some(options(limits))),
+            Err(ConfigError::LimitExceeded {
+                dimension: Dimension::Width,
+                value: 7,
+                max: 4,
+            })
+        );
+
+        let limits = Limits {
+            max_height: 3,
+            on_exceed: LimitAction::Error,
+            ..Default::default()
+        };
+        assert_eq!(
+            try_create_text_border("a\nb", Some(options(limits))),
+            Err(ConfigError::LimitExceeded {
+                dimension: Dimension::Height,
+                value: 4,
+                max: 3,
+            })
+        );
+        assert_eq!(
+            try_create_text_border("a", Some(options(limits))),
+            Ok("***\n*a*\n***".to_string())
+        );
+    }
+
+    #[test]
+    fn clamp_action_cuts_the_message() {
+        let limits = Limits {
+            max_width: 4,
+            max_height: 3,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            try_create_text_border("Hello\nWorld", Some(options(limits))),
+            Ok("****\n*He*\n****".to_string())
+        );
+    }
+
+    #[test]
+    fn infallible_functions_clamp_on_error() {
+        let limits = Limits {
+            max_border_thickness: 1,
+            max_width: 4,
+            on_exceed: LimitAction::Error,
+            ..Default::default()
+        };
+        let options = TextBorderOptions {
+            border_thickness: Sides::all(3),
+            ..options(limits)
+        };
+
+        assert_eq!(
+            create_text_border("Hello", Some(options)),
+            "****\n*He*\n****"
+        );
+    }
+
+    #[test]
+    fn errors_name_the_dimension() {
+        let error = ConfigError::LimitExceeded {
+            dimension: Dimension::MarginThickness,
+            value: 9,
+            max: 2,
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "margin thickness 9 exceeds the limit of 2"
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use crate::width::char_width;
+
+/// `LineBuilder` writes the pieces of a rendered line into a single buffer, so borders, fills
+/// and padding are pushed in place instead of being built as temporary strings and joined.
+pub(crate) struct LineBuilder {
+    line: String,
+}
+
+impl LineBuilder {
+    /// Creates an empty line with room for `capacity` bytes.
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        LineBuilder {
+            line: String::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the position of the end of the line, to refer to what is pushed after it.
+    pub(crate) fn mark(&self) -> usize {
+        self.line.len()
+    }
+
+    pub(crate) fn push(&mut self, c: char) {
+        self.line.push(c);
+    }
+
+    pub(crate) fn push_str(&mut self, s: &str) {
+        self.line.push_str(s);
+    }
+
+    /// Pushes `count` spaces.
+    pub(crate) fn push_spaces(&mut self, count: usize) {
+        self.push_repeated(' ', count);
+    }
+
+    /// Pushes `c` repeated to fill exactly `width` columns, padding with spaces where a wide
+    /// character does not fit.
+    pub(crate) fn push_repeated(&mut self, c: char, width: usize) {
+        let c_width = char_width(c);
+        let (count, padding) = (width / c_width, width % c_width);
+        self.line.reserve(count * c.len_utf8() + padding);
+        self.line.extend(std::iter::repeat_n(c, count));
+        self.line.extend(std::iter::repeat_n(' ', padding));
+    }
+
+    /// Surrounds what was pushed since `mark` with `open` and `close`.
+    pub(crate) fn wrap_since(&mut self, mark: usize, open: &str, close: &str) {
+        self.line.insert_str(mark, open);
+        self.line.push_str(close);
+    }
+
+    /// Replaces what was pushed since `mark` with the result of `f`.
+    pub(crate) fn map_since(&mut self, mark: usize, f: impl FnOnce(String) -> String) {
+        let tail = self.line.split_off(mark);
+        self.line.push_str(&f(tail));
+    }
+
+    /// Returns the line built.
+    pub(crate) fn finish(self) -> String {
+        self.line
+    }
+}
+
+impl Extend<char> for LineBuilder {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, chars: I) {
+        self.line.extend(chars);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide_characters_are_repeated_into_the_width_and_padded() {
+        let mut line = LineBuilder::with_capacity(0);
+        line.push_repeated('字', 5);
+        line.push_repeated('-', 0);
+        line.push_repeated('字', 1);
+        assert_eq!(line.finish(), "字字  ");
+    }
+
+    #[test]
+    fn text_since_a_mark_can_be_wrapped_and_mapped() {
+        let mut line = LineBuilder::with_capacity(8);
+        line.push('|');
+        let mark = line.mark();
+        line.push_str("ab");
+        line.wrap_since(mark, "[", "]");
+        let mark = line.mark();
+        line.extend("cd".chars());
+        line.map_since(mark, |tail| tail.to_uppercase());
+        line.push_spaces(2);
+        assert_eq!(line.finish(), "|[ab]CD  ");
+    }
+}
@@ -0,0 +1,107 @@
+/// `LineStyle` is the weight of a box-drawing line.
+///
+/// # Variants
+///
+/// * `Light` - Light lines (`─ │ ┌`).
+/// * `Double` - Double lines (`═ ║ ╔`).
+/// * `Heavy` - Heavy lines (`━ ┃ ┏`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineStyle {
+    Light,
+    Double,
+    Heavy,
+}
+
+/// The corners of a box.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl LineStyle {
+    pub(crate) fn horizontal(self) -> char {
+        match self {
+            LineStyle::Light => '─',
+            LineStyle::Double => '═',
+            LineStyle::Heavy => '━',
+        }
+    }
+
+    /// Returns the characters opening and closing a label set into a horizontal line.
+    pub(crate) fn label_delimiters(self) -> (char, char) {
+        match self {
+            LineStyle::Light => ('┤', '├'),
+            LineStyle::Double => ('╡', '╞'),
+            LineStyle::Heavy => ('┫', '┣'),
+        }
+    }
+
+    /// Returns the character where a horizontal and a vertical line of this style cross.
+    pub(crate) fn cross(self) -> char {
+        match self {
+            LineStyle::Light => '┼',
+            LineStyle::Double => '╬',
+            LineStyle::Heavy => '╋',
+        }
+    }
+
+    pub(crate) fn vertical(self) -> char {
+        match self {
+            LineStyle::Light => '│',
+            LineStyle::Double => '║',
+            LineStyle::Heavy => '┃',
+        }
+    }
+}
+
+/// Returns the box-drawing character joining a `horizontal` and a `vertical` line at `corner`.
+///
+/// Unicode has no glyphs mixing double and heavy lines, so such corners are drawn heavy.
+pub(crate) fn corner(corner: Corner, horizontal: LineStyle, vertical: LineStyle) -> char {
+    use self::LineStyle::*;
+
+    // Glyphs ordered (light, light), (light, double), (double, light), (double, double),
+    // (heavy, heavy), (heavy, light), (light, heavy) for (horizontal, vertical).
+    let glyphs = match corner {
+        Corner::TopLeft => ['┌', '╓', '╒', '╔', '┏', '┍', '┎'],
+        Corner::TopRight => ['┐', '╖', '╕', '╗', '┓', '┑', '┒'],
+        Corner::BottomLeft => ['└', '╙', '╘', '╚', '┗', '┕', '┖'],
+        Corner::BottomRight => ['┘', '╜', '╛', '╝', '┛', '┙', '┚'],
+    };
+
+    let index = match (horizontal, vertical) {
+        (Light, Light) => 0,
+        (Light, Double) => 1,
+        (Double, Light) => 2,
+        (Double, Double) => 3,
+        (Heavy, Heavy) | (Heavy, Double) | (Double, Heavy) => 4,
+        (Heavy, Light) => 5,
+        (Light, Heavy) => 6,
+    };
+
+    glyphs[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LineStyle::*;
+    use super::*;
+
+    #[test]
+    fn corners_join_mixed_line_styles() {
+        assert_eq!(corner(Corner::TopLeft, Light, Light), '┌');
+        assert_eq!(corner(Corner::TopRight, Double, Light), '╕');
+        assert_eq!(corner(Corner::BottomLeft, Light, Double), '╙');
+        assert_eq!(corner(Corner::BottomRight, Heavy, Light), '┙');
+        assert_eq!(corner(Corner::TopLeft, Light, Heavy), '┎');
+    }
+
+    #[test]
+    fn heavy_and_double_lines_meet_in_heavy_corners() {
+        assert_eq!(corner(Corner::TopLeft, Heavy, Double), '┏');
+        assert_eq!(corner(Corner::BottomRight, Double, Heavy), '┛');
+    }
+}
@@ -0,0 +1,103 @@
+/// Converts the inline Markdown of `line`, `**bold**`, `*italic*` and `` `code` ``, to escape
+/// sequences, or only removes the markers if `styled` is `false`.
+///
+/// Markers without a closing marker on the same line, or enclosing text that starts or ends with
+/// whitespace, are kept as they are, so `2 * 3 * 4` is left alone. A backslash escapes the
+/// character after it, and the text of code spans is kept literally.
+pub(crate) fn render_inline(line: &str, styled: bool) -> String {
+    let mut rendered = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '\\' {
+            let escaped = rest[1..].chars().next().filter(|c| "\\`*".contains(*c));
+            if let Some(escaped) = escaped {
+                rendered.push(escaped);
+                rest = &rest[1 + escaped.len_utf8()..];
+                continue;
+            }
+        }
+
+        let span = if c == '`' {
+            span(rest, "`").map(|(text, len)| (text.to_string(), ("7", "27"), len))
+        } else if rest.starts_with("**") {
+            span(rest, "**").map(|(text, len)| (render_inline(text, styled), ("1", "22"), len))
+        } else if c == '*' {
+            span(rest, "*").map(|(text, len)| (render_inline(text, styled), ("3", "23"), len))
+        } else {
+            None
+        };
+
+        match span {
+            Some((text, (open, close), len)) => {
+                if styled {
+                    rendered.push_str(&format!("\x1b[{}m{}\x1b[{}m", open, text, close));
+                } else {
+                    rendered.push_str(&text);
+                }
+                rest = &rest[len..];
+            }
+            None => {
+                rendered.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    rendered
+}
+
+/// Returns the text enclosed by `marker` at the start of `text` and the length of the span
+/// including both markers, if it is closed and its text neither starts nor ends with whitespace.
+fn span<'a>(text: &'a str, marker: &str) -> Option<(&'a str, usize)> {
+    let inner = &text[marker.len()..];
+    let end = inner.find(marker)?;
+    let enclosed = &inner[..end];
+
+    let trimmed = enclosed.trim();
+    if trimmed.is_empty() || trimmed.len() != enclosed.len() {
+        return None;
+    }
+    Some((enclosed, 2 * marker.len() + end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_inline;
+
+    #[test]
+    fn emphasis_and_code_are_styled() {
+        assert_eq!(
+            render_inline("**b** *i* `c`", true),
+            "\x1b[1mb\x1b[22m \x1b[3mi\x1b[23m \x1b[7mc\x1b[27m"
+        );
+        assert_eq!(
+            render_inline("**a *b* c**", true),
+            "\x1b[1ma \x1b[3mb\x1b[23m c\x1b[22m"
+        );
+    }
+
+    #[test]
+    fn markers_are_only_removed_without_styles() {
+        assert_eq!(render_inline("**b** *i* `c`", false), "b i c");
+    }
+
+    #[test]
+    fn code_spans_are_not_parsed_further() {
+        assert_eq!(render_inline("`*a*`", false), "*a*");
+    }
+
+    #[test]
+    fn unmatched_and_spaced_markers_are_kept() {
+        assert_eq!(render_inline("2 * 3 * 4", false), "2 * 3 * 4");
+        assert_eq!(render_inline("a* b*", false), "a* b*");
+        assert_eq!(render_inline("**open", false), "**open");
+        assert_eq!(render_inline("``", false), "``");
+        assert_eq!(render_inline("*", false), "*");
+    }
+
+    #[test]
+    fn escaped_markers_are_literal() {
+        assert_eq!(render_inline("\\*a\\* \\` \\\\ \\n", false), "*a* ` \\ \\n");
+    }
+}
@@ -0,0 +1,147 @@
+use std::borrow::Cow;
+
+use crate::render::Renderer;
+use crate::width::with_ambiguous_width;
+use crate::{default_options, strip_ansi_codes, TextBorderOptions};
+
+/// Creates an Emacs Org block containing the input message, in place of a box, so notes can
+/// take in tool output without Unicode frames. The message is prepared as by
+/// `create_text_border`, trimmed, wrapped and so on, without colors.
+///
+/// Messages whose whitespace matters, with `prevent_trim`, `code_block` or `diff` set, are put
+/// in an example block shown as is; others in a quote block. The title, with its icon and
+/// badge, is a bold line above the block, and the footer, with its status, an italic line
+/// below it. This function is available with the `export` feature.
+///
+/// # Arguments
+///
+/// * `message` - The message to be put in the block.
+/// * `options` - An optional `TextBorderOptions` instance specifying how the message is
+///   prepared, and its title and footer. If `None`, the options returned by `default_options`
+///   are used.
+///
+/// # Returns
+///
+/// * A `String` containing the Org block, delimited by `#+begin_` and `#+end_` lines.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_org_block, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     title: Some("Build".to_string()),
+///     code_block: Some(4),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_org_block("* compiled\n  finished in 4s", Some(options)),
+///     "*Build*\n#+begin_example\n,* compiled\n  finished in 4s\n#+end_example"
+/// );
+/// assert_eq!(
+///     create_org_block("  Hello  ", None),
+///     "#+begin_quote\nHello\n#+end_quote"
+/// );
+/// ```
+pub fn create_org_block<'a>(
+    message: impl Into<Cow<'a, str>>,
+    options: Option<TextBorderOptions>,
+) -> String {
+    let opts = options.unwrap_or_else(default_options);
+    let message = message.into();
+    let join = |parts: &[&Option<String>]| {
+        let parts: Vec<&str> = parts.iter().filter_map(|part| part.as_deref()).collect();
+        Some(parts.join(" ")).filter(|label| !label.trim().is_empty())
+    };
+    let title = join(&[&opts.title_icon, &opts.title, &opts.title_badge]);
+    let footer = join(&[&opts.footer, &opts.footer_status]);
+    let kind = if opts.prevent_trim || opts.code_block.is_some() || opts.diff {
+        "example"
+    } else {
+        "quote"
+    };
+
+    let lines = with_ambiguous_width(opts.ambiguous_width, || {
+        let mut renderer = Renderer::new(opts);
+        let lines = renderer.message_lines(&message);
+        renderer.message_rows(lines)
+    });
+
+    let mut block = Vec::new();
+    if let Some(title) = title {
+        block.push(format!("*{}*", title.trim()));
+    }
+    block.push(format!("#+begin_{}", kind));
+    for line in lines {
+        block.push(escape_line(strip_ansi_codes(&line)));
+    }
+    block.push(format!("#+end_{}", kind));
+    if let Some(footer) = footer {
+        block.push(format!("/{}/", footer.trim()));
+    }
+    block.join("\n")
+}
+
+/// Escapes `line` with a comma after its indentation if Org would read it as a heading or a
+/// keyword ending the block, as Org itself escapes the content of blocks.
+fn escape_line(mut line: String) -> String {
+    let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+    let rest = line[indent..].trim_start_matches(',');
+    if rest.starts_with('*') || rest.starts_with("#+") {
+        line.insert(indent, ',');
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headings_and_keywords_are_escaped() {
+        let escape = |line: &str| escape_line(line.to_string());
+        assert_eq!(escape("* heading"), ",* heading");
+        assert_eq!(escape("  #+end_quote"), "  ,#+end_quote");
+        assert_eq!(escape(",* escaped"), ",,* escaped");
+        assert_eq!(escape("a * b"), "a * b");
+        assert_eq!(escape("# comment"), "# comment");
+    }
+
+    #[test]
+    fn labels_are_joined_and_blank_ones_left_out() {
+        let options = TextBorderOptions {
+            title_icon: Some("!".into()),
+            title: Some("Build".into()),
+            title_badge: Some("[2]".into()),
+            footer: Some(" ".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            create_org_block("ok", Some(options)),
+            "*! Build [2]*\n#+begin_quote\nok\n#+end_quote"
+        );
+
+        let options = TextBorderOptions {
+            footer: Some("done".into()),
+            footer_status: Some("3 warnings".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            create_org_block("ok", Some(options)),
+            "#+begin_quote\nok\n#+end_quote\n/done 3 warnings/"
+        );
+    }
+
+    #[test]
+    fn whitespace_sensitive_messages_are_examples() {
+        let options = TextBorderOptions {
+            prevent_trim: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            create_org_block(" a ", Some(options)),
+            "#+begin_example\n a \n#+end_example"
+        );
+    }
+}
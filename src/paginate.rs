@@ -0,0 +1,150 @@
+use std::borrow::Cow;
+
+use crate::render::Renderer;
+use crate::width::{str_width, with_ambiguous_width};
+use crate::{default_options, TextBorderOptions};
+
+/// Splits `message` into a sequence of boxes at most `max_height` lines tall, with a `Page i/N`
+/// footer, so long reports can be written out page by page.
+///
+/// The message is prepared and wrapped once, as by `create_text_border`, and every page is as
+/// wide as the widest, so the boxes line up. The footer of the options is replaced by the page
+/// number, and their `aspect_ratio` and `limits` are not applied.
+///
+/// # Arguments
+///
+/// * `message` - The message to be split and surrounded by borders.
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations. If `None`, the options returned by `default_options` are used.
+/// * `max_height` - The maximum height, in lines, of each box, borders and margins included.
+///   Each page holds at least one line of the message, even if that makes it taller.
+///
+/// # Returns
+///
+/// * A `Vec` of the boxes, one per page, in order.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{paginate, BorderStyle, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     ..Default::default()
+/// };
+/// let pages = paginate("one\ntwo\nthree", Some(options), 4);
+///
+/// assert_eq!(
+///     pages,
+///     vec![
+///         "┌──────────────┐\n│one           │\n│two           │\n└─┤ Page 1/2 ├─┘",
+///         "┌──────────────┐\n│three         │\n└─┤ Page 2/2 ├─┘",
+///     ]
+/// );
+/// ```
+pub fn paginate<'a>(
+    message: impl Into<Cow<'a, str>>,
+    options: Option<TextBorderOptions>,
+    max_height: usize,
+) -> Vec<String> {
+    let options = options.unwrap_or_else(default_options);
+    with_ambiguous_width(options.ambiguous_width, || {
+        let message = message.into();
+        let mut renderer = Renderer::new(options);
+        let lines = renderer.message_lines(&message);
+        let rows = renderer.message_rows(lines);
+        let width = rows.iter().map(|row| str_width(row)).max().unwrap_or(0);
+
+        let page_height = max_height.saturating_sub(renderer.frame_height()).max(1);
+        let pages: Vec<&[String]> = rows.chunks(page_height).collect();
+        let total = pages.len();
+
+        // The label of the last page is the widest, so laying out with it fits every page.
+        renderer.set_footer(format!("Page {}/{}", total, total));
+        let layout = renderer.layout(width);
+        let first_row = renderer.first_message_row();
+
+        pages
+            .iter()
+            .enumerate()
+            .map(|(i, page)| {
+                renderer.set_footer(format!("Page {}/{}", i + 1, total));
+                let mut rows = renderer.top_rows(&layout);
+                for (y, row) in page.iter().enumerate() {
+                    rows.push(renderer.create_message_line(&layout, first_row + y, row));
+                }
+                rows.append(renderer.bottom_rows(&layout, first_row + page.len()));
+                rows.join_lines()
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::paginate;
+    use crate::{Sides, TextBorderOptions};
+
+    #[test]
+    fn every_page_holds_at_least_one_line() {
+        let pages = paginate("a\nb", None, 0);
+
+        assert_eq!(
+            pages,
+            [
+                "**************\n*a           *\n** Page 1/2 **",
+                "**************\n*b           *\n** Page 2/2 **",
+            ]
+        );
+    }
+
+    #[test]
+    fn a_short_message_is_one_page() {
+        let pages = paginate("a", None, usize::MAX);
+        assert_eq!(pages, ["**************\n*a           *\n** Page 1/1 **"]);
+    }
+
+    #[test]
+    fn pages_are_as_wide_as_the_widest() {
+        let options = TextBorderOptions {
+            margin_thickness: Sides::new(0, 1, 0, 0),
+            ..Default::default()
+        };
+        let message: Vec<String> = (1..=12).map(|i| "x".repeat(i)).collect();
+        let pages = paginate(message.join("\n"), Some(options), 6);
+
+        assert_eq!(pages.len(), 4);
+        assert!(pages.iter().all(|page| page.lines().count() <= 6));
+        assert!(pages[3].ends_with("** Page 4/4 **"));
+        let width = |page: &String| page.lines().next().unwrap().len();
+        assert!(pages.iter().all(|page| width(page) == width(&pages[0])));
+    }
+
+    #[test]
+    fn the_footer_is_replaced() {
+        let options = TextBorderOptions {
+            footer: Some("ignored".to_string()),
+            ..Default::default()
+        };
+        let pages = paginate("a", Some(options), 10);
+        assert!(!pages[0].contains("ignored"));
+    }
+
+    #[test]
+    fn the_ambiguous_width_of_the_options_is_used() {
+        let options = TextBorderOptions {
+            ambiguous_width: crate::AmbiguousWidth::Wide,
+            ..Default::default()
+        };
+        assert_eq!(
+            paginate("±±±±±±", Some(options.clone()), 10),
+            [crate::create_text_border(
+                "±±±±±±",
+                Some(TextBorderOptions {
+                    footer: Some("Page 1/1".into()),
+                    ..options
+                })
+            )]
+        );
+    }
+}
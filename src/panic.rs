@@ -0,0 +1,78 @@
+use std::any::Any;
+use std::panic;
+use std::thread;
+
+use crate::{default_options, Diagnostic, Severity, TextBorderOptions};
+
+/// Installs a panic hook that writes each panic message and its location to standard error
+/// inside a red border, making crashes of command line tools stand out to their users. The
+/// hook replaces any hook installed before it.
+///
+/// # Arguments
+///
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations. If `None`, the options returned by `default_options` when the hook is
+///   installed are used. The title names the panicking thread, and the border is red unless
+///   `border_color` is set.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{install_panic_hook, BorderStyle, TextBorderOptions};
+///
+/// install_panic_hook(Some(TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     ..Default::default()
+/// }));
+/// # let _ = std::panic::take_hook();
+/// ```
+pub fn install_panic_hook(options: Option<TextBorderOptions>) {
+    let options = options.unwrap_or_else(default_options);
+
+    panic::set_hook(Box::new(move |info| {
+        let mut message = payload_message(info.payload());
+        if let Some(location) = info.location() {
+            message.push_str(&format!("\n\nat {}", location));
+        }
+
+        let diagnostic = Diagnostic {
+            severity: Severity::Error,
+            title: format!(
+                "thread '{}' panicked",
+                thread::current().name().unwrap_or("<unnamed>")
+            ),
+            message,
+            snippet: None,
+        };
+        eprintln!("{}", diagnostic.render(Some(options.clone())));
+    }));
+}
+
+/// Returns the message of a panic from its payload, which is a `&str` or a `String` for panics
+/// raised by `panic!`, as the default hook prints it.
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_are_taken_from_str_and_string_payloads() {
+        let payload = panic::catch_unwind(|| panic!("static")).unwrap_err();
+        assert_eq!(payload_message(&*payload), "static");
+        let payload = panic::catch_unwind(|| panic!("formatted {}", 1)).unwrap_err();
+        assert_eq!(payload_message(&*payload), "formatted 1");
+    }
+
+    #[test]
+    fn other_payloads_are_named_by_their_type() {
+        let payload = panic::catch_unwind(|| panic::panic_any(42)).unwrap_err();
+        assert_eq!(payload_message(&*payload), "Box<dyn Any>");
+    }
+}
@@ -0,0 +1,107 @@
+use crate::line_builder::LineBuilder;
+use crate::width::char_width;
+
+/// `Pattern` is a decorative fill for the margins of a box, in place of spaces, for banners and
+/// watermark-like backgrounds.
+///
+/// Patterns are laid out over the whole interior of the box, counting columns from its left
+/// edge and rows from its top edge, and only show through where there is no message text. A
+/// double-width character that would not fit in the remaining columns is replaced by a space.
+///
+/// # Variants
+///
+/// * `Checkerboard` - Alternates two characters from cell to cell, shifted by one cell on each
+///   row, like the squares of a checkerboard.
+/// * `Rows` - Fills each row with a single character, cycling through the characters from row
+///   to row. An empty list fills with spaces.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, Pattern, Sides, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     margin_thickness: Sides::all(1),
+///     margin_pattern: Some(Pattern::Checkerboard('░', '▒')),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("OK", Some(options)),
+///     "******\n*░▒░▒*\n*▒OK░*\n*░▒░▒*\n******"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Pattern {
+    Checkerboard(char, char),
+    Rows(Vec<char>),
+}
+
+impl Pattern {
+    /// Returns the character of the pattern at column `x` of row `y` of the interior.
+    fn at(&self, x: usize, y: usize) -> char {
+        match self {
+            Pattern::Checkerboard(even, odd) => {
+                if (x + y).is_multiple_of(2) {
+                    *even
+                } else {
+                    *odd
+                }
+            }
+            Pattern::Rows(chars) if chars.is_empty() => ' ',
+            Pattern::Rows(chars) => chars[y % chars.len()],
+        }
+    }
+
+    /// Pushes `width` columns of the pattern onto `line`, starting at column `x` of row `y` of
+    /// the interior.
+    pub(crate) fn fill(&self, line: &mut LineBuilder, x: usize, y: usize, width: usize) {
+        let mut column = x;
+        let end = x.saturating_add(width);
+        while column < end {
+            let c = self.at(column, y);
+            let c_width = char_width(c);
+            if column + c_width > end {
+                line.push(' ');
+                column += 1;
+            } else {
+                line.push(c);
+                column += c_width;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(pattern: &Pattern, x: usize, y: usize, width: usize) -> String {
+        let mut line = LineBuilder::with_capacity(width);
+        pattern.fill(&mut line, x, y, width);
+        line.finish()
+    }
+
+    #[test]
+    fn checkerboards_shift_from_row_to_row() {
+        let pattern = Pattern::Checkerboard('a', 'b');
+        assert_eq!(fill(&pattern, 0, 0, 4), "abab");
+        assert_eq!(fill(&pattern, 0, 1, 4), "baba");
+        assert_eq!(fill(&pattern, 3, 0, 2), "ba");
+    }
+
+    #[test]
+    fn rows_cycle_through_their_characters() {
+        let pattern = Pattern::Rows(vec!['-', '=']);
+        assert_eq!(fill(&pattern, 0, 2, 3), "---");
+        assert_eq!(fill(&pattern, 5, 3, 3), "===");
+        assert_eq!(fill(&Pattern::Rows(Vec::new()), 0, 0, 2), "  ");
+    }
+
+    #[test]
+    fn wide_characters_that_do_not_fit_become_spaces() {
+        let pattern = Pattern::Rows(vec!['字']);
+        assert_eq!(fill(&pattern, 0, 0, 5), "字字 ");
+        assert_eq!(fill(&pattern, 0, 0, 0), "");
+    }
+}
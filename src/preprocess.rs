@@ -0,0 +1,144 @@
+use crate::strip_ansi_codes;
+
+/// `BlankLines` decides what happens to blank lines of the message, those holding nothing but
+/// whitespace, before it is boxed.
+///
+/// # Variants
+///
+/// * `Keep` - Blank lines are kept as they are. This is the default.
+/// * `Collapse` - Runs of consecutive blank lines are collapsed to a single blank line, so
+///   boxed command output stays compact.
+/// * `Remove` - Blank lines are removed.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, BlankLines, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     blank_lines: BlankLines::Collapse,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("one\n\n\n\ntwo", Some(options)),
+///     "*****\n*one*\n*   *\n*two*\n*****"
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlankLines {
+    #[default]
+    Keep,
+    Collapse,
+    Remove,
+}
+
+impl BlankLines {
+    /// Returns whether `line` is dropped, given whether the line before it was blank, and
+    /// records whether `line` is blank for the next call.
+    pub(crate) fn skip(self, line: &str, previous_blank: &mut bool) -> bool {
+        let blank = strip_ansi_codes(line).trim().is_empty();
+        let skip = match self {
+            BlankLines::Keep => false,
+            BlankLines::Collapse => blank && *previous_blank,
+            BlankLines::Remove => blank,
+        };
+        *previous_blank = blank;
+        skip
+    }
+}
+
+/// Removes the leading whitespace common to every line that is not blank, keeping the
+/// indentation of lines relative to each other. Blank lines lose up to as much.
+pub(crate) fn dedent(lines: &mut [String]) {
+    let indent = |line: &str| line.len() - line.trim_start().len();
+
+    let mut common: Option<&str> = None;
+    for line in lines.iter().filter(|line| !line.trim().is_empty()) {
+        let prefix = &line[..indent(line)];
+        common = Some(match common {
+            None => prefix,
+            Some(common) => {
+                let shared = common
+                    .char_indices()
+                    .zip(prefix.chars())
+                    .find(|&((_, a), b)| a != b)
+                    .map_or(common.len().min(prefix.len()), |((index, _), _)| index);
+                &common[..shared]
+            }
+        });
+    }
+    let common = common.map_or(0, str::len);
+    if common == 0 {
+        return;
+    }
+
+    for line in lines.iter_mut() {
+        let mut strip = common.min(indent(line));
+        while !line.is_char_boundary(strip) {
+            strip -= 1;
+        }
+        line.drain(..strip);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kept(blank_lines: BlankLines, lines: &[&str]) -> Vec<String> {
+        let mut previous_blank = false;
+        lines
+            .iter()
+            .filter(|line| !blank_lines.skip(line, &mut previous_blank))
+            .map(|line| line.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn blank_lines_are_kept_collapsed_or_removed() {
+        let lines = ["a", "", " ", "\x1b[0m", "b", ""];
+
+        assert_eq!(kept(BlankLines::Keep, &lines), lines);
+        assert_eq!(kept(BlankLines::Collapse, &lines), ["a", "", "b", ""]);
+        assert_eq!(kept(BlankLines::Remove, &lines), ["a", "b"]);
+    }
+
+    fn dedented(lines: &[&str]) -> Vec<String> {
+        let mut lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        dedent(&mut lines);
+        lines
+    }
+
+    #[test]
+    fn common_indentation_is_removed_ignoring_blank_lines() {
+        assert_eq!(
+            dedented(&["    a", "", "  ", "      b"]),
+            ["a", "", "", "  b"]
+        );
+    }
+
+    #[test]
+    fn only_identical_whitespace_is_common() {
+        assert_eq!(dedented(&["\t  a", "\t b"]), [" a", "b"]);
+        assert_eq!(dedented(&["\ta", "    b"]), ["\ta", "    b"]);
+    }
+
+    #[test]
+    fn multi_byte_whitespace_is_removed_whole() {
+        assert_eq!(
+            dedented(&["\u{3000}\u{3000}a", "\u{3000}b"]),
+            ["\u{3000}a", "b"]
+        );
+        assert_eq!(
+            dedented(&["\u{3000}a", "\u{2003}b"]),
+            ["\u{3000}a", "\u{2003}b"]
+        );
+    }
+
+    #[test]
+    fn unindented_and_empty_input_is_unchanged() {
+        assert_eq!(dedented(&["a", "  b"]), ["a", "  b"]);
+        assert!(dedented(&[]).is_empty());
+    }
+}
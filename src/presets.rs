@@ -0,0 +1,169 @@
+use crate::{BorderStyle, Sides, TextBorderOptions};
+
+impl TextBorderOptions {
+    /// Returns options that draw only a light vertical rail to the left of the message,
+    /// followed by one column of padding, like a Markdown blockquote.
+    ///
+    /// The options can be adjusted like any others, and wrapping, titles and colors work as for
+    /// full boxes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_border::{create_text_border, TextBorderOptions};
+    ///
+    /// assert_eq!(
+    ///     create_text_border("quoted\ntext", Some(TextBorderOptions::blockquote())),
+    ///     "│ quoted\n│ text  "
+    /// );
+    /// ```
+    pub fn blockquote() -> Self {
+        Self {
+            border_style: BorderStyle::Weighted,
+            border_thickness: Sides::new(1, 0, 0, 0),
+            margin_thickness: Sides::new(1, 0, 0, 0),
+            ..Default::default()
+        }
+    }
+
+    /// Returns options that draw a box like a window: a light frame with `title` in a title bar
+    /// row, `[-] [x]` decorations on its right, and a rule separating it from the body.
+    ///
+    /// Set `header_controls` to `None` to leave the decorations out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_border::{create_text_border, TextBorderOptions};
+    ///
+    /// assert_eq!(
+    ///     create_text_border("Body text", Some(TextBorderOptions::window("App"))),
+    ///     "┌─────────────┐\n\
+    ///      │ App [-] [x] │\n\
+    ///      ├─────────────┤\n\
+    ///      │ Body text   │\n\
+    ///      └─────────────┘"
+    /// );
+    /// ```
+    pub fn window(title: impl Into<String>) -> Self {
+        Self {
+            border_style: BorderStyle::Weighted,
+            margin_thickness: Sides::symmetric(1, 0),
+            header: Some(title.into()),
+            header_controls: Some("[-] [x]".to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Returns options that draw a light frame with a row of key hints inside its bottom
+    /// border, like the hint bar of a pager.
+    ///
+    /// Hints are pairs of a key and what it does, shown in order and separated by `·`. Hints
+    /// that do not fit the width of the box are left out, from the last, and replaced by `…`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_border::{create_text_border, TextBorderOptions};
+    ///
+    /// let hints = [("q", "quit"), ("r", "reload"), ("/", "search")];
+    ///
+    /// assert_eq!(
+    ///     create_text_border(
+    ///         "a page of text as long as the hints",
+    ///         Some(TextBorderOptions::hint_bar(&hints))
+    ///     ),
+    ///     "┌───────────────────────────────────┐\n\
+    ///      │a page of text as long as the hints│\n\
+    ///      │ q quit · r reload · / search      │\n\
+    ///      └───────────────────────────────────┘"
+    /// );
+    /// assert_eq!(
+    ///     create_text_border("a short page", Some(TextBorderOptions::hint_bar(&hints))),
+    ///     "┌────────────┐\n\
+    ///      │a short page│\n\
+    ///      │ q quit · … │\n\
+    ///      └────────────┘"
+    /// );
+    /// ```
+    pub fn hint_bar(hints: &[(&str, &str)]) -> Self {
+        Self {
+            border_style: BorderStyle::Weighted,
+            key_hints: hints
+                .iter()
+                .map(|&(key, action)| (key.to_string(), action.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{create_text_border, TextBorderOptions};
+
+    #[test]
+    fn blockquotes_wrap_beside_the_rail() {
+        let options = TextBorderOptions {
+            wrap_width: Some(5),
+            ..TextBorderOptions::blockquote()
+        };
+        assert_eq!(
+            create_text_border("hello world", Some(options)),
+            "│ hello\n│ world"
+        );
+    }
+
+    #[test]
+    fn an_empty_blockquote_is_a_single_rail() {
+        assert_eq!(
+            create_text_border("", Some(TextBorderOptions::blockquote())),
+            "│ "
+        );
+    }
+
+    #[test]
+    fn windows_can_leave_out_their_controls() {
+        let options = TextBorderOptions {
+            header_controls: None,
+            ..TextBorderOptions::window("Title")
+        };
+        assert_eq!(
+            create_text_border("x", Some(options)),
+            "┌───────┐\n│ Title │\n├───────┤\n│ x     │\n└───────┘"
+        );
+    }
+
+    #[test]
+    fn untitled_windows_show_only_their_controls() {
+        assert_eq!(
+            create_text_border("x", Some(TextBorderOptions::window(""))),
+            "┌─────────┐\n│ [-] [x] │\n├─────────┤\n│ x       │\n└─────────┘"
+        );
+    }
+
+    #[test]
+    fn without_hints_there_is_no_hint_row() {
+        assert_eq!(
+            create_text_border("x", Some(TextBorderOptions::hint_bar(&[]))),
+            "┌─┐\n│x│\n└─┘"
+        );
+    }
+
+    #[test]
+    fn narrow_hint_bars_show_an_ellipsis() {
+        let hints = TextBorderOptions::hint_bar(&[("q", "quit")]);
+        assert_eq!(
+            create_text_border("x", Some(hints.clone())),
+            "┌─┐\n│x│\n│…│\n└─┘"
+        );
+        assert_eq!(
+            create_text_border("abc", Some(hints.clone())),
+            "┌───┐\n│abc│\n│ … │\n└───┘"
+        );
+        assert_eq!(
+            create_text_border("abcdefgh", Some(hints)),
+            "┌────────┐\n│abcdefgh│\n│ q quit │\n└────────┘"
+        );
+    }
+}
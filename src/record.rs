@@ -0,0 +1,543 @@
+use std::error::Error;
+use std::fmt;
+
+use serde::ser::{
+    self, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+
+use crate::{ColumnAlignment, Table};
+
+/// `RecordError` describes why a record could not be turned into a row by
+/// `Table::from_serialize`, such as a record that is a number rather than a struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordError(String);
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for RecordError {}
+
+impl ser::Error for RecordError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        RecordError(msg.to_string())
+    }
+}
+
+impl Table {
+    /// Builds a table from records of a type that implements `Serialize`, such as a struct
+    /// deriving it, taking the names of its fields as the header.
+    ///
+    /// Each record is one row. Records may be structs or maps; fields missing from a record
+    /// are left empty, and fields that only some records have are added as columns in the
+    /// order they are first seen. Strings and numbers are written as they are, `None` as an
+    /// empty cell, enum variants without data by their name, and sequences and nested structs
+    /// on one line, separated by commas. Columns of numbers are aligned on their decimal
+    /// point, and other columns left. This constructor is available with the `serde` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `records` - The records, one per row.
+    ///
+    /// # Errors
+    ///
+    /// * `RecordError` - A record is not a struct or map, or failed to serialize.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use]
+    /// extern crate serde;
+    /// extern crate unicode_border;
+    ///
+    /// use unicode_border::{BorderStyle, Table, TextBorderOptions};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Crate {
+    ///     name: &'static str,
+    ///     version: f32,
+    ///     yanked: Option<bool>,
+    /// }
+    ///
+    /// fn main() {
+    ///     let crates = [
+    ///         Crate { name: "serde", version: 1.0, yanked: None },
+    ///         Crate { name: "png", version: 0.17, yanked: Some(false) },
+    ///     ];
+    ///     let options = TextBorderOptions {
+    ///         border_style: BorderStyle::Weighted,
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     assert_eq!(
+    ///         Table::from_serialize(&crates).unwrap().render(Some(options)),
+    ///         "┌──────────────────────┐\n\
+    ///          │name   version  yanked│\n\
+    ///          ├──────────────────────┤\n\
+    ///          │serde     1           │\n\
+    ///          │png       0.17  false │\n\
+    ///          └──────────────────────┘"
+    ///     );
+    /// }
+    /// ```
+    pub fn from_serialize<T: Serialize>(records: &[T]) -> Result<Table, RecordError> {
+        let mut header: Vec<String> = Vec::new();
+        let mut records_fields = Vec::with_capacity(records.len());
+        for record in records {
+            let fields = match record.serialize(CellSerializer)? {
+                Value::Fields(fields) => fields,
+                Value::Text(_) | Value::Number(_) => {
+                    return Err(RecordError(
+                        "records must serialize as structs or maps".to_string(),
+                    ))
+                }
+            };
+            for (name, _) in &fields {
+                if !header.contains(name) {
+                    header.push(name.clone());
+                }
+            }
+            records_fields.push(fields);
+        }
+
+        let mut numeric = vec![None; header.len()];
+        let rows = records_fields
+            .into_iter()
+            .map(|fields| {
+                header
+                    .iter()
+                    .enumerate()
+                    .map(|(column, name)| {
+                        let value = fields.iter().find(|(field, _)| field == name);
+                        match value {
+                            Some((_, Value::Number(text))) => {
+                                numeric[column].get_or_insert(true);
+                                text.clone()
+                            }
+                            Some((_, value)) => {
+                                let text = value.text();
+                                if !text.is_empty() {
+                                    numeric[column] = Some(false);
+                                }
+                                text
+                            }
+                            None => String::new(),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let alignments = numeric
+            .into_iter()
+            .map(|numeric| {
+                if numeric == Some(true) {
+                    ColumnAlignment::Decimal
+                } else {
+                    ColumnAlignment::Left
+                }
+            })
+            .collect();
+
+        Ok(Table {
+            header,
+            rows,
+            alignments,
+            ..Default::default()
+        })
+    }
+}
+
+/// A serialized value, as far as a table needs to know it.
+enum Value {
+    Text(String),
+    Number(String),
+    Fields(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Returns the value written on one line, as in a cell.
+    fn text(&self) -> String {
+        match self {
+            Value::Text(text) | Value::Number(text) => text.clone(),
+            Value::Fields(fields) => fields
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value.text()))
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+}
+
+/// Serializes a value into a `Value`.
+struct CellSerializer;
+
+/// Collects the elements of a sequence, or the fields of a struct or map.
+#[derive(Default)]
+struct Collector {
+    elements: Vec<String>,
+    fields: Vec<(String, Value)>,
+    key: Option<String>,
+}
+
+macro_rules! serialize_display {
+    ($($method:ident: $ty:ty => $variant:ident),* $(,)*) => {
+        $(
+            fn $method(self, v: $ty) -> Result<Value, RecordError> {
+                Ok(Value::$variant(v.to_string()))
+            }
+        )*
+    };
+}
+
+impl Serializer for CellSerializer {
+    type Ok = Value;
+    type Error = RecordError;
+    type SerializeSeq = Collector;
+    type SerializeTuple = Collector;
+    type SerializeTupleStruct = Collector;
+    type SerializeTupleVariant = Collector;
+    type SerializeMap = Collector;
+    type SerializeStruct = Collector;
+    type SerializeStructVariant = Collector;
+
+    serialize_display! {
+        serialize_bool: bool => Text,
+        serialize_i8: i8 => Number,
+        serialize_i16: i16 => Number,
+        serialize_i32: i32 => Number,
+        serialize_i64: i64 => Number,
+        serialize_i128: i128 => Number,
+        serialize_u8: u8 => Number,
+        serialize_u16: u16 => Number,
+        serialize_u32: u32 => Number,
+        serialize_u64: u64 => Number,
+        serialize_u128: u128 => Number,
+        serialize_f32: f32 => Number,
+        serialize_f64: f64 => Number,
+        serialize_char: char => Text,
+        serialize_str: &str => Text,
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, RecordError> {
+        Ok(Value::Text(String::from_utf8_lossy(v).into_owned()))
+    }
+
+    fn serialize_none(self) -> Result<Value, RecordError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Value, RecordError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, RecordError> {
+        Ok(Value::Text(String::new()))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, RecordError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value, RecordError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, RecordError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<Value, RecordError> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Collector, RecordError> {
+        Ok(Collector::default())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Collector, RecordError> {
+        Ok(Collector::default())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Collector, RecordError> {
+        Ok(Collector::default())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Collector, RecordError> {
+        Ok(Collector::default())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Collector, RecordError> {
+        Ok(Collector::default())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Collector, RecordError> {
+        Ok(Collector::default())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Collector, RecordError> {
+        Ok(Collector::default())
+    }
+}
+
+impl Collector {
+    fn element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), RecordError> {
+        self.elements.push(value.serialize(CellSerializer)?.text());
+        Ok(())
+    }
+
+    fn field<T: ?Sized + Serialize>(&mut self, key: String, value: &T) -> Result<(), RecordError> {
+        self.fields.push((key, value.serialize(CellSerializer)?));
+        Ok(())
+    }
+
+    fn sequence(self) -> Result<Value, RecordError> {
+        Ok(Value::Text(self.elements.join(", ")))
+    }
+
+    fn fields(self) -> Result<Value, RecordError> {
+        Ok(Value::Fields(self.fields))
+    }
+}
+
+impl SerializeSeq for Collector {
+    type Ok = Value;
+    type Error = RecordError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), RecordError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<Value, RecordError> {
+        self.sequence()
+    }
+}
+
+impl SerializeTuple for Collector {
+    type Ok = Value;
+    type Error = RecordError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), RecordError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<Value, RecordError> {
+        self.sequence()
+    }
+}
+
+impl SerializeTupleStruct for Collector {
+    type Ok = Value;
+    type Error = RecordError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), RecordError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<Value, RecordError> {
+        self.sequence()
+    }
+}
+
+impl SerializeTupleVariant for Collector {
+    type Ok = Value;
+    type Error = RecordError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), RecordError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<Value, RecordError> {
+        self.sequence()
+    }
+}
+
+impl SerializeMap for Collector {
+    type Ok = Value;
+    type Error = RecordError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), RecordError> {
+        self.key = Some(key.serialize(CellSerializer)?.text());
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), RecordError> {
+        let key = self.key.take().unwrap_or_default();
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<Value, RecordError> {
+        self.fields()
+    }
+}
+
+impl SerializeStruct for Collector {
+    type Ok = Value;
+    type Error = RecordError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), RecordError> {
+        self.field(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Value, RecordError> {
+        self.fields()
+    }
+}
+
+impl SerializeStructVariant for Collector {
+    type Ok = Value;
+    type Error = RecordError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), RecordError> {
+        self.field(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Value, RecordError> {
+        self.fields()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::ser::{Error as _, Serializer};
+    use serde::Serialize;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    enum Kind {
+        Unit,
+        Newtype(u8),
+    }
+
+    #[derive(Serialize)]
+    struct Inner {
+        a: u8,
+        b: &'static str,
+    }
+
+    #[derive(Serialize)]
+    struct Row {
+        kind: Kind,
+        tags: Vec<&'static str>,
+        inner: Inner,
+        size: Option<f64>,
+    }
+
+    #[test]
+    fn nested_values_are_flattened_into_cells() {
+        let rows = [
+            Row {
+                kind: Kind::Unit,
+                tags: vec!["x", "y"],
+                inner: Inner { a: 1, b: "z" },
+                size: Some(1.5),
+            },
+            Row {
+                kind: Kind::Newtype(7),
+                tags: Vec::new(),
+                inner: Inner { a: 2, b: "" },
+                size: None,
+            },
+        ];
+        let table = Table::from_serialize(&rows).unwrap();
+
+        assert_eq!(table.header, ["kind", "tags", "inner", "size"]);
+        assert_eq!(table.rows[0], ["Unit", "x, y", "a: 1, b: z", "1.5"]);
+        assert_eq!(table.rows[1], ["7", "", "a: 2, b: ", ""]);
+        assert_eq!(table.alignments[0], ColumnAlignment::Left);
+        assert_eq!(table.alignments[3], ColumnAlignment::Decimal);
+    }
+
+    #[test]
+    fn maps_with_different_keys_share_a_header() {
+        let mut first = BTreeMap::new();
+        first.insert("b", 1);
+        let mut second = BTreeMap::new();
+        second.insert("a", 2);
+        second.insert("b", 3);
+        let table = Table::from_serialize(&[first, second]).unwrap();
+
+        assert_eq!(table.header, ["b", "a"]);
+        assert_eq!(table.rows, [vec!["1", ""], vec!["3", "2"]]);
+        assert_eq!(table.alignments, [ColumnAlignment::Decimal; 2]);
+    }
+
+    #[test]
+    fn no_records_make_an_empty_table() {
+        let table = Table::from_serialize::<Row>(&[]).unwrap();
+        assert!(table.header.is_empty() && table.rows.is_empty());
+    }
+
+    #[test]
+    fn records_that_are_not_structs_are_rejected() {
+        let error = Table::from_serialize(&[1, 2]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "records must serialize as structs or maps"
+        );
+        assert!(Table::from_serialize(&["text"]).is_err());
+    }
+
+    #[test]
+    fn serialization_errors_are_returned() {
+        struct Failing;
+
+        impl Serialize for Failing {
+            fn serialize<S: Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+                Err(S::Error::custom("cannot serialize"))
+            }
+        }
+
+        #[derive(Serialize)]
+        struct Holder {
+            value: Failing,
+        }
+
+        let error = Table::from_serialize(&[Holder { value: Failing }]).unwrap_err();
+        assert_eq!(error, RecordError("cannot serialize".to_string()));
+    }
+}
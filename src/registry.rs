@@ -0,0 +1,117 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use crate::{create_text_border, TextBorderOptions};
+
+fn themes() -> MutexGuard<'static, HashMap<String, TextBorderOptions>> {
+    static THEMES: OnceLock<Mutex<HashMap<String, TextBorderOptions>>> = OnceLock::new();
+
+    THEMES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Registers `options` under `name` so they can later be referred to by name, e.g. from a
+/// plugin or a configuration file.
+///
+/// # Arguments
+///
+/// * `name` - The name of the theme.
+/// * `options` - The `TextBorderOptions` to store under `name`.
+///
+/// # Returns
+///
+/// * The options previously registered under `name`, if any.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border_themed, register_theme, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_char: '!',
+///     ..Default::default()
+/// };
+///
+/// register_theme("audit", options);
+///
+/// assert_eq!(
+///     create_text_border_themed("Hi", "audit").unwrap(),
+///     "!!!!\n!Hi!\n!!!!"
+/// );
+/// ```
+pub fn register_theme(name: &str, options: TextBorderOptions) -> Option<TextBorderOptions> {
+    themes().insert(name.to_string(), options)
+}
+
+/// Removes the theme registered under `name`.
+///
+/// # Returns
+///
+/// * The options that were registered under `name`, if any.
+pub fn unregister_theme(name: &str) -> Option<TextBorderOptions> {
+    themes().remove(name)
+}
+
+/// Returns a copy of the options registered under `name`, if any.
+pub fn theme(name: &str) -> Option<TextBorderOptions> {
+    themes().get(name).cloned()
+}
+
+/// Creates a bordered string like `create_text_border`, using the options registered under
+/// `theme_name` with `register_theme`.
+///
+/// # Arguments
+///
+/// * `message` - The message to be surrounded by a border, such as a `&str` or a `String`.
+/// * `theme_name` - The name the options were registered under.
+///
+/// # Returns
+///
+/// * `Some(String)` containing the bordered message, or `None` if no theme is registered
+///   under `theme_name`.
+pub fn create_text_border_themed<'a>(
+    message: impl Into<Cow<'a, str>>,
+    theme_name: &str,
+) -> Option<String> {
+    theme(theme_name).map(|options| create_text_border(message, Some(options)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hashes() -> TextBorderOptions {
+        TextBorderOptions {
+            border_char: '#',
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn registering_again_replaces_the_theme() {
+        let name = "registry::tests::replaced";
+        assert!(register_theme(name, TextBorderOptions::default()).is_none());
+        let previous = register_theme(name, hashes()).unwrap();
+
+        assert_eq!(previous.border_char, '*');
+        assert_eq!(theme(name).unwrap().border_char, '#');
+        assert_eq!(
+            create_text_border_themed("a", name).as_deref(),
+            Some("###\n#a#\n###")
+        );
+    }
+
+    #[test]
+    fn unregistered_themes_are_not_found() {
+        let name = "registry::tests::unregistered";
+        register_theme(name, hashes());
+
+        assert_eq!(unregister_theme(name).unwrap().border_char, '#');
+        assert!(unregister_theme(name).is_none());
+        assert!(theme(name).is_none());
+        assert_eq!(create_text_border_themed("a", name), None);
+    }
+}
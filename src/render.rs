@@ -0,0 +1,1713 @@
+#[cfg(feature = "bumpalo")]
+use bumpalo::collections::String as BumpString;
+#[cfg(feature = "bumpalo")]
+use bumpalo::Bump;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::ansi::{close_styles_per_line, sanitize_with_tab_width, trim_visible};
+use crate::color::paint_foreground;
+use crate::diff::color_diff_line;
+use crate::limits::check;
+use crate::line_builder::LineBuilder;
+use crate::markdown::render_inline;
+use crate::preprocess::dedent;
+use crate::style::apply_highlights;
+use crate::width::{char_width, str_width};
+use crate::wrap::{truncate_to_width, wrap_line};
+use crate::{
+    strip_ansi_codes, Alignment, BorderChars, BorderLayers, BorderStyle, Charset, ColorMode,
+    ColorSupport, ConfigError, Dimension, LabelDelimiters, LimitAction, Region, Sides, StatusBar,
+    Style, TextBorderOptions, Width, MAX_CONTENT_WIDTH, MAX_THICKNESS,
+};
+
+/// A label set into a horizontal border row, such as a title or a footer.
+struct EdgeLabel {
+    text: String,
+    width: usize,
+}
+
+impl EdgeLabel {
+    fn new(text: String, width: usize) -> Self {
+        Self { text, width }
+    }
+}
+
+/// Returns the width of `label` once padded with a space on each side and delimited.
+fn label_segment_width(label: &EdgeLabel, delimiters: Option<(char, char)>) -> usize {
+    let delimiters_width =
+        delimiters.map_or(0, |(open, close)| char_width(open) + char_width(close));
+
+    label.width + 2 + delimiters_width
+}
+
+/// Returns the interior width a horizontal border row needs to fit `labels`, keeping at least
+/// one `fill` character around and between them.
+fn labels_width(
+    labels: &(Option<EdgeLabel>, Option<EdgeLabel>),
+    fill: char,
+    delimiters: Option<(char, char)>,
+) -> usize {
+    let segment_width = |label: &EdgeLabel| label_segment_width(label, delimiters);
+    let fill_width = char_width(fill);
+
+    match labels {
+        (None, None) => 0,
+        (Some(label), None) | (None, Some(label)) => segment_width(label) + 2 * fill_width,
+        (Some(left), Some(right)) => segment_width(left) + segment_width(right) + 3 * fill_width,
+    }
+}
+
+/// Removes whitespace from the start of the first line and the end of the last line, dropping
+/// lines left blank, the same way `str::trim` would on the joined lines, but keeping the escape
+/// sequences among the whitespace.
+fn trim_lines(lines: &mut Vec<String>) {
+    let blank = |line: &String| strip_ansi_codes(line).trim().is_empty();
+    let Some(first) = lines.iter().position(|line| !blank(line)) else {
+        lines.clear();
+        return;
+    };
+    let last = lines.iter().rposition(|line| !blank(line)).unwrap_or(first);
+
+    // The escape sequences of the dropped lines move onto the first and last kept lines, so
+    // styles opened or closed there still apply.
+    let before: String = lines[..first]
+        .iter()
+        .map(|line| trim_visible(line, true, true))
+        .collect();
+    let after: String = lines[last + 1..]
+        .iter()
+        .map(|line| trim_visible(line, true, true))
+        .collect();
+    lines.truncate(last + 1);
+    lines.drain(..first);
+
+    if let Some(line) = lines.first_mut() {
+        *line = before + &trim_visible(line, true, false);
+    }
+    if let Some(line) = lines.last_mut() {
+        *line = trim_visible(line, false, true) + &after;
+    }
+}
+
+/// Flows `lines` into `columns` columns separated by `gap` spaces, filling each column top to
+/// bottom before the next. Each column is as wide as its widest line.
+fn flow_columns(lines: Vec<String>, columns: usize, gap: usize) -> Vec<String> {
+    let height = lines.len().div_ceil(columns);
+    let chunks: Vec<&[String]> = lines.chunks(height.max(1)).collect();
+    let widths: Vec<usize> = chunks
+        .iter()
+        .map(|chunk| chunk.iter().map(|line| str_width(line)).max().unwrap_or(0))
+        .collect();
+
+    (0..height)
+        .map(|row| {
+            let mut line = String::new();
+            for (i, chunk) in chunks.iter().enumerate() {
+                let cell = chunk.get(row).map_or("", String::as_str);
+                if i > 0 {
+                    line.push_str(&" ".repeat(gap));
+                }
+                line.push_str(cell);
+                line.push_str(&" ".repeat(widths[i] - str_width(cell)));
+            }
+            line.truncate(line.trim_end_matches(' ').len());
+            line
+        })
+        .collect()
+}
+
+/// `Rows` is the rows of a rendered box as runs of identical rows, so the layers of a thick
+/// border and the rows of a plain margin are rendered once and written as many times as they
+/// repeat, instead of being copied row by row.
+#[derive(Default)]
+pub(crate) struct Rows {
+    runs: Vec<(String, usize)>,
+}
+
+impl Rows {
+    /// Adds a row.
+    pub(crate) fn push(&mut self, line: String) {
+        self.push_run(line, 1);
+    }
+
+    /// Adds `count` rows of `line`.
+    pub(crate) fn push_run(&mut self, line: String, count: usize) {
+        if count > 0 {
+            self.runs.push((line, count));
+        }
+    }
+
+    /// Adds the rows of `other` after these.
+    pub(crate) fn append(&mut self, other: Rows) {
+        self.runs.extend(other.runs);
+    }
+
+    /// Returns the rows joined with line breaks.
+    pub(crate) fn join_lines(&self) -> String {
+        let mut joined = String::with_capacity(self.joined_len());
+        self.write_joined(|piece| joined.push_str(piece));
+        joined
+    }
+
+    /// Returns the rows joined with line breaks, allocated in `bump`.
+    #[cfg(feature = "bumpalo")]
+    pub(crate) fn join_lines_in<'bump>(&self, bump: &'bump Bump) -> &'bump str {
+        let mut joined = BumpString::with_capacity_in(self.joined_len(), bump);
+        self.write_joined(|piece| joined.push_str(piece));
+        joined.into_bump_str()
+    }
+
+    /// Returns the length in bytes of the rows joined with line breaks.
+    fn joined_len(&self) -> usize {
+        let len: usize = self
+            .runs
+            .iter()
+            .map(|(line, count)| (line.len() + 1) * count)
+            .sum();
+        len.saturating_sub(1)
+    }
+
+    /// Passes the rows, joined with line breaks, to `write` piece by piece.
+    fn write_joined(&self, mut write: impl FnMut(&str)) {
+        let mut first = true;
+        for (line, count) in &self.runs {
+            for _ in 0..*count {
+                if !first {
+                    write("\n");
+                }
+                write(line);
+                first = false;
+            }
+        }
+    }
+
+    /// Returns each row as a separate line.
+    pub(crate) fn into_lines(self) -> Vec<String> {
+        let mut lines = Vec::with_capacity(self.runs.iter().map(|(_, count)| count).sum());
+        for (line, count) in self.runs {
+            lines.extend(std::iter::repeat_n(line, count));
+        }
+        lines
+    }
+}
+
+/// The column widths of the left border, the interior (margins and message) and the right border.
+pub(crate) struct Layout {
+    left: usize,
+    inner: usize,
+    right: usize,
+}
+
+impl Layout {
+    /// Returns the width of the whole box.
+    fn width(&self) -> usize {
+        self.left
+            .saturating_add(self.inner)
+            .saturating_add(self.right)
+    }
+
+    /// Returns the width of the left and right borders.
+    fn frame_width(&self) -> usize {
+        self.left.saturating_add(self.right)
+    }
+}
+
+/// Returns an error if `action` is `LimitAction::Error`, for a `value` exceeding its limit.
+fn check_limit(
+    action: LimitAction,
+    dimension: Dimension,
+    value: usize,
+    max: usize,
+) -> Result<(), ConfigError> {
+    match action {
+        LimitAction::Clamp => Ok(()),
+        LimitAction::Error => check(dimension, value, max),
+    }
+}
+
+/// A row of a box: a row of the top or bottom border, counting layers from the outermost, or a
+/// row between them.
+#[derive(Clone, Copy)]
+enum Row {
+    Top(usize),
+    Middle,
+    Bottom(usize),
+}
+
+/// Returns the character of a layered border at `column` of a left or right border, counting
+/// from the outermost, in `row`. Each layer is a ring, drawn with the top or bottom layer
+/// character along its horizontal edges and the `side` character along its vertical edges.
+fn layer_char(row: Row, column: usize, side: char, top: &[char], bottom: &[char]) -> char {
+    match row {
+        Row::Top(layer) if layer <= column => top[layer],
+        Row::Bottom(layer) if layer <= column => bottom[layer],
+        _ => side,
+    }
+}
+
+/// The style of the active tab.
+const ACTIVE_TAB: Style = Style {
+    foreground: None,
+    background: None,
+    bold: false,
+    dim: false,
+    underline: false,
+    inverse: true,
+};
+
+/// The most bytes reserved up front for a row of a box. Longer rows grow as they are built.
+const MAX_LINE_CAPACITY: usize = 4 * MAX_CONTENT_WIDTH + 32;
+
+/// How many times taller than wide a terminal cell is assumed to be.
+const CELL_ASPECT_RATIO: f64 = 2.0;
+
+/// The indent of the lines of the message in plain mode.
+const PLAIN_INDENT: &str = "  ";
+
+/// `Renderer` holds options resolved for rendering, and the border characters they select.
+///
+/// Resolving decides once whether colors are written, downgrading them to what the terminal
+/// supports, resolves the width constraints to columns, and collapses the thickness of
+/// `BorderStyle::Weighted` and `BorderStyle::Lines` borders to one cell at most.
+#[derive(Clone)]
+pub(crate) struct Renderer {
+    opts: TextBorderOptions,
+    chars: BorderChars,
+    delimiters: LabelDelimiters,
+    layers: Option<BorderLayers>,
+    layer_width: usize,
+    border_sgr: Option<(String, String)>,
+    background_sgr: Option<String>,
+    min_width: Option<usize>,
+    max_width: Option<usize>,
+    target_width: Option<usize>,
+}
+
+impl Renderer {
+    pub(crate) fn new(mut opts: TextBorderOptions) -> Self {
+        let mut chars = opts
+            .border_style
+            .chars(opts.border_char, opts.border_thickness);
+        let mut delimiters = opts.border_style.label_delimiters(opts.border_thickness);
+        let charset = opts.charset.resolve();
+        opts.charset = charset;
+        if charset != Charset::Unicode {
+            chars = chars.with_charset(charset);
+            delimiters = delimiters.with_charset(charset);
+            opts.border_char = charset.fallback(opts.border_char);
+        }
+
+        if let Some(theme) = &opts.theme {
+            opts.border_color = opts.border_color.or(theme.border.foreground);
+            opts.background = opts.background.or(theme.content.background);
+            opts.badge_color = opts.badge_color.or(theme.accent.foreground);
+        }
+
+        if opts.color_mode.enabled() {
+            let color_support = opts.color_support.unwrap_or_else(ColorSupport::detect);
+            opts.border_color = opts
+                .border_color
+                .map(|color| color.downgrade(color_support));
+            opts.background = opts.background.map(|color| color.downgrade(color_support));
+            opts.badge_color = opts.badge_color.map(|color| color.downgrade(color_support));
+            for (_, style) in &mut opts.highlights {
+                *style = style.downgrade(color_support);
+            }
+            opts.theme = opts.theme.map(|theme| theme.downgrade(color_support));
+            opts.title_style = opts.title_style.map(|style| style.downgrade(color_support));
+            opts.border_attributes = opts
+                .border_attributes
+                .map(|style| style.downgrade(color_support));
+            opts.color_support = Some(color_support);
+            opts.color_mode = ColorMode::Always;
+        } else {
+            opts.border_color = None;
+            opts.background = None;
+            opts.badge_color = None;
+            opts.highlights.clear();
+            opts.theme = None;
+            opts.title_style = None;
+            opts.border_attributes = None;
+            opts.color_mode = ColorMode::Never;
+        }
+
+        let cap = |thickness: Sides<usize>, max: usize| {
+            let max = max.min(MAX_THICKNESS);
+            thickness.map(|side| side.min(max))
+        };
+        opts.border_thickness = cap(opts.border_thickness, opts.limits.max_border_thickness);
+        opts.margin_thickness = cap(opts.margin_thickness, opts.limits.max_margin_thickness);
+        opts.column_gap = opts.column_gap.min(MAX_CONTENT_WIDTH);
+        opts.code_block = opts
+            .code_block
+            .map(|tab_width| tab_width.min(MAX_CONTENT_WIDTH));
+
+        match opts.border_style {
+            BorderStyle::Weighted => {
+                opts.border_thickness = opts.border_thickness.map(|side| side.min(1));
+            }
+            BorderStyle::Lines(sides) => {
+                opts.border_thickness = sides.map(|side| usize::from(side.is_some()));
+            }
+            _ => {}
+        }
+
+        let layers = match (opts.border_style, &opts.border_layers) {
+            (BorderStyle::Char, Some(chars)) => {
+                let side = |chars: &[char], thickness: usize| -> Vec<char> {
+                    (0..thickness)
+                        .map(|layer| chars.get(layer).copied().unwrap_or(opts.border_char))
+                        .map(|c| charset.fallback(c))
+                        .collect()
+                };
+                let thickness = opts.border_thickness;
+                Some(Sides::new(
+                    side(&chars.left, thickness.left),
+                    side(&chars.top, thickness.top),
+                    side(&chars.right, thickness.right),
+                    side(&chars.bottom, thickness.bottom),
+                ))
+            }
+            _ => None,
+        };
+        let layer_width = layers.as_ref().map_or(1, |layers| {
+            layers
+                .left
+                .iter()
+                .chain(&layers.top)
+                .chain(&layers.right)
+                .chain(&layers.bottom)
+                .map(|&c| char_width(c))
+                .max()
+                .unwrap_or(1)
+        });
+
+        let max_width = opts.max_width.and_then(Width::resolve);
+        // Widths the box is widened to are capped, so a huge one cannot attempt an absurd
+        // allocation.
+        let resolve_capped = |width: Option<Width>| {
+            width
+                .and_then(Width::resolve)
+                .map(|width| width.min(MAX_CONTENT_WIDTH))
+        };
+        let target_width = resolve_capped(opts.target_width);
+        // The wrapped or shrunk box must still be able to satisfy the minimum.
+        let min_width = resolve_capped(opts.min_width).map(|min| {
+            min.min(max_width.unwrap_or(usize::MAX))
+                .min(target_width.unwrap_or(usize::MAX))
+        });
+
+        let border_attributes = opts.border_attributes.unwrap_or_default();
+        let border_style = Style {
+            foreground: opts.border_color.or(border_attributes.foreground),
+            ..border_attributes
+        };
+        let border_sgr =
+            Some((border_style.open(), border_style.close())).filter(|(open, _)| !open.is_empty());
+        let background_sgr = opts
+            .background
+            .map(|color| format!("\x1b[{}m", color.sgr(40)));
+
+        Self {
+            opts,
+            chars,
+            delimiters,
+            layers,
+            layer_width,
+            border_sgr,
+            background_sgr,
+            min_width,
+            max_width,
+            target_width,
+        }
+    }
+
+    /// Returns the options as resolved for rendering.
+    pub(crate) fn options(&self) -> &TextBorderOptions {
+        &self.opts
+    }
+
+    /// Renders `lines` into a complete box, one entry per output line, clamping it to `limits`.
+    pub(crate) fn render(self, lines: Vec<String>) -> Rows {
+        self.render_limited(lines, LimitAction::Clamp)
+            .unwrap_or_default()
+    }
+
+    /// Renders `lines` into a complete box, one entry per output line, taking `action` if the
+    /// box exceeds the width or height limit.
+    pub(crate) fn render_limited(
+        mut self,
+        lines: Vec<String>,
+        action: LimitAction,
+    ) -> Result<Rows, ConfigError> {
+        let mut lines = self.message_rows(lines);
+        if self.opts.plain {
+            return Ok(self.plain_rows(lines));
+        }
+
+        if let Some(ratio) = self.opts.aspect_ratio {
+            self.pad_to_aspect_ratio(&lines, ratio);
+        }
+
+        let limits = self.opts.limits;
+        let mut message_width = lines.iter().map(|line| str_width(line)).max().unwrap_or(0);
+        let mut layout = self.layout(message_width);
+        let frame_height = self.frame_height();
+
+        let height = lines.len().saturating_add(frame_height);
+        if height > limits.max_height {
+            check_limit(action, Dimension::Height, height, limits.max_height)?;
+            lines.truncate(limits.max_height.saturating_sub(frame_height));
+        }
+
+        let width = layout.width();
+        if width > limits.max_width {
+            check_limit(action, Dimension::Width, width, limits.max_width)?;
+            message_width = message_width.saturating_sub(width - limits.max_width);
+            for line in &mut lines {
+                *line = truncate_to_width(line, message_width);
+            }
+            layout = self.layout(message_width);
+        }
+
+        let top_margin = self.first_message_row();
+        let mut bordered_message = self.top_rows(&layout);
+        for (i, line) in lines.iter().enumerate() {
+            bordered_message.push(self.create_message_line(&layout, top_margin + i, line));
+        }
+        bordered_message.append(self.bottom_rows(&layout, top_margin + lines.len()));
+
+        Ok(bordered_message)
+    }
+
+    /// Renders `lines` in plain mode: the title as a `=== title ===` header, the lines indented
+    /// and the footer as a `--- footer ---` line, without box glyphs.
+    fn plain_rows(&self, lines: Vec<String>) -> Rows {
+        let opts = &self.opts;
+        let join = |parts: &[&Option<String>]| {
+            let parts: Vec<&str> = parts.iter().filter_map(|part| part.as_deref()).collect();
+            Some(parts.join(" ")).filter(|label| !label.is_empty())
+        };
+
+        let mut rows = Rows::default();
+        if let Some(title) = join(&[&opts.title_icon, &opts.title, &opts.title_badge]) {
+            rows.push(format!("=== {} ===", title));
+        }
+        for line in lines {
+            rows.push(if line.is_empty() {
+                line
+            } else {
+                format!("{}{}", PLAIN_INDENT, line)
+            });
+        }
+        if let Some(footer) = join(&[&opts.footer, &opts.footer_status]) {
+            rows.push(format!("--- {} ---", footer));
+        }
+        rows
+    }
+
+    /// Returns the rows of the message prepared, trimmed and wrapped as they are set into the
+    /// box, before `aspect_ratio` and `limits` are applied.
+    pub(crate) fn message_rows(&mut self, lines: Vec<String>) -> Vec<String> {
+        let mut previous_blank = false;
+        let mut lines: Vec<String> = lines
+            .into_iter()
+            .filter(|line| !self.skip_line(line, &mut previous_blank))
+            .map(|line| self.prepare_line(line))
+            .collect();
+
+        if self.opts.dedent {
+            dedent(&mut lines);
+        }
+        let opts = &self.opts;
+        if !(opts.prevent_trim || opts.show_invisibles || opts.code_block.is_some() || opts.diff) {
+            trim_lines(&mut lines);
+        }
+
+        if let Some(width) = self.opts.wrap_width {
+            lines = lines
+                .iter()
+                .flat_map(|line| wrap_line(line, width))
+                .collect();
+        }
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+
+        let columns = self.opts.columns.max(1);
+        if columns > 1 {
+            let gap = self.opts.column_gap;
+            if let Some(max) = self.max_width {
+                let gaps = gap.saturating_mul(columns - 1);
+                let width = self.content_width_for(max).saturating_sub(gaps) / columns;
+                lines = lines
+                    .iter()
+                    .flat_map(|line| wrap_line(line, width.max(1)))
+                    .collect();
+            }
+            lines = flow_columns(lines, columns, gap);
+        } else if let Some(max) = self.max_width {
+            lines = self.wrap_to(lines, max);
+        }
+
+        if let Some(target) = self.target_width {
+            lines = self.shrink_to(lines, target);
+        }
+
+        if self.opts.show_invisibles {
+            lines = lines
+                .iter()
+                .map(|line| self.show_invisibles(line))
+                .collect();
+        }
+        if lines.iter().any(|line| line.contains('\x1b')) {
+            close_styles_per_line(&mut lines);
+        }
+        lines
+    }
+
+    /// Wraps `lines` so the box is at most `max` columns wide, if labels allow it.
+    fn wrap_to(&self, mut lines: Vec<String>, max: usize) -> Vec<String> {
+        let margins = self.opts.margin_thickness.horizontal();
+        let mut available = max.saturating_sub(self.layout(0).frame_width() + margins);
+
+        // Widening the interior to an even width for wide edge characters can leave the box a
+        // column too wide.
+        for _ in 0..2 {
+            let message_width = lines.iter().map(|line| str_width(line)).max().unwrap_or(0);
+            if self.layout(message_width).width() <= max || message_width == 0 {
+                break;
+            }
+            lines = lines
+                .iter()
+                .flat_map(|line| wrap_line(line, available))
+                .collect();
+            available = available.saturating_sub(1);
+        }
+
+        lines
+    }
+
+    /// Shrinks the box to be at most `target` columns wide, reducing the left and right margins
+    /// first, then wrapping `lines`, then truncating the lines that still do not fit.
+    ///
+    /// Labels wider than the target keep the box wider, and the message is only shrunk to the
+    /// width they leave.
+    fn shrink_to(&mut self, mut lines: Vec<String>, target: usize) -> Vec<String> {
+        let labels = self.layout(0).frame_width() + self.fit_edges(self.labels_inner_width());
+        let target = target.max(labels);
+        let message_width = |lines: &[String]| lines.iter().map(|line| str_width(line)).max();
+        let excess = |renderer: &Self, lines: &[String]| {
+            let width = renderer.layout(message_width(lines).unwrap_or(0)).width();
+            width.saturating_sub(target)
+        };
+
+        let mut over = excess(self, &lines);
+        if over == 0 {
+            return lines;
+        }
+
+        let margins = &mut self.opts.margin_thickness;
+        while over > 0 && (margins.left > 0 || margins.right > 0) {
+            if margins.left > margins.right {
+                margins.left -= 1;
+            } else {
+                margins.right -= 1;
+            }
+            over -= 1;
+        }
+
+        over = excess(self, &lines);
+        if over > 0 {
+            let available = message_width(&lines).unwrap_or(0).saturating_sub(over);
+            lines = lines
+                .iter()
+                .flat_map(|line| wrap_line(line, available))
+                .collect();
+        }
+
+        // Wide characters and widening the interior to an even width can leave the box a
+        // column or two too wide after wrapping.
+        for _ in 0..2 {
+            over = excess(self, &lines);
+            if over == 0 {
+                break;
+            }
+            let available = message_width(&lines).unwrap_or(0).saturating_sub(over);
+            for line in &mut lines {
+                *line = truncate_to_width(line, available);
+            }
+        }
+
+        lines
+    }
+
+    /// Widens the left and right margins, or deepens the top and bottom margins, so the box
+    /// looks about `ratio` times as wide as it is tall.
+    ///
+    /// The box is only widened as far as the `max_width` and `target_width` constraints allow.
+    fn pad_to_aspect_ratio(&mut self, lines: &[String], ratio: f64) {
+        if !(ratio.is_finite() && ratio > 0.0) {
+            return;
+        }
+
+        let message_width = lines.iter().map(|line| str_width(line)).max().unwrap_or(0);
+        let layout = self.layout(message_width);
+        let rows = self.frame_height() + lines.len();
+        let (width, height) = (layout.width() as f64, rows as f64 * CELL_ASPECT_RATIO);
+
+        let grow = |thickness: &mut usize, extra: usize| {
+            *thickness = thickness.saturating_add(extra).min(MAX_THICKNESS);
+        };
+        let margins = &mut self.opts.margin_thickness;
+
+        if width / height < ratio {
+            let max_width = self
+                .max_width
+                .unwrap_or(usize::MAX)
+                .min(self.target_width.unwrap_or(usize::MAX));
+            let wanted = ((ratio * height).round() as usize).min(max_width);
+            let extra = wanted.saturating_sub(layout.width());
+            grow(&mut margins.left, extra / 2);
+            grow(&mut margins.right, extra - extra / 2);
+        } else {
+            let wanted = (width / (ratio * CELL_ASPECT_RATIO)).round() as usize;
+            let extra = wanted.saturating_sub(rows);
+            grow(&mut margins.top, extra / 2);
+            grow(&mut margins.bottom, extra - extra / 2);
+        }
+    }
+
+    /// Replaces spaces in `line` with `·` and tabs with `→`, highlighting trailing whitespace
+    /// in inverse video if colors are written.
+    fn show_invisibles(&self, line: &str) -> String {
+        let visible = |text: &str| -> String {
+            text.chars()
+                .map(|c| match c {
+                    ' ' => '·',
+                    '\t' => '→',
+                    c => c,
+                })
+                .collect()
+        };
+
+        let (content, trailing) = line.split_at(line.trim_end_matches([' ', '\t']).len());
+        let mut shown = visible(content);
+        match self.opts.color_mode {
+            ColorMode::Always if !trailing.is_empty() => {
+                shown.push_str(&format!("\x1b[7m{}\x1b[27m", visible(trailing)));
+            }
+            _ => shown.push_str(&visible(trailing)),
+        }
+        shown
+    }
+
+    /// Splits `message` into lines, after highlighting it.
+    pub(crate) fn message_lines(&self, message: &str) -> Vec<String> {
+        if self.opts.highlights.is_empty() {
+            return message.lines().map(str::to_string).collect();
+        }
+
+        apply_highlights(message, &self.opts.highlights)
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Returns whether `blank_lines` drops `line`, given whether the line before it was blank.
+    pub(crate) fn skip_line(&self, line: &str, previous_blank: &mut bool) -> bool {
+        self.opts.blank_lines.skip(line, previous_blank)
+    }
+
+    /// Strips escape sequences from `line` if colors are disabled, and normalizes it if enabled.
+    pub(crate) fn prepare_line(&self, line: String) -> String {
+        let line = match self.opts.color_mode {
+            ColorMode::Never => strip_ansi_codes(&line),
+            _ => line,
+        };
+        let line = match self.opts.code_block {
+            Some(tab_width) => sanitize_with_tab_width(&line, tab_width),
+            None => line,
+        };
+        let line = if self.opts.markdown {
+            render_inline(&line, self.opts.color_mode == ColorMode::Always)
+        } else {
+            line
+        };
+        let line = if self.opts.diff && self.opts.color_mode == ColorMode::Always {
+            color_diff_line(line)
+        } else {
+            line
+        };
+        let line = match &self.opts.theme {
+            Some(theme) if !line.is_empty() => Style {
+                background: None,
+                ..theme.content
+            }
+            .paint(&line),
+            _ => line,
+        };
+
+        if self.opts.normalize {
+            line.nfc().collect()
+        } else {
+            line
+        }
+    }
+
+    /// Returns the layout of a box whose widest message line is `message_width` columns wide.
+    pub(crate) fn layout(&self, message_width: usize) -> Layout {
+        let opts = &self.opts;
+        let chars = &self.chars;
+        let side_width =
+            |glyphs: [char; 3]| glyphs.iter().map(|&c| char_width(c)).max().unwrap_or(1);
+
+        let (left_width, right_width) = if self.layers.is_some() {
+            (self.layer_width, self.layer_width)
+        } else {
+            (
+                side_width([chars.top_left, chars.left, chars.bottom_left]),
+                side_width([chars.top_right, chars.right, chars.bottom_right]),
+            )
+        };
+        let left = opts.border_thickness.left.saturating_mul(left_width);
+        let right = opts.border_thickness.right.saturating_mul(right_width);
+
+        let mut inner = message_width.saturating_add(opts.margin_thickness.horizontal());
+        if let Some(min) = self.min_width {
+            inner = inner.max(min.saturating_sub(left.saturating_add(right)));
+        }
+
+        let inner = self.fit_edges(inner.max(self.labels_inner_width()));
+
+        Layout { left, inner, right }
+    }
+
+    /// Returns `inner` widened so the horizontal edges fill it without a gap.
+    fn fit_edges(&self, inner: usize) -> usize {
+        // A wide horizontal edge character can only fill an even number of columns, so the
+        // interior is widened by one column rather than leaving a gap in the edge.
+        let (opts, chars) = (&self.opts, &self.chars);
+        let wide_top = opts.border_thickness.top > 0 && char_width(chars.top) > 1;
+        let wide_bottom = opts.border_thickness.bottom > 0 && char_width(chars.bottom) > 1;
+        if wide_top || wide_bottom {
+            inner.saturating_add(inner % 2)
+        } else {
+            inner
+        }
+    }
+
+    /// Returns the width of the interior needed by the labels, header and tabs alone.
+    fn labels_inner_width(&self) -> usize {
+        let chars = &self.chars;
+        labels_width(&self.top_labels(), chars.top, self.delimiters.top)
+            .max(labels_width(
+                &self.bottom_labels(),
+                chars.bottom,
+                self.delimiters.bottom,
+            ))
+            .max(self.header_width())
+            .max(self.tabs_width())
+    }
+
+    /// Returns the rows above the message: the top border and the top margin.
+    pub(crate) fn top_lines(&self, layout: &Layout) -> Vec<String> {
+        self.top_rows(layout).into_lines()
+    }
+
+    /// Returns the rows above the message, as `top_lines` does, with identical rows kept as
+    /// one run.
+    pub(crate) fn top_rows(&self, layout: &Layout) -> Rows {
+        let mut rows = Rows::default();
+        let thickness = self.opts.border_thickness.top;
+        if thickness > 0 {
+            let outermost = if self.has_tabs() {
+                self.create_tabs_line(layout)
+            } else {
+                match self.top_labels() {
+                    (None, None) => self.create_border_line(layout, Row::Top(0)),
+                    labels => {
+                        self.create_labeled_line(layout, Row::Top(0), self.delimiters.top, labels)
+                    }
+                }
+            };
+            rows.push(outermost);
+            self.push_border_rows(&mut rows, layout, (1..thickness).map(Row::Top));
+        }
+
+        for line in self.header_lines(layout) {
+            rows.push(line);
+        }
+        self.push_margin_rows(&mut rows, layout, 0, self.opts.margin_thickness.top);
+        rows
+    }
+
+    /// Returns the number of rows above and below the message.
+    pub(crate) fn frame_height(&self) -> usize {
+        let opts = &self.opts;
+        let header = if opts.header.is_some() || opts.header_controls.is_some() {
+            2
+        } else {
+            0
+        };
+        opts.border_thickness.top
+            + header
+            + opts.margin_thickness.top
+            + opts.margin_thickness.bottom
+            + usize::from(opts.status_bar.is_some())
+            + usize::from(!opts.key_hints.is_empty())
+            + opts.border_thickness.bottom
+    }
+
+    /// Adds the border rows `rows_to_add`, in order. Without per-layer characters every row of
+    /// a side is the same, so it is rendered once.
+    fn push_border_rows(
+        &self,
+        rows: &mut Rows,
+        layout: &Layout,
+        mut rows_to_add: impl ExactSizeIterator<Item = Row>,
+    ) {
+        if self.layers.is_none() {
+            let count = rows_to_add.len();
+            if let Some(row) = rows_to_add.next() {
+                rows.push_run(self.create_border_line(layout, row), count);
+            }
+            return;
+        }
+        for row in rows_to_add {
+            rows.push(self.create_border_line(layout, row));
+        }
+    }
+
+    /// Adds `count` margin rows, starting at row `first_row` of the interior. Without a margin
+    /// pattern every margin row is the same, so it is rendered once.
+    fn push_margin_rows(&self, rows: &mut Rows, layout: &Layout, first_row: usize, count: usize) {
+        if self.opts.margin_pattern.is_none() {
+            if count > 0 {
+                rows.push_run(self.create_margin_line(layout, first_row), count);
+            }
+            return;
+        }
+        for y in 0..count {
+            rows.push(self.create_margin_line(layout, first_row.saturating_add(y)));
+        }
+    }
+
+    /// Returns whether tabs are set into the outermost top border row.
+    fn has_tabs(&self) -> bool {
+        !self.opts.tabs.is_empty() && self.opts.border_thickness.top > 0
+    }
+
+    /// Returns the width of the interior needed by the tabs, each bracketed and padded with a
+    /// space on each side, with two fill characters between tabs and at least one around them.
+    fn tabs_width(&self) -> usize {
+        if !self.has_tabs() {
+            return 0;
+        }
+
+        let fill_width = char_width(self.fill(Row::Top(0)));
+        let tabs = &self.opts.tabs;
+        let labels: usize = tabs.iter().map(|tab| str_width(tab) + 4).sum();
+        labels + fill_width * (2 * tabs.len())
+    }
+
+    /// Creates the outermost top border row with the tabs set into it, the active tab shown in
+    /// inverse video.
+    fn create_tabs_line(&self, layout: &Layout) -> String {
+        let opts = &self.opts;
+        let fill = self.fill(Row::Top(0));
+        let fill_width = char_width(fill);
+
+        let mut line = self.line_builder(layout);
+        self.push_border(&mut line, |line| {
+            self.push_left_edge(line, layout, Row::Top(0))
+        });
+        let mut remaining = layout.inner;
+        for (i, tab) in opts.tabs.iter().enumerate() {
+            let lead = if i == 0 { fill_width } else { 2 * fill_width };
+            self.push_border(&mut line, |line| {
+                line.push_repeated(fill, lead);
+                line.push('[');
+            });
+            let mark = line.mark();
+            line.push(' ');
+            line.push_str(tab);
+            line.push(' ');
+            if opts.active_tab == Some(i) && opts.color_mode.enabled() {
+                line.map_since(mark, |text| ACTIVE_TAB.paint(&text));
+            }
+            self.push_border(&mut line, |line| line.push(']'));
+            remaining = remaining.saturating_sub(lead + str_width(tab) + 4);
+        }
+        self.push_border(&mut line, |line| line.push_repeated(fill, remaining));
+        self.push_border(&mut line, |line| {
+            self.push_right_edge(line, layout, Row::Top(0))
+        });
+        line.finish()
+    }
+
+    /// Creates the status bar row, its segments separated by the bottom border character.
+    fn create_status_line(&self, layout: &Layout, status_bar: &StatusBar) -> String {
+        let fill = match self.opts.border_style {
+            BorderStyle::Char => self.opts.border_char,
+            _ => self.chars.bottom,
+        };
+
+        self.create_interior_line(layout, |line| {
+            let mut end = 0;
+            for (gap, segment) in status_bar.fit(layout.inner) {
+                self.push_border(line, |line| line.push_repeated(fill, gap));
+                end += gap + str_width(&segment);
+                line.push_str(&segment);
+            }
+            self.push_border(line, |line| {
+                line.push_repeated(fill, layout.inner.saturating_sub(end))
+            });
+        })
+    }
+
+    /// Creates the key hints row, with as many hints as fit the interior, separated by `·`.
+    fn create_key_hints_line(&self, layout: &Layout) -> String {
+        let width = layout.inner.saturating_sub(2);
+        let hints: Vec<String> = self
+            .opts
+            .key_hints
+            .iter()
+            .map(|(key, action)| format!("{} {}", key, action))
+            .collect();
+
+        let mut shown = hints.len();
+        let mut text = hints.join(" · ");
+        while shown > 0 && str_width(&text) > width {
+            shown -= 1;
+            text = hints[..shown]
+                .iter()
+                .map(String::as_str)
+                .chain(["…"])
+                .collect::<Vec<_>>()
+                .join(" · ");
+        }
+        // Too narrow for the padding, the row shows the ellipsis alone.
+        let interior = if str_width(&text) <= width {
+            format!(" {} ", text)
+        } else {
+            truncate_to_width("…", layout.inner)
+        };
+        self.create_interior_line(layout, |line| {
+            line.push_str(&interior);
+            line.push_spaces(layout.inner - str_width(&interior));
+        })
+    }
+
+    /// Returns the width of the interior needed by the header row, or 0 without one.
+    fn header_width(&self) -> usize {
+        let opts = &self.opts;
+        // An empty header needs no space between it and the controls.
+        let header = opts.header.as_ref().filter(|header| !header.is_empty());
+        let widths = match (header, &opts.header_controls) {
+            (None, None) => return 0,
+            (Some(header), None) => str_width(header),
+            (None, Some(controls)) => str_width(controls),
+            (Some(header), Some(controls)) => str_width(header) + 1 + str_width(controls),
+        };
+        widths.saturating_add(opts.margin_thickness.horizontal())
+    }
+
+    /// Returns the header row, with the header aligned left and its controls aligned right,
+    /// followed by the rule separating it from the rest of the box. Without a header or
+    /// controls, no rows are returned.
+    fn header_lines(&self, layout: &Layout) -> Vec<String> {
+        let opts = &self.opts;
+        if opts.header.is_none() && opts.header_controls.is_none() {
+            return Vec::new();
+        }
+
+        let header = opts.header.as_deref().unwrap_or("");
+        let controls = opts.header_controls.as_deref().unwrap_or("");
+        let (left_margin, right_margin) = (opts.margin_thickness.left, opts.margin_thickness.right);
+        let gap = layout.inner.saturating_sub(
+            left_margin
+                .saturating_add(str_width(header))
+                .saturating_add(str_width(controls))
+                .saturating_add(right_margin),
+        );
+        let header_line = self.create_interior_line(layout, |line| {
+            line.push_spaces(left_margin);
+            line.push_str(header);
+            line.push_spaces(gap);
+            line.push_str(controls);
+            line.push_spaces(right_margin);
+        });
+
+        vec![header_line, self.create_rule_line(layout)]
+    }
+
+    /// Returns a horizontal rule across the box, separating parts of its interior.
+    pub(crate) fn create_rule_line(&self, layout: &Layout) -> String {
+        // Box-drawing borders join the rule with tees; other borders extend their sides.
+        let chars = &self.chars;
+        let mut line = self.line_builder(layout);
+        self.push_border(&mut line, |line| {
+            line.push_repeated(chars.tee_left, layout.left);
+            line.push_repeated(chars.top, layout.inner);
+            line.push_repeated(chars.tee_right, layout.right);
+        });
+        line.finish()
+    }
+
+    /// Renders `regions` into a complete box, one entry per output line, each region aligned and
+    /// styled on its own and separated from the next by a rule. Empty regions are left out.
+    pub(crate) fn render_regions(&self, regions: &[&Region]) -> Rows {
+        let opts = &self.opts;
+        let regions: Vec<(Vec<String>, &Region)> = regions
+            .iter()
+            .filter(|region| !region.lines.is_empty())
+            .map(|region| {
+                let mut previous_blank = false;
+                let mut lines: Vec<String> = region
+                    .lines
+                    .iter()
+                    .filter(|line| !self.skip_line(line, &mut previous_blank))
+                    .map(|line| self.prepare_line(line.clone()))
+                    .collect();
+                if !(opts.prevent_trim || opts.code_block.is_some() || opts.diff) {
+                    trim_lines(&mut lines);
+                    if region.alignment != Alignment::Left {
+                        for line in &mut lines {
+                            *line = line.trim().to_string();
+                        }
+                    }
+                }
+                if let Some(width) = opts.wrap_width {
+                    lines = lines
+                        .iter()
+                        .flat_map(|line| wrap_line(line, width))
+                        .collect();
+                }
+                (lines, *region)
+            })
+            .collect();
+
+        if opts.plain {
+            let mut lines = Vec::new();
+            for (i, (region_lines, _)) in regions.into_iter().enumerate() {
+                if i > 0 {
+                    lines.push(String::new());
+                }
+                lines.extend(region_lines);
+            }
+            return self.plain_rows(lines);
+        }
+
+        let message_width = regions
+            .iter()
+            .flat_map(|(lines, _)| lines.iter().map(|line| str_width(line)))
+            .max()
+            .unwrap_or(0);
+        let layout = self.layout(message_width);
+        let content_width = layout
+            .inner
+            .saturating_sub(opts.margin_thickness.horizontal());
+
+        let mut row = self.first_message_row();
+        let mut bordered = self.top_rows(&layout);
+        for (i, (lines, region)) in regions.iter().enumerate() {
+            if i > 0 {
+                bordered.push(self.create_rule_line(&layout));
+                row += 1;
+            }
+            for line in lines {
+                let gap = content_width.saturating_sub(str_width(line));
+                let indent = match region.alignment {
+                    Alignment::Left => 0,
+                    Alignment::Center => gap / 2,
+                    Alignment::Right => gap,
+                };
+                let line = match (region.style, opts.color_mode) {
+                    (Some(style), ColorMode::Always) if !line.is_empty() => {
+                        let support = opts.color_support.unwrap_or(ColorSupport::TrueColor);
+                        style.downgrade(support).paint(line)
+                    }
+                    _ => line.clone(),
+                };
+                let line = format!("{}{}", " ".repeat(indent), line);
+                bordered.push(self.create_message_line(&layout, row, &line));
+                row += 1;
+            }
+        }
+        bordered.append(self.bottom_rows(&layout, row));
+        bordered
+    }
+
+    /// Returns the width left for the message in a box `total_width` columns wide, once the
+    /// left and right borders and margins are taken off.
+    pub(crate) fn content_width_for(&self, total_width: usize) -> usize {
+        let margins = self.opts.margin_thickness.horizontal();
+        total_width.saturating_sub(self.layout(0).frame_width().saturating_add(margins))
+    }
+
+    /// Replaces the label set into the bottom border, such as to number pages.
+    pub(crate) fn set_footer(&mut self, footer: String) {
+        self.opts.footer = Some(footer);
+    }
+
+    /// Returns the row of the interior the message starts at, below the top margin.
+    pub(crate) fn first_message_row(&self) -> usize {
+        self.opts.margin_thickness.top
+    }
+
+    /// Returns the rows below the message: the bottom margin and the bottom border. `first_row`
+    /// is the row of the interior the bottom margin starts at.
+    pub(crate) fn bottom_lines(&self, layout: &Layout, first_row: usize) -> Vec<String> {
+        self.bottom_rows(layout, first_row).into_lines()
+    }
+
+    /// Returns the rows below the message, as `bottom_lines` does, with identical rows kept as
+    /// one run.
+    pub(crate) fn bottom_rows(&self, layout: &Layout, first_row: usize) -> Rows {
+        let mut rows = Rows::default();
+        self.push_margin_rows(
+            &mut rows,
+            layout,
+            first_row,
+            self.opts.margin_thickness.bottom,
+        );
+        if let Some(status_bar) = &self.opts.status_bar {
+            rows.push(self.create_status_line(layout, status_bar));
+        }
+        if !self.opts.key_hints.is_empty() {
+            rows.push(self.create_key_hints_line(layout));
+        }
+
+        let thickness = self.opts.border_thickness.bottom;
+        if thickness > 0 {
+            self.push_border_rows(&mut rows, layout, (1..thickness).rev().map(Row::Bottom));
+            let outermost = match self.bottom_labels() {
+                (None, None) => self.create_border_line(layout, Row::Bottom(0)),
+                labels => {
+                    self.create_labeled_line(layout, Row::Bottom(0), self.delimiters.bottom, labels)
+                }
+            };
+            rows.push(outermost);
+        }
+        rows
+    }
+
+    fn create_border_line(&self, layout: &Layout, row: Row) -> String {
+        let mut line = self.line_builder(layout);
+        self.push_border(&mut line, |line| {
+            self.push_left_edge(line, layout, row);
+            line.push_repeated(self.fill(row), layout.inner);
+            self.push_right_edge(line, layout, row);
+        });
+        line.finish()
+    }
+
+    /// Returns the character filling the interior columns of a border row.
+    fn fill(&self, row: Row) -> char {
+        match (&self.layers, row) {
+            (Some(Sides { top, .. }), Row::Top(layer)) => top[layer],
+            (Some(Sides { bottom, .. }), Row::Bottom(layer)) => bottom[layer],
+            (None, Row::Top(_)) => self.chars.top,
+            (None, Row::Bottom(_)) => self.chars.bottom,
+            (_, Row::Middle) => ' ',
+        }
+    }
+
+    /// Pushes the left border of `row`.
+    fn push_left_edge(&self, line: &mut LineBuilder, layout: &Layout, row: Row) {
+        let Some(Sides {
+            left, top, bottom, ..
+        }) = &self.layers
+        else {
+            let c = match row {
+                Row::Top(_) => self.chars.top_left,
+                Row::Middle => self.chars.left,
+                Row::Bottom(_) => self.chars.bottom_left,
+            };
+            line.push_repeated(c, layout.left);
+            return;
+        };
+
+        for (column, &side) in left.iter().enumerate() {
+            let c = layer_char(row, column, side, top, bottom);
+            line.push_repeated(c, self.layer_width);
+        }
+    }
+
+    /// Pushes the right border of `row`.
+    fn push_right_edge(&self, line: &mut LineBuilder, layout: &Layout, row: Row) {
+        let Some(Sides {
+            top, right, bottom, ..
+        }) = &self.layers
+        else {
+            let c = match row {
+                Row::Top(_) => self.chars.top_right,
+                Row::Middle => self.chars.right,
+                Row::Bottom(_) => self.chars.bottom_right,
+            };
+            line.push_repeated(c, layout.right);
+            return;
+        };
+
+        for (column, &side) in right.iter().enumerate().rev() {
+            let c = layer_char(row, column, side, top, bottom);
+            line.push_repeated(c, self.layer_width);
+        }
+    }
+
+    /// Returns the labels set into the outermost top border row: the title and its badge.
+    fn top_labels(&self) -> (Option<EdgeLabel>, Option<EdgeLabel>) {
+        let opts = &self.opts;
+        if opts.border_thickness.top == 0 {
+            return (None, None);
+        }
+
+        let title = opts.title.as_ref().map(|title| {
+            let style = opts
+                .title_style
+                .or_else(|| opts.theme.map(|theme| theme.title));
+            let text = match style {
+                Some(style) => style.paint(title),
+                None => title.clone(),
+            };
+            EdgeLabel::new(text, str_width(title))
+        });
+        let title = match (&opts.title_icon, title) {
+            (Some(icon), Some(title)) => Some(EdgeLabel::new(
+                format!("{} {}", icon, title.text),
+                str_width(icon) + 1 + title.width,
+            )),
+            (Some(icon), None) => Some(EdgeLabel::new(icon.clone(), str_width(icon))),
+            (None, title) => title,
+        };
+
+        let title = title.map(|mut label| {
+            if let Some(badge) = &opts.title_badge {
+                label.text.push(' ');
+                label.text.push_str(&match opts.badge_color {
+                    Some(color) => paint_foreground(badge, color),
+                    None => badge.clone(),
+                });
+                label.width += 1 + str_width(badge);
+            }
+            label
+        });
+
+        (title, None)
+    }
+
+    /// Returns the labels set into the outermost bottom border row: the footer and its
+    /// right-aligned status.
+    fn bottom_labels(&self) -> (Option<EdgeLabel>, Option<EdgeLabel>) {
+        let opts = &self.opts;
+        if opts.border_thickness.bottom == 0 {
+            return (None, None);
+        }
+
+        let label = |text: &String| EdgeLabel::new(text.clone(), str_width(text));
+
+        (
+            opts.footer.as_ref().map(label),
+            opts.footer_status.as_ref().map(label),
+        )
+    }
+
+    /// Creates a horizontal border row with a left-aligned and a right-aligned label set into it.
+    fn create_labeled_line(
+        &self,
+        layout: &Layout,
+        row: Row,
+        delimiters: Option<(char, char)>,
+        labels: (Option<EdgeLabel>, Option<EdgeLabel>),
+    ) -> String {
+        let fill = self.fill(row);
+        let (open, close) = match delimiters {
+            Some((open, close)) => (Some(open), Some(close)),
+            None => (None, None),
+        };
+        let segment_width = |label: &EdgeLabel| label_segment_width(label, delimiters);
+        let push_label = |line: &mut LineBuilder, label: &EdgeLabel| {
+            line.push(' ');
+            line.push_str(&label.text);
+            line.push(' ');
+        };
+
+        let mut line = self.line_builder(layout);
+        self.push_border(&mut line, |line| self.push_left_edge(line, layout, row));
+        let mut remaining = layout.inner;
+
+        if let Some(label) = &labels.0 {
+            self.push_border(&mut line, |line| {
+                line.push_repeated(fill, char_width(fill));
+                line.extend(open);
+            });
+            push_label(&mut line, label);
+            self.push_border(&mut line, |line| line.extend(close));
+            remaining = remaining.saturating_sub(char_width(fill) + segment_width(label));
+        }
+
+        let right_width = labels
+            .1
+            .as_ref()
+            .map_or(0, |label| segment_width(label) + char_width(fill));
+        // A wide fill character leaves an odd column over, which pads the label beside it.
+        let middle = remaining.saturating_sub(right_width);
+        let odd = middle % char_width(fill);
+        if labels.0.is_some() {
+            line.push_spaces(odd);
+        }
+        self.push_border(&mut line, |line| line.push_repeated(fill, middle - odd));
+        if labels.0.is_none() {
+            line.push_spaces(odd);
+        }
+
+        if let Some(label) = &labels.1 {
+            self.push_border(&mut line, |line| line.extend(open));
+            push_label(&mut line, label);
+            self.push_border(&mut line, |line| {
+                line.extend(close);
+                line.push_repeated(fill, char_width(fill));
+            });
+        }
+
+        self.push_border(&mut line, |line| self.push_right_edge(line, layout, row));
+        line.finish()
+    }
+
+    fn create_margin_line(&self, layout: &Layout, y: usize) -> String {
+        self.create_interior_line(layout, |line| self.push_blank(line, 0, y, layout.inner))
+    }
+
+    /// Creates the row `y` of the interior holding one line of the message, padded to the width
+    /// of the interior.
+    pub(crate) fn create_message_line(&self, layout: &Layout, y: usize, message: &str) -> String {
+        self.create_interior_line(layout, |line| self.push_message(line, layout, y, message))
+    }
+
+    /// Creates a row of the interior between the left and right borders, its interior pushed by
+    /// `interior` and painted with the background color.
+    fn create_interior_line(
+        &self,
+        layout: &Layout,
+        interior: impl FnOnce(&mut LineBuilder),
+    ) -> String {
+        let mut line = self.line_builder(layout);
+        self.push_border(&mut line, |line| {
+            self.push_left_edge(line, layout, Row::Middle)
+        });
+        self.push_interior(&mut line, interior);
+        self.push_border(&mut line, |line| {
+            self.push_right_edge(line, layout, Row::Middle)
+        });
+        line.finish()
+    }
+
+    /// Returns the interior of the margin row `y`, without the borders.
+    pub(crate) fn margin_interior(&self, layout: &Layout, y: usize) -> String {
+        let mut line = self.line_builder(layout);
+        self.push_interior(&mut line, |line| self.push_blank(line, 0, y, layout.inner));
+        line.finish()
+    }
+
+    /// Returns the interior of the row `y` holding one line of the message, without the borders.
+    pub(crate) fn message_interior(&self, layout: &Layout, y: usize, message: &str) -> String {
+        let mut line = self.line_builder(layout);
+        self.push_interior(&mut line, |line| {
+            self.push_message(line, layout, y, message)
+        });
+        line.finish()
+    }
+
+    /// Pushes one line of the message at row `y` of the interior, between the blank margins.
+    fn push_message(&self, line: &mut LineBuilder, layout: &Layout, y: usize, message: &str) {
+        let left_margin = self.opts.margin_thickness.left;
+        let message_end = left_margin.saturating_add(str_width(message));
+
+        self.push_blank(line, 0, y, left_margin);
+        line.push_str(message);
+        self.push_blank(
+            line,
+            message_end,
+            y,
+            layout.inner.saturating_sub(message_end),
+        );
+    }
+
+    /// Pushes `width` columns of blank interior starting at column `x` of row `y`, filled with
+    /// the margin pattern if one is set.
+    fn push_blank(&self, line: &mut LineBuilder, x: usize, y: usize, width: usize) {
+        match &self.opts.margin_pattern {
+            Some(pattern) => pattern.fill(line, x, y, width),
+            None => line.push_spaces(width),
+        }
+    }
+
+    /// Returns an empty line with room for a row of the box, up to `MAX_LINE_CAPACITY` bytes.
+    fn line_builder(&self, layout: &Layout) -> LineBuilder {
+        let capacity = layout.width().saturating_mul(4).saturating_add(32);
+        LineBuilder::with_capacity(capacity.min(MAX_LINE_CAPACITY))
+    }
+
+    /// Pushes the border pushed by `border`, encoded for the charset and painted with the border
+    /// color.
+    fn push_border(&self, line: &mut LineBuilder, border: impl FnOnce(&mut LineBuilder)) {
+        let mark = line.mark();
+        border(line);
+        if line.mark() == mark {
+            return;
+        }
+        if self.opts.charset == Charset::DecGraphics {
+            line.map_since(mark, |border| Charset::DecGraphics.encode(border));
+        }
+        if let Some((open, close)) = &self.border_sgr {
+            line.wrap_since(mark, open, close);
+        }
+    }
+
+    /// Pushes the interior pushed by `interior`, painted with the background color.
+    fn push_interior(&self, line: &mut LineBuilder, interior: impl FnOnce(&mut LineBuilder)) {
+        let mark = line.mark();
+        interior(line);
+        if let Some(open) = &self.background_sgr {
+            line.wrap_since(mark, open, "\x1b[49m");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::catch_unwind;
+
+    use crate::{
+        create_text_border, Sides, TextBorderOptions, Width, MAX_CONTENT_WIDTH, MAX_THICKNESS,
+    };
+
+    /// Renders `message` with `options` and fails the test if rendering panics.
+    fn render(message: &str, options: TextBorderOptions) -> String {
+        catch_unwind(|| create_text_border(message, Some(options))).expect("rendering panicked")
+    }
+
+    #[test]
+    fn huge_thicknesses_are_capped() {
+        let bordered = render(
+            "x",
+            TextBorderOptions {
+                border_thickness: Sides::new(usize::MAX, 0, 0, 0),
+                ..Default::default()
+            },
+        );
+        assert_eq!(bordered, format!("{}x", "*".repeat(MAX_THICKNESS)));
+
+        let bordered = render(
+            "x",
+            TextBorderOptions {
+                border_thickness: Sides::all(0),
+                margin_thickness: Sides::new(0, 0, usize::MAX, 0),
+                prevent_trim: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(bordered, format!("x{}", " ".repeat(MAX_THICKNESS)));
+    }
+
+    #[test]
+    fn huge_column_gap_is_capped() {
+        let bordered = render(
+            "a\nb\nc",
+            TextBorderOptions {
+                border_thickness: Sides::all(0),
+                columns: 3,
+                column_gap: usize::MAX,
+                ..Default::default()
+            },
+        );
+        let gap = " ".repeat(MAX_CONTENT_WIDTH);
+        assert_eq!(bordered, format!("a{}b{}c", gap, gap));
+    }
+
+    #[test]
+    fn huge_tab_width_is_capped() {
+        let bordered = render(
+            "\tx",
+            TextBorderOptions {
+                border_thickness: Sides::all(0),
+                code_block: Some(usize::MAX),
+                ..Default::default()
+            },
+        );
+        assert_eq!(bordered, format!("{}x", " ".repeat(MAX_CONTENT_WIDTH)));
+    }
+
+    #[test]
+    fn labels_wider_than_a_narrow_box_do_not_underflow() {
+        let bordered = render(
+            "x",
+            TextBorderOptions {
+                title: Some("a long title".to_string()),
+                footer: Some("a long footer".to_string()),
+                max_width: Some(Width::Columns(4)),
+                ..Default::default()
+            },
+        );
+        assert!(bordered.lines().count() >= 3);
+    }
+
+    #[test]
+    fn huge_min_width_is_capped() {
+        let bordered = render(
+            "x",
+            TextBorderOptions {
+                min_width: Some(Width::Columns(usize::MAX)),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(bordered.lines().next().unwrap().len(), MAX_CONTENT_WIDTH);
+    }
+
+    #[test]
+    fn huge_target_width_is_capped() {
+        let bordered = render(
+            "x",
+            TextBorderOptions {
+                target_width: Some(Width::Columns(usize::MAX)),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(bordered, "***\n*x*\n***");
+    }
+
+    #[test]
+    fn invisibles_are_shown_and_kept_untrimmed() {
+        let options = TextBorderOptions {
+            show_invisibles: true,
+            ..Default::default()
+        };
+        assert_eq!(render(" a\tb  \n", options), "********\n*·a→b··*\n********");
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn trailing_invisibles_are_highlighted_with_colors() {
+        let options = TextBorderOptions {
+            show_invisibles: true,
+            color_mode: crate::ColorMode::Always,
+            ..Default::default()
+        };
+        let rendered = render("a \nb", options);
+        assert!(rendered.contains("a\x1b[7m·\x1b[27m"));
+        assert!(!rendered.contains("b\x1b[7m"));
+    }
+
+    #[test]
+    fn wide_edges_have_no_gaps_next_to_labels() {
+        let options = TextBorderOptions {
+            border_char: '＃',
+            title: Some("T".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            render("Hello", options.clone()),
+            "＃＃ T  ＃＃\n＃Hello   ＃\n＃＃＃＃＃＃"
+        );
+
+        let options = TextBorderOptions {
+            footer: Some("Title".into()),
+            ..options
+        };
+        assert_eq!(
+            render("Hello", options),
+            "＃＃ T  ＃＃＃＃\n＃Hello       ＃\n＃＃ Title  ＃＃"
+        );
+    }
+
+    #[test]
+    fn titles_wider_than_the_target_keep_the_message() {
+        let options = TextBorderOptions {
+            target_width: Some(Width::Columns(1)),
+            title: Some("Title".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            render("Hello world", options),
+            "** Title **\n*Hello    *\n*world    *\n***********"
+        );
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn wrapped_highlights_are_closed_at_each_row() {
+        let options = TextBorderOptions {
+            wrap_width: Some(6),
+            color_mode: crate::ColorMode::Always,
+            highlights: vec![(
+                0..11,
+                crate::Style {
+                    bold: true,
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        assert_eq!(
+            render("hello world", options),
+            "*******\n*\x1b[1mhello\x1b[22m*\n*\x1b[1mworld\x1b[22m*\n*******"
+        );
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn whitespace_inside_highlights_is_trimmed() {
+        let options = TextBorderOptions {
+            color_mode: crate::ColorMode::Always,
+            highlights: vec![(
+                0..4,
+                crate::Style {
+                    bold: true,
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        assert_eq!(
+            render("  abc  ", options),
+            "*****\n*\x1b[1mab\x1b[22mc*\n*****"
+        );
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn wrapped_markdown_styles_stay_out_of_the_border() {
+        let options = TextBorderOptions {
+            wrap_width: Some(6),
+            color_mode: crate::ColorMode::Always,
+            markdown: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            render("**hello world**", options),
+            "*******\n*\x1b[1mhello\x1b[22m*\n*\x1b[1mworld\x1b[22m*\n*******"
+        );
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn wrapped_diff_lines_stay_out_of_the_border() {
+        let options = TextBorderOptions {
+            max_width: Some(Width::Columns(10)),
+            color_mode: crate::ColorMode::Always,
+            diff: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            render("+added line here", options),
+            "********\n\
+             *\x1b[32m+added\x1b[39m*\n\
+             *\x1b[32mline\x1b[39m  *\n\
+             *\x1b[32mhere\x1b[39m  *\n\
+             ********"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use std::error::Error;
+use std::fmt;
+
+use eyre::{EyreHandler, InstallError};
+
+use crate::{default_options, Diagnostic, Severity, TextBorderOptions};
+
+/// `BorderedHandler` is an eyre `EyreHandler` that renders reports inside a border: the error
+/// in the top border, and each error in its chain of causes on its own line. This type is
+/// available with the `eyre` feature.
+///
+/// Install it with `install_eyre_hook`, so errors returned from `main` come out framed.
+pub struct BorderedHandler {
+    options: TextBorderOptions,
+}
+
+impl BorderedHandler {
+    /// Creates a handler rendering reports with `options`.
+    pub fn new(options: TextBorderOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl EyreHandler for BorderedHandler {
+    fn debug(&self, error: &(dyn Error + 'static), f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            return fmt::Debug::fmt(error, f);
+        }
+
+        let mut causes = Vec::new();
+        let mut source = error.source();
+        while let Some(cause) = source {
+            causes.push(format!("{}: {}", causes.len(), cause));
+            source = cause.source();
+        }
+        let message = if causes.is_empty() {
+            String::new()
+        } else {
+            format!("Caused by:\n{}", causes.join("\n"))
+        };
+
+        let diagnostic = Diagnostic {
+            severity: Severity::Error,
+            title: error.to_string(),
+            message,
+            snippet: None,
+        };
+        f.write_str(&diagnostic.render(Some(self.options.clone())))
+    }
+}
+
+/// Installs `BorderedHandler` as the eyre report handler, so `eyre::Report`s are rendered
+/// inside a border when printed with `{:?}`, as errors returned from `main` are. This function
+/// is available with the `eyre` feature.
+///
+/// # Arguments
+///
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations. If `None`, the options returned by `default_options` when the hook is
+///   installed are used.
+///
+/// # Errors
+///
+/// * `eyre::InstallError` - A report handler has already been installed.
+///
+/// # Examples
+///
+/// ```
+/// extern crate eyre;
+///
+/// use unicode_border::{install_eyre_hook, ColorMode, TextBorderOptions};
+///
+/// install_eyre_hook(Some(TextBorderOptions {
+///     color_mode: ColorMode::Never,
+///     ..Default::default()
+/// }))
+/// .unwrap();
+///
+/// let report = eyre::eyre!("disk full").wrap_err("saving failed");
+///
+/// assert_eq!(
+///     format!("{:?}", report),
+///     "** error: saving failed **\n\
+///      *Caused by:              *\n\
+///      *0: disk full            *\n\
+///      **************************"
+/// );
+/// ```
+pub fn install_eyre_hook(options: Option<TextBorderOptions>) -> Result<(), InstallError> {
+    let options = options.unwrap_or_else(default_options);
+
+    eyre::set_hook(Box::new(move |_| {
+        Box::new(BorderedHandler::new(options.clone()))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ColorMode;
+
+    #[derive(Debug)]
+    struct Failure(&'static str, Option<Box<Failure>>);
+
+    impl fmt::Display for Failure {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl Error for Failure {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            self.1
+                .as_deref()
+                .map(|cause| cause as &(dyn Error + 'static))
+        }
+    }
+
+    struct Report<'a>(&'a BorderedHandler, Failure);
+
+    impl fmt::Debug for Report<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.0.debug(&self.1, f)
+        }
+    }
+
+    fn handler() -> BorderedHandler {
+        BorderedHandler::new(TextBorderOptions {
+            color_mode: ColorMode::Never,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn causes_are_numbered_in_order() {
+        let error = Failure(
+            "c",
+            Some(Box::new(Failure("b", Some(Box::new(Failure("a", None)))))),
+        );
+        assert_eq!(
+            format!("{:?}", Report(&handler(), error)),
+            "** error: c **\n*Caused by:  *\n*0: b        *\n*1: a        *\n**************"
+        );
+    }
+
+    #[test]
+    fn errors_without_causes_have_no_body() {
+        assert_eq!(
+            format!("{:?}", Report(&handler(), Failure("oops", None))),
+            "** error: oops **\n*               *\n*****************"
+        );
+    }
+
+    #[test]
+    fn the_alternate_form_is_left_unframed() {
+        let error = Failure("oops", None);
+        assert_eq!(
+            format!("{:#?}", Report(&handler(), Failure("oops", None))),
+            format!("{:#?}", error)
+        );
+    }
+
+    #[test]
+    fn the_hook_is_installed_once() {
+        let _ = install_eyre_hook(None);
+        assert!(install_eyre_hook(None).is_err());
+    }
+}
@@ -0,0 +1,262 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// `Sides` holds a value for each side of a box, such as the thickness of a border or the line
+/// style of each of its edges.
+///
+/// Tuples in the order (left, top, right, bottom) and single values, used for every side,
+/// convert into `Sides`.
+///
+/// Sides also parse from strings of one to four values separated by spaces or commas, in the
+/// order of CSS shorthands such as `margin`: `"all"`, `"vertical horizontal"`,
+/// `"top horizontal bottom"` and `"top right bottom left"`.
+///
+/// # Fields
+///
+/// * `left` - The value for the left side.
+/// * `top` - The value for the top side.
+/// * `right` - The value for the right side.
+/// * `bottom` - The value for the bottom side.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, Sides, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_thickness: Sides::all(1),
+///     margin_thickness: Sides::symmetric(1, 0),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(create_text_border("Hi", Some(options)), "******\n* Hi *\n******");
+///
+/// assert_eq!(Sides::from((1, 2, 3, 4)), Sides::new(1, 2, 3, 4));
+/// assert_eq!(Sides::from(2), Sides::new(2, 2, 2, 2));
+/// assert_eq!(Sides::symmetric(2, 1), Sides::new(2, 1, 2, 1));
+/// assert_eq!("1 2".parse(), Ok(Sides::symmetric(2, 1)));
+/// assert_eq!("1, 2, 3, 4".parse(), Ok(Sides::new(4, 1, 2, 3)));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Sides<T> {
+    pub left: T,
+    pub top: T,
+    pub right: T,
+    pub bottom: T,
+}
+
+impl<T> Sides<T> {
+    /// Creates sides from their values in the order (left, top, right, bottom).
+    pub fn new(left: T, top: T, right: T, bottom: T) -> Self {
+        Sides {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// Returns the sides with `f` applied to the value of each side.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Sides<U> {
+        Sides {
+            left: f(self.left),
+            top: f(self.top),
+            right: f(self.right),
+            bottom: f(self.bottom),
+        }
+    }
+}
+
+impl<T: Clone> Sides<T> {
+    /// Creates sides with the same `value` for every side.
+    pub fn all(value: T) -> Self {
+        Sides::new(value.clone(), value.clone(), value.clone(), value)
+    }
+
+    /// Creates sides with `horizontal` for the left and right sides and `vertical` for the top
+    /// and bottom sides, such as a margin 2 columns wide and 1 row tall.
+    pub fn symmetric(horizontal: T, vertical: T) -> Self {
+        Sides::new(horizontal.clone(), vertical.clone(), horizontal, vertical)
+    }
+}
+
+impl Sides<usize> {
+    /// Returns the largest value of the sides.
+    pub(crate) fn largest(&self) -> usize {
+        self.left.max(self.top).max(self.right).max(self.bottom)
+    }
+
+    /// Returns the sum of the left and right values, saturating at `usize::MAX`.
+    pub(crate) fn horizontal(&self) -> usize {
+        self.left.saturating_add(self.right)
+    }
+}
+
+impl<T> From<(T, T, T, T)> for Sides<T> {
+    fn from((left, top, right, bottom): (T, T, T, T)) -> Self {
+        Sides::new(left, top, right, bottom)
+    }
+}
+
+impl<T: Clone> From<T> for Sides<T> {
+    fn from(value: T) -> Self {
+        Sides::all(value)
+    }
+}
+
+impl<T> From<Sides<T>> for (T, T, T, T) {
+    fn from(sides: Sides<T>) -> Self {
+        (sides.left, sides.top, sides.right, sides.bottom)
+    }
+}
+
+impl<T: FromStr + Clone> FromStr for Sides<T> {
+    type Err = ParseSidesError;
+
+    /// Parses one to four values separated by spaces or commas, in the order of CSS
+    /// shorthands, clockwise from the top.
+    ///
+    /// # Errors
+    ///
+    /// * `ParseSidesError::InvalidCount` - The string has no values or more than four.
+    /// * `ParseSidesError::InvalidValue` - A value could not be parsed.
+    ///
+    /// # Examples
+    ///
+    /// One value is used for every side:
+    ///
+    /// ```
+    /// use unicode_border::Sides;
+    ///
+    /// assert_eq!("1".parse(), Ok(Sides::all(1)));
+    /// ```
+    ///
+    /// Two values are the vertical sides, top and bottom, then the horizontal sides, left and
+    /// right:
+    ///
+    /// ```
+    /// use unicode_border::Sides;
+    ///
+    /// assert_eq!("1 2".parse(), Ok(Sides { top: 1, bottom: 1, left: 2, right: 2 }));
+    /// ```
+    ///
+    /// Three values are the top, the horizontal sides and the bottom:
+    ///
+    /// ```
+    /// use unicode_border::Sides;
+    ///
+    /// assert_eq!("1 2 3".parse(), Ok(Sides { top: 1, left: 2, right: 2, bottom: 3 }));
+    /// ```
+    ///
+    /// Four values are the top, right, bottom and left sides:
+    ///
+    /// ```
+    /// use unicode_border::Sides;
+    ///
+    /// assert_eq!(
+    ///     "1, 2, 3, 4".parse(),
+    ///     Ok(Sides { top: 1, right: 2, bottom: 3, left: 4 })
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|value| !value.is_empty())
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| ParseSidesError::InvalidValue(value.to_string()))
+            })
+            .collect::<Result<Vec<T>, _>>()?;
+
+        match values.as_slice() {
+            [all] => Ok(Sides::all(all.clone())),
+            [vertical, horizontal] => Ok(Sides::symmetric(horizontal.clone(), vertical.clone())),
+            [top, horizontal, bottom] => Ok(Sides::new(
+                horizontal.clone(),
+                top.clone(),
+                horizontal.clone(),
+                bottom.clone(),
+            )),
+            [top, right, bottom, left] => Ok(Sides::new(
+                left.clone(),
+                top.clone(),
+                right.clone(),
+                bottom.clone(),
+            )),
+            _ => Err(ParseSidesError::InvalidCount(values.len())),
+        }
+    }
+}
+
+/// `ParseSidesError` describes why a string could not be parsed into `Sides`.
+///
+/// # Variants
+///
+/// * `InvalidCount` - The string has no values or more than four.
+/// * `InvalidValue` - A value of the string could not be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseSidesError {
+    InvalidCount(usize),
+    InvalidValue(String),
+}
+
+impl fmt::Display for ParseSidesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseSidesError::InvalidCount(count) => write!(
+                f,
+                "expected 1 to 4 values for the sides of a box, found {}",
+                count
+            ),
+            ParseSidesError::InvalidValue(value) => {
+                write!(f, "invalid value {:?} for a side of a box", value)
+            }
+        }
+    }
+}
+
+impl Error for ParseSidesError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_css_form() {
+        assert_eq!("3".parse(), Ok(Sides::new(3, 3, 3, 3)));
+        assert_eq!("1 2".parse(), Ok(Sides::new(2, 1, 2, 1)));
+        assert_eq!("1 2 3".parse(), Ok(Sides::new(2, 1, 2, 3)));
+        assert_eq!("1 2 3 4".parse(), Ok(Sides::new(4, 1, 2, 3)));
+    }
+
+    #[test]
+    fn accepts_commas_and_extra_spaces() {
+        assert_eq!(" 1,2 ,, 3\t4 ".parse(), Ok(Sides::new(4, 1, 2, 3)));
+    }
+
+    #[test]
+    fn rejects_wrong_counts() {
+        assert_eq!(
+            "".parse::<Sides<u8>>(),
+            Err(ParseSidesError::InvalidCount(0))
+        );
+        assert_eq!(
+            "1 2 3 4 5".parse::<Sides<u8>>(),
+            Err(ParseSidesError::InvalidCount(5))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        assert_eq!(
+            "1 x".parse::<Sides<u8>>(),
+            Err(ParseSidesError::InvalidValue("x".to_string()))
+        );
+        assert_eq!(
+            "-1".parse::<Sides<usize>>(),
+            Err(ParseSidesError::InvalidValue("-1".to_string()))
+        );
+    }
+}
@@ -0,0 +1,116 @@
+/// The bars of a sparkline, from the lowest value to the highest.
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Creates a sparkline of `values`, one bar per column, such as to show a compact history of a
+/// metric as a line of a box or as its footer.
+///
+/// The values are stretched or squeezed to fill `width` columns: when there are more values
+/// than columns, each bar shows the average of the values it covers. The lowest value is drawn
+/// as `▁` and the highest as `█`; if all values are the same, they are drawn as `▄`. Values that
+/// are not finite, such as `NaN`, are left out, and columns with no values are left blank.
+///
+/// # Arguments
+///
+/// * `values` - The values, in order.
+/// * `width` - The width, in columns, of the sparkline, such as the width of the content of
+///   the box.
+///
+/// # Returns
+///
+/// * A `String` of `width` bars, or an empty string if there are no values.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, sparkline, BorderStyle, TextBorderOptions};
+///
+/// let latency = [12.0, 15.0, 11.0, 30.0, 22.0, 18.0, 40.0, 25.0];
+/// assert_eq!(sparkline(&latency, 8), "▁▂▁▆▄▃█▄");
+/// assert_eq!(sparkline(&latency, 4), "▁▄▃█");
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     ..Default::default()
+/// };
+/// let message = format!("p99 latency\n{}", sparkline(&latency, 11));
+///
+/// assert_eq!(
+///     create_text_border(message, Some(options)),
+///     "┌───────────┐\n│p99 latency│\n│▁▁▂▁▁▆▄▃▃█▄│\n└───────────┘"
+/// );
+/// ```
+pub fn sparkline(values: &[f64], width: usize) -> String {
+    if values.is_empty() {
+        return String::new();
+    }
+
+    let columns: Vec<Option<f64>> = (0..width)
+        .map(|column| {
+            let start = column * values.len() / width;
+            let end = ((column + 1) * values.len() / width).max(start + 1);
+            let finite: Vec<f64> = values[start..end]
+                .iter()
+                .copied()
+                .filter(|value| value.is_finite())
+                .collect();
+            if finite.is_empty() {
+                None
+            } else {
+                Some(finite.iter().sum::<f64>() / finite.len() as f64)
+            }
+        })
+        .collect();
+
+    let (min, max) = columns
+        .iter()
+        .flatten()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+            (min.min(value), max.max(value))
+        });
+    columns
+        .iter()
+        .map(|column| match column {
+            None => ' ',
+            Some(_) if max - min <= f64::EPSILON * max.abs().max(1.0) => BARS[3],
+            Some(value) => {
+                let level = (value - min) / (max - min) * (BARS.len() - 1) as f64;
+                BARS[level.round() as usize]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sparkline;
+
+    #[test]
+    fn values_are_scaled_from_the_lowest_to_the_highest_bar() {
+        assert_eq!(sparkline(&[0.0, 1.0, 2.0, 7.0], 4), "▁▂▃█");
+        assert_eq!(sparkline(&[-5.0, 5.0], 2), "▁█");
+    }
+
+    #[test]
+    fn values_are_averaged_or_stretched_to_the_width() {
+        assert_eq!(sparkline(&[0.0, 2.0, 10.0, 12.0], 2), "▁█");
+        assert_eq!(sparkline(&[0.0, 1.0], 4), "▁▁██");
+    }
+
+    #[test]
+    fn constant_values_are_drawn_at_mid_height() {
+        assert_eq!(sparkline(&[3.0, 3.0, 3.0], 3), "▄▄▄");
+        assert_eq!(sparkline(&[1e300, 1e300], 2), "▄▄");
+    }
+
+    #[test]
+    fn values_that_are_not_finite_leave_gaps() {
+        assert_eq!(sparkline(&[0.0, f64::NAN, f64::INFINITY, 1.0], 4), "▁  █");
+        assert_eq!(sparkline(&[f64::NAN], 2), "  ");
+    }
+
+    #[test]
+    fn nothing_is_drawn_without_values_or_width() {
+        assert_eq!(sparkline(&[], 5), "");
+        assert_eq!(sparkline(&[1.0], 0), "");
+    }
+}
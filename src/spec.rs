@@ -0,0 +1,303 @@
+use crate::{
+    AmbiguousWidth, BlankLines, BorderLayers, BorderStyle, Charset, Color, Pattern, Sides, Style,
+    TextBorderOptions, Width,
+};
+
+/// `BorderSpec` is the part of a `TextBorderOptions` drawing the border, so one border can be
+/// shared by boxes, tables and documents while their other options differ.
+///
+/// # Fields
+///
+/// * `border_char` - The character used to create the border when `border_style` is
+///   `BorderStyle::Char`.
+/// * `border_style` - The `BorderStyle` selecting the characters used to draw the border.
+/// * `border_thickness` - The `Sides` specifying the border thickness of each side.
+/// * `border_layers` - Optional characters for each layer of a border thicker than 1.
+/// * `border_color` - An optional `Color` for the border characters.
+/// * `border_attributes` - An optional `Style` adding attributes such as `dim` to the border.
+/// * `charset` - The `Charset` borders are drawn with.
+///
+/// The fields mean the same as in `TextBorderOptions`, and default to the same values.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, BorderSpec, BorderStyle, BorderedDocument, Sides};
+/// use unicode_border::TextBorderOptions;
+///
+/// let border = BorderSpec {
+///     border_style: BorderStyle::Weighted,
+///     ..Default::default()
+/// };
+/// let options = TextBorderOptions::default().with_border(border.clone());
+///
+/// assert_eq!(create_text_border("Hi", Some(options)), "┌──┐\n│Hi│\n└──┘");
+///
+/// let mut document = BorderedDocument::new(Some(TextBorderOptions {
+///     margin_thickness: Sides::symmetric(1, 0),
+///     ..TextBorderOptions::default().with_border(border)
+/// }));
+/// document.header.lines.push("1".to_string());
+/// document.push_line("2");
+///
+/// assert_eq!(document.render(), "┌───┐\n│ 1 │\n├───┤\n│ 2 │\n└───┘");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BorderSpec {
+    pub border_char: char,
+    pub border_style: BorderStyle,
+    pub border_thickness: Sides<usize>,
+    pub border_layers: Option<BorderLayers>,
+    pub border_color: Option<Color>,
+    pub border_attributes: Option<Style>,
+    pub charset: Charset,
+}
+
+impl Default for BorderSpec {
+    fn default() -> Self {
+        TextBorderOptions::default().border()
+    }
+}
+
+/// `SpacingSpec` is the part of a `TextBorderOptions` sizing the box: its margins and the
+/// widths and proportions it is fitted to.
+///
+/// # Fields
+///
+/// * `margin_thickness` - The `Sides` specifying the margin thickness of each side.
+/// * `margin_pattern` - An optional `Pattern` filling the margins in place of spaces.
+/// * `target_width` - An optional `Width` the whole box should fit in.
+/// * `min_width` - An optional minimum `Width` of the whole box.
+/// * `max_width` - An optional maximum `Width` of the whole box.
+/// * `aspect_ratio` - An optional ratio of the width of the box to its height.
+///
+/// The fields mean the same as in `TextBorderOptions`, and default to the same values.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, Sides, SpacingSpec, TextBorderOptions};
+///
+/// let spacing = SpacingSpec {
+///     margin_thickness: Sides::symmetric(1, 0),
+///     ..Default::default()
+/// };
+/// let options = TextBorderOptions::default().with_spacing(spacing);
+///
+/// assert_eq!(create_text_border("Hi", Some(options)), "******\n* Hi *\n******");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpacingSpec {
+    pub margin_thickness: Sides<usize>,
+    pub margin_pattern: Option<Pattern>,
+    pub target_width: Option<Width>,
+    pub min_width: Option<Width>,
+    pub max_width: Option<Width>,
+    pub aspect_ratio: Option<f64>,
+}
+
+impl Default for SpacingSpec {
+    fn default() -> Self {
+        TextBorderOptions::default().spacing()
+    }
+}
+
+/// `ContentSpec` is the part of a `TextBorderOptions` preparing the message before it is
+/// framed: trimming, wrapping, measuring and the modes it is read in.
+///
+/// # Fields
+///
+/// * `prevent_trim` - A boolean flag indicating whether to prevent trimming whitespace from the
+///   message.
+/// * `normalize` - A boolean flag indicating whether to normalize the message to Unicode
+///   Normalization Form C.
+/// * `wrap_width` - An optional maximum width, in columns, of the message.
+/// * `ambiguous_width` - The `AmbiguousWidth` deciding how East Asian Ambiguous characters are
+///   measured.
+/// * `show_invisibles` - A boolean flag showing spaces and tabs.
+/// * `blank_lines` - The `BlankLines` deciding whether blank lines of the message are kept.
+/// * `dedent` - A boolean flag indicating whether to strip common leading whitespace.
+/// * `code_block` - An optional tab width enabling code mode.
+/// * `markdown` - A boolean flag indicating whether to convert simple inline Markdown.
+/// * `diff` - A boolean flag enabling diff mode.
+/// * `json_indent` - The number of spaces each level of nesting of JSON is indented by.
+/// * `columns` - The number of columns the message is flowed into.
+/// * `column_gap` - The number of spaces between columns.
+///
+/// The fields mean the same as in `TextBorderOptions`, and default to the same values.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, ContentSpec, TextBorderOptions};
+///
+/// let content = ContentSpec {
+///     wrap_width: Some(5),
+///     ..Default::default()
+/// };
+/// let options = TextBorderOptions::default().with_content(content);
+///
+/// assert_eq!(
+///     create_text_border("hello world", Some(options)),
+///     "*******\n*hello*\n*world*\n*******"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContentSpec {
+    pub prevent_trim: bool,
+    pub normalize: bool,
+    pub wrap_width: Option<usize>,
+    pub ambiguous_width: AmbiguousWidth,
+    pub show_invisibles: bool,
+    pub blank_lines: BlankLines,
+    pub dedent: bool,
+    pub code_block: Option<usize>,
+    pub markdown: bool,
+    pub diff: bool,
+    pub json_indent: usize,
+    pub columns: usize,
+    pub column_gap: usize,
+}
+
+impl Default for ContentSpec {
+    fn default() -> Self {
+        TextBorderOptions::default().content()
+    }
+}
+
+impl TextBorderOptions {
+    /// Returns the `BorderSpec` of the options.
+    pub fn border(&self) -> BorderSpec {
+        BorderSpec {
+            border_char: self.border_char,
+            border_style: self.border_style,
+            border_thickness: self.border_thickness,
+            border_layers: self.border_layers.clone(),
+            border_color: self.border_color,
+            border_attributes: self.border_attributes,
+            charset: self.charset,
+        }
+    }
+
+    /// Returns the `SpacingSpec` of the options.
+    pub fn spacing(&self) -> SpacingSpec {
+        SpacingSpec {
+            margin_thickness: self.margin_thickness,
+            margin_pattern: self.margin_pattern.clone(),
+            target_width: self.target_width,
+            min_width: self.min_width,
+            max_width: self.max_width,
+            aspect_ratio: self.aspect_ratio,
+        }
+    }
+
+    /// Returns the `ContentSpec` of the options.
+    pub fn content(&self) -> ContentSpec {
+        ContentSpec {
+            prevent_trim: self.prevent_trim,
+            normalize: self.normalize,
+            wrap_width: self.wrap_width,
+            ambiguous_width: self.ambiguous_width,
+            show_invisibles: self.show_invisibles,
+            blank_lines: self.blank_lines,
+            dedent: self.dedent,
+            code_block: self.code_block,
+            markdown: self.markdown,
+            diff: self.diff,
+            json_indent: self.json_indent,
+            columns: self.columns,
+            column_gap: self.column_gap,
+        }
+    }
+
+    /// Returns the options with the fields of `border` in place of their own.
+    pub fn with_border(mut self, border: BorderSpec) -> Self {
+        self.border_char = border.border_char;
+        self.border_style = border.border_style;
+        self.border_thickness = border.border_thickness;
+        self.border_layers = border.border_layers;
+        self.border_color = border.border_color;
+        self.border_attributes = border.border_attributes;
+        self.charset = border.charset;
+        self
+    }
+
+    /// Returns the options with the fields of `spacing` in place of their own.
+    pub fn with_spacing(mut self, spacing: SpacingSpec) -> Self {
+        self.margin_thickness = spacing.margin_thickness;
+        self.margin_pattern = spacing.margin_pattern;
+        self.target_width = spacing.target_width;
+        self.min_width = spacing.min_width;
+        self.max_width = spacing.max_width;
+        self.aspect_ratio = spacing.aspect_ratio;
+        self
+    }
+
+    /// Returns the options with the fields of `content` in place of their own.
+    pub fn with_content(mut self, content: ContentSpec) -> Self {
+        self.prevent_trim = content.prevent_trim;
+        self.normalize = content.normalize;
+        self.wrap_width = content.wrap_width;
+        self.ambiguous_width = content.ambiguous_width;
+        self.show_invisibles = content.show_invisibles;
+        self.blank_lines = content.blank_lines;
+        self.dedent = content.dedent;
+        self.code_block = content.code_block;
+        self.markdown = content.markdown;
+        self.diff = content.diff;
+        self.json_indent = content.json_indent;
+        self.columns = content.columns;
+        self.column_gap = content.column_gap;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn customized() -> TextBorderOptions {
+        TextBorderOptions {
+            border_char: '#',
+            border_style: BorderStyle::Weighted,
+            border_thickness: Sides::new(1, 2, 3, 4),
+            charset: Charset::Ascii,
+            margin_thickness: Sides::symmetric(2, 1),
+            max_width: Some(Width::Columns(40)),
+            aspect_ratio: Some(2.0),
+            wrap_width: Some(10),
+            ambiguous_width: AmbiguousWidth::Wide,
+            code_block: Some(4),
+            columns: 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn specs_round_trip_through_the_options() {
+        let options = customized();
+        let rebuilt = TextBorderOptions::default()
+            .with_border(options.border())
+            .with_spacing(options.spacing())
+            .with_content(options.content());
+        assert_eq!(rebuilt.border(), options.border());
+        assert_eq!(rebuilt.spacing(), options.spacing());
+        assert_eq!(rebuilt.content(), options.content());
+    }
+
+    #[test]
+    fn each_spec_replaces_its_own_fields_only() {
+        let options = customized().with_border(BorderSpec::default());
+        assert_eq!(options.border(), BorderSpec::default());
+        assert_eq!(options.spacing(), customized().spacing());
+        assert_eq!(options.content(), customized().content());
+
+        let options = customized().with_spacing(SpacingSpec::default());
+        assert_eq!(options.border(), customized().border());
+        assert_eq!(options.spacing(), SpacingSpec::default());
+
+        let options = customized().with_content(ContentSpec::default());
+        assert_eq!(options.spacing(), customized().spacing());
+        assert_eq!(options.content(), ContentSpec::default());
+    }
+}
@@ -0,0 +1,166 @@
+use crate::width::str_width;
+use crate::wrap::truncate_to_width;
+
+/// `StatusBar` is a row inside the bottom border split into segments aligned left, centered and
+/// aligned right, such as a mode, a count and a time.
+///
+/// The status bar never widens the box. The gaps between segments are filled with the bottom
+/// border character, and segments that do not fit are clipped: the center segment is left out
+/// first, then the right and left segments are truncated.
+///
+/// # Fields
+///
+/// * `left` - The segment aligned left. Empty segments are left out.
+/// * `center` - The segment centered in the row.
+/// * `right` - The segment aligned right.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, BorderStyle, StatusBar, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     status_bar: Some(StatusBar {
+///         left: "mode".to_string(),
+///         center: "12 items".to_string(),
+///         right: "14:05".to_string(),
+///     }),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     create_text_border("a message as wide as a long status bar", Some(options)),
+///     "┌──────────────────────────────────────┐\n\
+///      │a message as wide as a long status bar│\n\
+///      │ mode ──────── 12 items ─────── 14:05 │\n\
+///      └──────────────────────────────────────┘"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StatusBar {
+    pub left: String,
+    pub center: String,
+    pub right: String,
+}
+
+impl StatusBar {
+    /// Lays the segments out over `width` columns. Returns each segment shown, padded with a
+    /// space on each side, along with the width of the gap before it.
+    pub(crate) fn fit(&self, width: usize) -> Vec<(usize, String)> {
+        let pad = |segment: &str| {
+            if segment.is_empty() {
+                None
+            } else {
+                Some(format!(" {} ", segment))
+            }
+        };
+        let (mut left, mut center, mut right) =
+            (pad(&self.left), pad(&self.center), pad(&self.right));
+        let segment_width = |segment: &Option<String>| segment.as_deref().map_or(0, str_width);
+
+        let needed = |segments: [&Option<String>; 3]| {
+            let shown = segments.iter().filter(|segment| segment.is_some()).count();
+            segments
+                .iter()
+                .map(|segment| segment_width(segment))
+                .sum::<usize>()
+                + shown.saturating_sub(1)
+        };
+        if needed([&left, &center, &right]) > width {
+            center = None;
+        }
+
+        if needed([&left, &None, &right]) > width {
+            match left {
+                Some(ref text) if str_width(text) >= width => {
+                    left = Some(truncate_to_width(text, width));
+                    right = None;
+                }
+                _ => {
+                    let available = width
+                        .saturating_sub(segment_width(&left))
+                        .saturating_sub(left.is_some() as usize);
+                    right = right
+                        .map(|text| truncate_to_width(&text, available))
+                        .filter(|text| !text.is_empty());
+                }
+            }
+        }
+
+        let (left_width, center_width, right_width) = (
+            segment_width(&left),
+            segment_width(&center),
+            segment_width(&right),
+        );
+        let right_start = width - right_width;
+
+        let mut segments = Vec::new();
+        let mut end = 0;
+        if let Some(text) = left {
+            segments.push((0, text));
+            end = left_width;
+        }
+        if let Some(text) = center {
+            let latest = right_start - center_width - right.is_some() as usize;
+            let earliest = end + (end > 0) as usize;
+            let start = ((width - center_width) / 2).clamp(earliest, latest);
+            segments.push((start - end, text));
+            end = start + center_width;
+        }
+        if let Some(text) = right {
+            segments.push((right_start - end, text));
+        }
+        segments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the row `status` is laid out into over `width` columns, without its padding to
+    /// the width.
+    fn row(status: (&str, &str, &str), width: usize) -> String {
+        let status = StatusBar {
+            left: status.0.to_string(),
+            center: status.1.to_string(),
+            right: status.2.to_string(),
+        };
+        status
+            .fit(width)
+            .into_iter()
+            .map(|(gap, text)| format!("{}{}", " ".repeat(gap), text))
+            .collect()
+    }
+
+    #[test]
+    fn segments_are_spread_over_the_width() {
+        assert_eq!(row(("a", "b", "c"), 11), " a   b   c ");
+        assert_eq!(row(("a", "b", "c"), 15), " a     b     c ");
+    }
+
+    #[test]
+    fn empty_segments_are_left_out() {
+        assert_eq!(row(("", "b", ""), 7), "   b ");
+        assert_eq!(row(("", "", "c"), 5), "   c ");
+        assert_eq!(row(("", "", ""), 5), "");
+    }
+
+    #[test]
+    fn the_center_is_dropped_first() {
+        assert_eq!(row(("a", "b", "c"), 10), " a      c ");
+    }
+
+    #[test]
+    fn the_right_is_truncated_then_dropped() {
+        assert_eq!(row(("a", "", "long"), 8), " a   lon");
+        assert_eq!(row(("a", "", "long"), 4), " a ");
+    }
+
+    #[test]
+    fn a_left_wider_than_the_bar_is_truncated_alone() {
+        assert_eq!(row(("wide", "", "c"), 3), " wi");
+        assert_eq!(row(("a", "b", "c"), 0), "");
+    }
+}
@@ -0,0 +1,355 @@
+use std::borrow::Cow;
+use std::io::{self, BufRead, Write};
+
+use crate::render::{Layout, Renderer};
+use crate::width::with_ambiguous_width;
+use crate::wrap::wrap_line;
+use crate::{default_options, render_to_lines, TextBorderOptions, MAX_CONTENT_WIDTH};
+
+/// Writes each line of `lines` surrounded by a border and margin to `writer` as it is consumed.
+///
+/// The top border is written before the first line is read and the bottom border after the
+/// last, so the input is never buffered. Because the box cannot grow once its top border has
+/// been written, its width is fixed up front: lines are wrapped to `content_width` columns, at
+/// most `MAX_CONTENT_WIDTH`, and narrower lines are padded to it. Each output line, including the last, ends with `\n`.
+///
+/// Unlike `create_text_border`, leading and trailing blank lines are kept, and `wrap_width` is
+/// ignored in favour of `content_width`.
+///
+/// # Arguments
+///
+/// * `writer` - The writer to which the bordered lines are written.
+/// * `lines` - An iterator of lines. It is consumed lazily, one line per output line written.
+/// * `content_width` - The width, in columns, of the message area of the box.
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations. If `None`, the options returned by `default_options` are used.
+///
+/// # Returns
+///
+/// * `Ok(())` once the bottom border has been written, or the first error returned by `writer`.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::stream_text_border;
+///
+/// let mut output = Vec::new();
+/// stream_text_border(&mut output, ["one", "two three"], 5, None).unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "*******\n*one  *\n*two  *\n*three*\n*******\n"
+/// );
+/// ```
+pub fn stream_text_border<W, I, S>(
+    writer: &mut W,
+    lines: I,
+    content_width: usize,
+    options: Option<TextBorderOptions>,
+) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let opts = options.unwrap_or_else(default_options);
+    let content_width = content_width.clamp(1, MAX_CONTENT_WIDTH);
+
+    with_ambiguous_width(opts.ambiguous_width, || {
+        let renderer = Renderer::new(opts);
+        let layout = renderer.layout(content_width);
+
+        let mut row = renderer.first_message_row();
+        write_lines(writer, renderer.top_lines(&layout))?;
+        let mut previous_blank = false;
+        for line in lines {
+            let line = line.as_ref();
+            if renderer.skip_line(line, &mut previous_blank) {
+                continue;
+            }
+            row += write_message_line(writer, &renderer, &layout, row, line, content_width)?;
+        }
+        write_lines(writer, renderer.bottom_lines(&layout, row))
+    })
+}
+
+/// Renders only the interior rows of a box, its margins and message, for the geometry that
+/// `stream_text_border` gives a box with the same `content_width` and options, so text inside
+/// an already drawn frame can be updated in place without redrawing its borders.
+///
+/// The message is wrapped to `content_width` columns and every row is padded to the width of
+/// the interior. Header and status bar rows are not included.
+///
+/// # Arguments
+///
+/// * `message` - The message to be rendered, such as a `&str` or a `String`.
+/// * `content_width` - The width, in columns, of the message area of the box.
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations. If `None`, the options returned by `default_options` are used.
+///
+/// # Returns
+///
+/// * A `Vec<String>` of the interior rows, from the top margin to the bottom margin, each to be
+///   written as many columns into the box as the left border is wide.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{render_content_only, Sides, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     margin_thickness: Sides::symmetric(1, 0),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(render_content_only("42%", 4, Some(options)), vec![" 42%  "]);
+/// ```
+pub fn render_content_only<'a>(
+    message: impl Into<Cow<'a, str>>,
+    content_width: usize,
+    options: Option<TextBorderOptions>,
+) -> Vec<String> {
+    let opts = options.unwrap_or_else(default_options);
+    let content_width = content_width.clamp(1, MAX_CONTENT_WIDTH);
+    let message = message.into();
+
+    with_ambiguous_width(opts.ambiguous_width, || {
+        let (top, bottom) = (opts.margin_thickness.top, opts.margin_thickness.bottom);
+        let renderer = Renderer::new(opts);
+        let layout = renderer.layout(content_width);
+
+        let mut previous_blank = false;
+        let lines: Vec<String> = message
+            .lines()
+            .filter(|line| !renderer.skip_line(line, &mut previous_blank))
+            .flat_map(|line| wrap_line(&renderer.prepare_line(line.to_string()), content_width))
+            .collect();
+
+        let mut rows: Vec<String> = (0..top)
+            .map(|y| renderer.margin_interior(&layout, y))
+            .collect();
+        rows.extend(
+            lines
+                .iter()
+                .enumerate()
+                .map(|(i, line)| renderer.message_interior(&layout, top + i, line)),
+        );
+        rows.extend((0..bottom).map(|y| renderer.margin_interior(&layout, top + lines.len() + y)));
+        rows
+    })
+}
+
+/// Reads lines from `reader`, surrounds them with a border and margin and writes the result to
+/// `writer`, as a filter from standard input to standard output would.
+///
+/// If `wrap_width` is set, the box is `wrap_width` columns wide and is written line by line as
+/// input arrives, like `stream_text_border`. Otherwise the input is read to its end first so the
+/// box can be sized to its widest line, and it is rendered like `create_text_border`. Either way
+/// each output line ends with `\n`.
+///
+/// # Arguments
+///
+/// * `reader` - The source of the lines to be surrounded by a border.
+/// * `writer` - The writer to which the bordered lines are written.
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations. If `None`, the options returned by `default_options` are used.
+///
+/// # Returns
+///
+/// * `Ok(())` once the bottom border has been written, or the first error returned by `reader`
+///   or `writer`.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::box_stream;
+///
+/// let mut output = Vec::new();
+/// box_stream("first\nsecond\n".as_bytes(), &mut output, None).unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "********\n*first *\n*second*\n********\n"
+/// );
+/// ```
+pub fn box_stream<R, W>(
+    reader: R,
+    mut writer: W,
+    options: Option<TextBorderOptions>,
+) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    let opts = options.unwrap_or_else(default_options);
+
+    match opts.wrap_width {
+        Some(width) => {
+            let mut error = None;
+            let lines = reader.lines().map_while(|line| match line {
+                Ok(line) => Some(line),
+                Err(err) => {
+                    error = Some(err);
+                    None
+                }
+            });
+            stream_text_border(&mut writer, lines, width, Some(opts))?;
+            error.map_or(Ok(()), Err)
+        }
+        None => {
+            let message = reader
+                .lines()
+                .collect::<io::Result<Vec<String>>>()?
+                .join("\n");
+            write_lines(&mut writer, render_to_lines(&message, Some(opts)))
+        }
+    }
+}
+
+/// Writes one line of the message, wrapped to `content_width`, as one or more bordered rows
+/// starting at row `first_row` of the interior. Returns the number of rows written.
+pub(crate) fn write_message_line<W: Write>(
+    writer: &mut W,
+    renderer: &Renderer,
+    layout: &Layout,
+    first_row: usize,
+    line: &str,
+    content_width: usize,
+) -> io::Result<usize> {
+    let line = renderer.prepare_line(line.to_string());
+    let rows: Vec<String> = wrap_line(&line, content_width)
+        .iter()
+        .enumerate()
+        .map(|(i, row)| renderer.create_message_line(layout, first_row + i, row))
+        .collect();
+    let count = rows.len();
+
+    write_lines(writer, rows)?;
+    Ok(count)
+}
+
+pub(crate) fn write_lines<W: Write>(writer: &mut W, lines: Vec<String>) -> io::Result<()> {
+    for line in lines {
+        writeln!(writer, "{}", line)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::io::{self, Write};
+
+    use super::*;
+
+    /// A writer that fails on every write.
+    struct BrokenWriter;
+
+    impl Write for BrokenWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn streamed(lines: &[&str], content_width: usize) -> String {
+        let mut output = Vec::new();
+        stream_text_border(&mut output, lines, content_width, None).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn empty_input_writes_an_empty_box() {
+        assert_eq!(streamed(&[], 2), "****\n****\n");
+    }
+
+    #[test]
+    fn blank_lines_are_kept() {
+        assert_eq!(streamed(&["", "a", ""], 1), "***\n* *\n*a*\n* *\n***\n");
+    }
+
+    #[test]
+    fn content_width_is_clamped() {
+        assert_eq!(streamed(&["ab"], 0), "***\n*a*\n*b*\n***\n");
+        let wide = streamed(&["a"], usize::MAX);
+        assert_eq!(wide.lines().next().unwrap().len(), MAX_CONTENT_WIDTH + 2);
+    }
+
+    #[test]
+    fn writer_errors_stop_the_stream() {
+        let consumed = Cell::new(0);
+        let lines = ["a", "b"]
+            .iter()
+            .inspect(|_| consumed.set(consumed.get() + 1));
+
+        let error = stream_text_border(&mut BrokenWriter, lines, 1, None).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(consumed.get(), 0);
+    }
+
+    /// A reader that yields `data` and then fails.
+    struct FailingReader<'a> {
+        data: &'a [u8],
+    }
+
+    impl io::Read for FailingReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.data.is_empty() {
+                return Err(io::Error::other("disconnected"));
+            }
+            let count = self.data.len().min(buf.len());
+            buf[..count].copy_from_slice(&self.data[..count]);
+            self.data = &self.data[count..];
+            Ok(count)
+        }
+    }
+
+    fn wrapped(wrap_width: usize) -> Option<TextBorderOptions> {
+        Some(TextBorderOptions {
+            wrap_width: Some(wrap_width),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn box_stream_streams_with_a_wrap_width() {
+        let mut output = Vec::new();
+        box_stream("abc\n\nd".as_bytes(), &mut output, wrapped(2)).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "****\n*ab*\n*c *\n*  *\n*d *\n****\n"
+        );
+    }
+
+    #[test]
+    fn box_stream_reports_read_errors() {
+        let reader = io::BufReader::new(FailingReader { data: b"ok\n" });
+        let mut output = Vec::new();
+        let error = box_stream(reader, &mut output, None).unwrap_err();
+        assert_eq!(error.to_string(), "disconnected");
+        assert!(output.is_empty());
+
+        let reader = io::BufReader::new(FailingReader { data: b"ok\n" });
+        let mut output = Vec::new();
+        let error = box_stream(reader, &mut output, wrapped(2)).unwrap_err();
+        assert_eq!(error.to_string(), "disconnected");
+        assert_eq!(String::from_utf8(output).unwrap(), "****\n*ok*\n****\n");
+    }
+
+    #[test]
+    fn box_stream_rejects_invalid_utf8() {
+        let mut output = Vec::new();
+        let error = box_stream(&b"\xff\n"[..], &mut output, None).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn box_stream_reports_write_errors() {
+        let error = box_stream("a\n".as_bytes(), BrokenWriter, None).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+    }
+}
@@ -0,0 +1,222 @@
+use std::ops::Range;
+
+use crate::{Color, ColorSupport};
+
+/// `Style` is a set of text attributes applied with escape sequences, such as to highlight part
+/// of a message.
+///
+/// # Fields
+///
+/// * `foreground` - An optional `Color` for the text.
+/// * `background` - An optional `Color` painted behind the text.
+/// * `bold` - A boolean flag indicating whether the text is bold.
+/// * `dim` - A boolean flag indicating whether the text is dimmed, such as a border drawn
+///   faintly so the content stands out.
+/// * `underline` - A boolean flag indicating whether the text is underlined.
+/// * `inverse` - A boolean flag indicating whether the foreground and background are swapped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    pub bold: bool,
+    pub dim: bool,
+    pub underline: bool,
+    pub inverse: bool,
+}
+
+impl Style {
+    /// Returns the style with its colors downgraded to what `support` can display.
+    pub(crate) fn downgrade(self, support: ColorSupport) -> Style {
+        Style {
+            foreground: self.foreground.map(|color| color.downgrade(support)),
+            background: self.background.map(|color| color.downgrade(support)),
+            ..self
+        }
+    }
+
+    /// Returns `text` surrounded by the escape sequences of this style.
+    pub(crate) fn paint(&self, text: &str) -> String {
+        format!("{}{}{}", self.open(), text, self.close())
+    }
+
+    /// Returns the escape sequences that turn the attributes of this style on.
+    pub(crate) fn open(&self) -> String {
+        let mut codes = Vec::new();
+        if let Some(color) = self.foreground {
+            codes.push(color.sgr(30));
+        }
+        if let Some(color) = self.background {
+            codes.push(color.sgr(40));
+        }
+        if self.bold {
+            codes.push("1".to_string());
+        }
+        if self.dim {
+            codes.push("2".to_string());
+        }
+        if self.underline {
+            codes.push("4".to_string());
+        }
+        if self.inverse {
+            codes.push("7".to_string());
+        }
+        sgr(&codes)
+    }
+
+    /// Returns the escape sequences that turn the attributes of this style off again.
+    pub(crate) fn close(&self) -> String {
+        let mut codes = Vec::new();
+        if self.foreground.is_some() {
+            codes.push("39".to_string());
+        }
+        if self.background.is_some() {
+            codes.push("49".to_string());
+        }
+        if self.bold || self.dim {
+            codes.push("22".to_string());
+        }
+        if self.underline {
+            codes.push("24".to_string());
+        }
+        if self.inverse {
+            codes.push("27".to_string());
+        }
+        sgr(&codes)
+    }
+}
+
+fn sgr(codes: &[String]) -> String {
+    if codes.is_empty() {
+        String::new()
+    } else {
+        format!("\x1b[{}m", codes.join(";"))
+    }
+}
+
+/// Inserts the escape sequences of each style around its byte range of `text`.
+///
+/// Ranges are clamped to `text` and moved back to the nearest character boundary, and empty
+/// ranges are ignored. Where ranges overlap, each attribute ends with the first range using it
+/// to end. Styles spanning several lines are closed before each `\n` and opened again after it,
+/// so they never leak into the border drawn around each line.
+pub(crate) fn apply_highlights(text: &str, highlights: &[(Range<usize>, Style)]) -> String {
+    let boundary = |mut index: usize| {
+        index = index.min(text.len());
+        while !text.is_char_boundary(index) {
+            index -= 1;
+        }
+        index
+    };
+
+    // Each edge is (position, is_start, style). Ends sort before starts at the same position so
+    // adjacent ranges do not cancel each other.
+    let mut edges: Vec<(usize, bool, &Style)> = Vec::new();
+    for (range, style) in highlights {
+        let (start, end) = (boundary(range.start), boundary(range.end));
+        if start < end {
+            edges.push((start, true, style));
+            edges.push((end, false, style));
+        }
+    }
+    edges.sort_by_key(|&(position, is_start, _)| (position, is_start));
+
+    let mut highlighted = String::with_capacity(text.len());
+    let mut open: Vec<&Style> = Vec::new();
+    let push_text = |highlighted: &mut String, open: &[&Style], segment: &str| {
+        let mut lines = segment.split('\n');
+        let mut line = lines.next().unwrap_or_default();
+        for next in lines {
+            let (text, ending) = match line.strip_suffix('\r') {
+                Some(text) => (text, "\r\n"),
+                None => (line, "\n"),
+            };
+            highlighted.push_str(text);
+            for style in open.iter().rev() {
+                highlighted.push_str(&style.close());
+            }
+            highlighted.push_str(ending);
+            for style in open {
+                highlighted.push_str(&style.open());
+            }
+            line = next;
+        }
+        highlighted.push_str(line);
+    };
+
+    let mut last = 0;
+    for (position, is_start, style) in edges {
+        push_text(&mut highlighted, &open, &text[last..position]);
+        if is_start {
+            highlighted.push_str(&style.open());
+            open.push(style);
+        } else {
+            highlighted.push_str(&style.close());
+            if let Some(index) = open.iter().position(|&o| std::ptr::eq(o, style)) {
+                open.remove(index);
+            }
+        }
+        last = position;
+    }
+    push_text(&mut highlighted, &open, &text[last..]);
+
+    highlighted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_highlights, Style};
+
+    const BOLD: Style = Style {
+        foreground: None,
+        background: None,
+        bold: true,
+        dim: false,
+        underline: false,
+        inverse: false,
+    };
+
+    const UNDERLINE: Style = Style {
+        bold: false,
+        underline: true,
+        ..BOLD
+    };
+
+    #[test]
+    fn styles_are_closed_at_line_breaks() {
+        assert_eq!(
+            apply_highlights("ab\ncd\r\nef", &[(1..8, BOLD)]),
+            "a\x1b[1mb\x1b[22m\n\x1b[1mcd\x1b[22m\r\n\x1b[1me\x1b[22mf"
+        );
+    }
+
+    #[test]
+    fn overlapping_styles_are_reopened_in_order() {
+        assert_eq!(
+            apply_highlights("ab\ncd", &[(0..5, BOLD), (1..4, UNDERLINE)]),
+            "\x1b[1ma\x1b[4mb\x1b[24m\x1b[22m\n\x1b[1m\x1b[4mc\x1b[24md\x1b[22m"
+        );
+    }
+
+    #[test]
+    fn ranges_are_clamped_to_character_boundaries() {
+        assert_eq!(apply_highlights("é", &[(0..1, BOLD)]), "é");
+        assert_eq!(apply_highlights("éa", &[(1..8, BOLD)]), "\x1b[1méa\x1b[22m");
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn highlights_do_not_leak_into_the_border() {
+        use crate::{create_text_border, ColorMode, TextBorderOptions};
+
+        let options = TextBorderOptions {
+            color_mode: ColorMode::Always,
+            highlights: vec![(0..4, BOLD)],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            create_text_border("ab\ncd", Some(options)),
+            "****\n*\x1b[1mab\x1b[22m*\n*\x1b[1mc\x1b[22md*\n****"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use std::sync::OnceLock;
+
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::{as_24_bit_terminal_escaped, LinesWithEndings};
+
+use crate::{create_text_border_from_lines, TextBorderOptions};
+
+/// The theme code is highlighted with.
+const THEME: &str = "base16-ocean.dark";
+
+/// Returns the syntax definitions and the theme, loading them on first use.
+fn highlighting() -> &'static (SyntaxSet, Theme) {
+    static HIGHLIGHTING: OnceLock<(SyntaxSet, Theme)> = OnceLock::new();
+
+    HIGHLIGHTING.get_or_init(|| {
+        let mut themes = ThemeSet::load_defaults().themes;
+        let theme = themes
+            .remove(THEME)
+            .expect("default themes include base16-ocean.dark");
+        (SyntaxSet::load_defaults_newlines(), theme)
+    })
+}
+
+/// Creates a string containing `code`, highlighted with syntect as the language named by
+/// `language`, surrounded by a border and margin as specified by the provided
+/// `TextBorderOptions`.
+///
+/// Highlighting uses 24-bit foreground colors, which take up no width. Colors are left out
+/// when the options do not write colors, and code in an unknown language is framed as plain
+/// text. This function is available with the `syntax` feature.
+///
+/// # Arguments
+///
+/// * `code` - The code to be highlighted and surrounded by a border.
+/// * `language` - A hint at the language of the code: a name such as `"Rust"` or a file
+///   extension such as `"rs"`.
+/// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+///   configurations. If `None`, the options returned by `default_options` are used.
+///
+/// # Returns
+///
+/// * A `String` containing the highlighted code surrounded by the specified border and margin.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_highlighted_border, strip_ansi_codes, ColorMode, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     color_mode: ColorMode::Always,
+///     ..Default::default()
+/// };
+/// let boxed = create_highlighted_border("let x = 1;", "rs", Some(options));
+///
+/// assert!(boxed.contains("\x1b[38;2;"));
+/// assert_eq!(strip_ansi_codes(&boxed), "************\n*let x = 1;*\n************");
+/// ```
+pub fn create_highlighted_border(
+    code: &str,
+    language: &str,
+    options: Option<TextBorderOptions>,
+) -> String {
+    let (syntaxes, theme) = highlighting();
+    let syntax = syntaxes
+        .find_syntax_by_token(language)
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    let lines: Vec<String> = LinesWithEndings::from(code)
+        .map(|line| {
+            let plain = line.trim_end_matches(['\n', '\r']);
+            match highlighter.highlight_line(line, syntaxes) {
+                Ok(ranges) => {
+                    let escaped = as_24_bit_terminal_escaped(&ranges, false);
+                    format!("{}\x1b[39m", escaped.trim_end_matches(['\n', '\r']))
+                }
+                Err(_) => plain.to_string(),
+            }
+        })
+        .collect();
+
+    create_text_border_from_lines(lines, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_text_border, strip_ansi_codes, ColorMode};
+
+    fn colored() -> Option<TextBorderOptions> {
+        Some(TextBorderOptions {
+            color_mode: ColorMode::Always,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn colors_are_left_out_when_the_options_write_none() {
+        let options = TextBorderOptions {
+            color_mode: ColorMode::Never,
+            ..Default::default()
+        };
+        assert_eq!(
+            create_highlighted_border("fn f() {}", "rs", Some(options)),
+            "***********\n*fn f() {}*\n***********"
+        );
+    }
+
+    #[test]
+    fn line_endings_are_kept_out_of_the_box() {
+        let boxed = create_highlighted_border("a = 1\r\nb = 2\n", "py", colored());
+        assert_eq!(
+            strip_ansi_codes(&boxed),
+            create_text_border("a = 1\nb = 2", None)
+        );
+    }
+
+    #[test]
+    fn unknown_languages_are_framed_as_plain_text() {
+        let boxed = create_highlighted_border("x", "no such language", colored());
+        assert_eq!(strip_ansi_codes(&boxed), "***\n*x*\n***");
+    }
+}
@@ -0,0 +1,738 @@
+use std::cmp::Ordering;
+#[cfg(feature = "csv")]
+use std::io::Read;
+
+#[cfg(feature = "csv")]
+use csv::ReaderBuilder;
+
+use crate::document::{Alignment, Region};
+use crate::render::Renderer;
+use crate::width::{str_width, with_ambiguous_width};
+use crate::{default_options, TextBorderOptions, MAX_CONTENT_WIDTH};
+
+/// `ColumnAlignment` is how the cells of a `Table` column are placed across its width.
+///
+/// # Variants
+///
+/// * `Left` - Cells start at the left of the column. This is the default.
+/// * `Center` - Cells are centered, with any odd column of space on the right.
+/// * `Right` - Cells end at the right of the column.
+/// * `Decimal` - Cells are lined up on their decimal point, such as for prices. Cells without
+///   a point are lined up as if it followed them. The header is aligned right.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColumnAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+    Decimal,
+}
+
+/// `Table` is rows of cells laid out in columns inside a box, with an optional header row
+/// separated from the rows by a rule.
+///
+/// Each column is as wide as its widest cell, measured in display columns, so tables of CJK
+/// text or emoji line up.
+///
+/// # Fields
+///
+/// * `header` - The cells of the header row. An empty header is left out, with its rule.
+/// * `rows` - The rows of cells. Rows shorter than others are padded with empty cells.
+/// * `alignments` - The `ColumnAlignment` of each column, in order. Columns without one are
+///   aligned left.
+/// * `column_gap` - The number of spaces between columns, capped to `MAX_CONTENT_WIDTH`.
+/// * `decimal_separator` - The character separating the integer part of a number from its
+///   fraction in `ColumnAlignment::Decimal` columns, such as `,` for many European locales.
+///   Thousands separators, such as the `.` of `1.234,5`, are part of the integer part.
+/// * `max_rows` - The optional maximum number of rows shown. Rows past it are left out and
+///   counted in a last line, such as `… and 37 more`.
+/// * `sort_by` - The optional index of the column the rows are sorted by, before they are
+///   limited to `max_rows`. Numbers are compared as numbers and sort before text, which is
+///   compared as text. Missing cells sort as empty text.
+/// * `sort_descending` - A boolean flag indicating whether rows are sorted from the largest
+///   cell down, such as for top-N reports.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{BorderStyle, ColumnAlignment, Table, TextBorderOptions};
+///
+/// let table = Table {
+///     header: vec!["item".to_string(), "qty".to_string(), "price".to_string()],
+///     rows: vec![
+///         vec!["tea".to_string(), "2".to_string(), "3.5".to_string()],
+///         vec!["cake".to_string(), "12".to_string(), "12.25".to_string()],
+///     ],
+///     alignments: vec![
+///         ColumnAlignment::Left,
+///         ColumnAlignment::Right,
+///         ColumnAlignment::Decimal,
+///     ],
+///     ..Default::default()
+/// };
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     table.render(Some(options)),
+///     "┌────────────────┐\n\
+///      │item  qty  price│\n\
+///      ├────────────────┤\n\
+///      │tea     2   3.5 │\n\
+///      │cake   12  12.25│\n\
+///      └────────────────┘"
+/// );
+/// ```
+///
+/// The three largest files, sorted by size:
+///
+/// ```
+/// use unicode_border::{ColumnAlignment, Table};
+///
+/// let table = Table {
+///     header: vec!["file".to_string(), "size".to_string()],
+///     rows: (1..=40)
+///         .map(|i| vec![format!("log{}.txt", i), (i * 7 % 41).to_string()])
+///         .collect(),
+///     alignments: vec![ColumnAlignment::Left, ColumnAlignment::Right],
+///     sort_by: Some(1),
+///     sort_descending: true,
+///     max_rows: Some(3),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     table.render(None),
+///     "*****************\n\
+///      *file       size*\n\
+///      *****************\n\
+///      *log35.txt    40*\n\
+///      *log29.txt    39*\n\
+///      *log23.txt    38*\n\
+///      *… and 37 more  *\n\
+///      *****************"
+/// );
+/// ```
+///
+/// With a custom character set, whose tees join the header rule to the sides:
+///
+/// ```
+/// use unicode_border::{BorderChars, BorderStyle, Table, TextBorderOptions};
+///
+/// let table = Table {
+///     header: vec!["a".to_string(), "b".to_string()],
+///     rows: vec![vec!["1".to_string(), "2".to_string()]],
+///     ..Default::default()
+/// };
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Custom(BorderChars {
+///         tee_left: '╞',
+///         tee_right: '╡',
+///         ..Default::default()
+///     }),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     table.render(Some(options)),
+///     "┌────┐\n│a  b│\n╞────╡\n│1  2│\n└────┘"
+/// );
+/// ```
+///
+/// With a comma before the fraction and dots between thousands:
+///
+/// ```
+/// use unicode_border::{ColumnAlignment, Table};
+///
+/// let table = Table {
+///     rows: vec![
+///         vec!["rent".to_string(), "1.250,00".to_string()],
+///         vec!["coffee".to_string(), "3,5".to_string()],
+///         vec!["total".to_string(), "1.253,5".to_string()],
+///     ],
+///     alignments: vec![ColumnAlignment::Left, ColumnAlignment::Decimal],
+///     decimal_separator: ',',
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     table.render(None),
+///     "******************\n\
+///      *rent    1.250,00*\n\
+///      *coffee      3,5 *\n\
+///      *total   1.253,5 *\n\
+///      ******************"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Table {
+    pub header: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    pub alignments: Vec<ColumnAlignment>,
+    pub column_gap: usize,
+    pub decimal_separator: char,
+    pub max_rows: Option<usize>,
+    pub sort_by: Option<usize>,
+    pub sort_descending: bool,
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Table {
+            header: Vec::new(),
+            rows: Vec::new(),
+            alignments: Vec::new(),
+            column_gap: 2,
+            decimal_separator: '.',
+            max_rows: None,
+            sort_by: None,
+            sort_descending: false,
+        }
+    }
+}
+
+impl Table {
+    /// Renders the table inside a border.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+    ///   configurations. If `None`, the options returned by `default_options` are used. Lines
+    ///   are never trimmed, so the columns stay lined up.
+    ///
+    /// # Returns
+    ///
+    /// * A `String` containing the table surrounded by the specified border and margin.
+    pub fn render(&self, options: Option<TextBorderOptions>) -> String {
+        let mut options = options.unwrap_or_else(default_options);
+        options.prevent_trim = true;
+
+        with_ambiguous_width(options.ambiguous_width, || {
+            let (header, rows) = self.lines();
+            let region = |lines: Vec<String>| Region {
+                lines,
+                alignment: Alignment::Left,
+                style: None,
+            };
+            Renderer::new(options)
+                .render_regions(&[&region(header), &region(rows)])
+                .join_lines()
+        })
+    }
+
+    /// Parses CSV into a table, taking the first record as the header.
+    ///
+    /// Records may have different numbers of fields. Columns in which every cell that is not
+    /// empty is a number, with a `.` decimal point and optionally `,` or space thousands
+    /// separators, are aligned on their decimal point, and other columns left. This
+    /// constructor is available with the `csv` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The source of the CSV, such as a file or standard input.
+    ///
+    /// # Errors
+    ///
+    /// * `csv::Error` - The input could not be read, or is not valid CSV.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_border::Table;
+    ///
+    /// let table = Table::from_csv("name,size\nCargo.toml,512\nREADME.md,\"1,024\"\n".as_bytes())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     table.render(None),
+    ///     "*******************\n\
+    ///      *name         size*\n\
+    ///      *******************\n\
+    ///      *Cargo.toml    512*\n\
+    ///      *README.md   1,024*\n\
+    ///      *******************"
+    /// );
+    /// ```
+    #[cfg(feature = "csv")]
+    pub fn from_csv<R: Read>(reader: R) -> Result<Table, csv::Error> {
+        let mut reader = ReaderBuilder::new().flexible(true).from_reader(reader);
+        let header: Vec<String> = reader.headers()?.iter().map(str::to_string).collect();
+        let rows = reader
+            .records()
+            .map(|record| Ok(record?.iter().map(str::to_string).collect()))
+            .collect::<Result<Vec<Vec<String>>, csv::Error>>()?;
+
+        let count = rows
+            .iter()
+            .map(Vec::len)
+            .chain([header.len()])
+            .max()
+            .unwrap_or(0);
+        let alignments = (0..count)
+            .map(|column| {
+                let mut cells = rows
+                    .iter()
+                    .filter_map(|row| row.get(column))
+                    .filter(|cell| !cell.trim().is_empty())
+                    .peekable();
+                let numeric = cells.peek().is_some() && cells.all(|cell| is_number(cell));
+                if numeric {
+                    ColumnAlignment::Decimal
+                } else {
+                    ColumnAlignment::Left
+                }
+            })
+            .collect();
+
+        Ok(Table {
+            header,
+            rows,
+            alignments,
+            ..Default::default()
+        })
+    }
+
+    /// Returns the header row and the other rows laid out in columns, one line each.
+    fn lines(&self) -> (Vec<String>, Vec<String>) {
+        let clean = |cell: &String| cell.replace(['\n', '\r'], " ");
+        let header: Vec<String> = self.header.iter().map(clean).collect();
+        let mut sorted: Vec<&Vec<String>> = self.rows.iter().collect();
+        if let Some(column) = self.sort_by {
+            let separator = self.decimal_separator;
+            sorted.sort_by(|a, b| {
+                // Numbers sort before text, so every pair of cells compares consistently.
+                let keys = (
+                    sort_key(a, column, separator),
+                    sort_key(b, column, separator),
+                );
+                let ordering = match keys {
+                    ((Some(x), _), (Some(y), _)) => x.total_cmp(&y),
+                    ((Some(_), _), (None, _)) => Ordering::Less,
+                    ((None, _), (Some(_), _)) => Ordering::Greater,
+                    ((None, x), (None, y)) => x.cmp(y),
+                };
+                if self.sort_descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+        let hidden = match self.max_rows {
+            Some(max_rows) if max_rows < sorted.len() => sorted.split_off(max_rows).len(),
+            _ => 0,
+        };
+        let rows: Vec<Vec<String>> = sorted
+            .iter()
+            .map(|row| row.iter().map(clean).collect())
+            .collect();
+
+        let count = rows
+            .iter()
+            .map(Vec::len)
+            .chain([header.len()])
+            .max()
+            .unwrap_or(0);
+        let alignment = |column: usize| {
+            self.alignments
+                .get(column)
+                .copied()
+                .unwrap_or(ColumnAlignment::Left)
+        };
+        let cell = |row: &[String], column: usize| row.get(column).cloned().unwrap_or_default();
+
+        // Decimal columns line up the integer parts right and the fractions left.
+        let split = |cell: &str| {
+            let point = cell.find(self.decimal_separator).unwrap_or(cell.len());
+            (str_width(&cell[..point]), str_width(&cell[point..]))
+        };
+        let decimals: Vec<(usize, usize)> = (0..count)
+            .map(|column| {
+                rows.iter()
+                    .map(|row| split(&cell(row, column)))
+                    .fold((0, 0), |(a, b), (c, d)| (a.max(c), b.max(d)))
+            })
+            .collect();
+
+        let format = |row: &[String], column: usize, is_header: bool| {
+            let text = cell(row, column);
+            match alignment(column) {
+                ColumnAlignment::Decimal if !is_header => {
+                    let (integer, fraction) = split(&text);
+                    let (integer_width, fraction_width) = decimals[column];
+                    format!(
+                        "{}{}{}",
+                        " ".repeat(integer_width - integer),
+                        text,
+                        " ".repeat(fraction_width - fraction)
+                    )
+                }
+                _ => text,
+            }
+        };
+        let widths: Vec<usize> = (0..count)
+            .map(|column| {
+                rows.iter()
+                    .map(|row| str_width(&format(row, column, false)))
+                    .chain([str_width(&format(&header, column, true))])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let column_gap = " ".repeat(self.column_gap.min(MAX_CONTENT_WIDTH));
+        let line = |row: &[String], is_header: bool| {
+            let mut line = String::new();
+            for (column, &width) in widths.iter().enumerate() {
+                if column > 0 {
+                    line.push_str(&column_gap);
+                }
+                let text = format(row, column, is_header);
+                let gap = width - str_width(&text);
+                let left = match alignment(column) {
+                    ColumnAlignment::Left => 0,
+                    ColumnAlignment::Center => gap / 2,
+                    ColumnAlignment::Right | ColumnAlignment::Decimal => gap,
+                };
+                line.push_str(&" ".repeat(left));
+                line.push_str(&text);
+                line.push_str(&" ".repeat(gap - left));
+            }
+            line
+        };
+
+        let header = if header.is_empty() {
+            Vec::new()
+        } else {
+            vec![line(&header, true)]
+        };
+        let mut rows: Vec<String> = rows.iter().map(|row| line(row, false)).collect();
+        if hidden > 0 {
+            rows.push(format!("… and {} more", hidden));
+        }
+        (header, rows)
+    }
+}
+
+/// Returns the cell of `row` in `column`, with its value if it is a number, to sort the row by.
+fn sort_key(row: &[String], column: usize, decimal_separator: char) -> (Option<f64>, &str) {
+    let cell = row.get(column).map_or("", String::as_str);
+    (number(cell, decimal_separator), cell)
+}
+
+/// Returns whether `cell` is a number, such as `-1,234.5`, with a `.` decimal point.
+#[cfg(feature = "csv")]
+fn is_number(cell: &str) -> bool {
+    number(cell, '.').is_some()
+}
+
+/// Parses `cell` as a number, such as `-1,234.5` or `1.234,5`, with `decimal_separator`
+/// before its fraction and any other of `,`, `.`, `'`, `_` and spaces separating thousands.
+fn number(cell: &str, decimal_separator: char) -> Option<f64> {
+    let digits: String = cell
+        .trim()
+        .chars()
+        .filter(|&c| {
+            c == decimal_separator
+                || !matches!(c, ',' | '.' | '\'' | '_' | ' ' | '\u{a0}' | '\u{202f}')
+        })
+        .map(|c| if c == decimal_separator { '.' } else { c })
+        .collect();
+    // `f64` also parses `nan`, `inf` and `infinity`, which are words rather than numbers.
+    if !digits.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    digits
+        .parse()
+        .ok()
+        .filter(|number: &f64| number.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::catch_unwind;
+
+    use super::ColumnAlignment;
+    use super::{number, Table};
+    use crate::MAX_CONTENT_WIDTH;
+
+    fn column(table: &Table) -> Vec<String> {
+        let (_, rows) = table.lines();
+        rows.iter().map(|row| row.trim_end().to_string()).collect()
+    }
+
+    #[test]
+    fn sorting_mixed_cells_does_not_panic() {
+        let rows: Vec<Vec<String>> = (0..500)
+            .map(|i| match i % 3 {
+                0 => vec![format!("{}x", i % 97)],
+                _ => vec![(i % 89).to_string()],
+            })
+            .collect();
+
+        for &sort_descending in &[false, true] {
+            let table = Table {
+                rows: rows.clone(),
+                sort_by: Some(0),
+                sort_descending,
+                ..Default::default()
+            };
+            let sorted = catch_unwind(|| column(&table)).expect("sorting panicked");
+
+            let first_text = sorted.iter().position(|cell| cell.ends_with('x'));
+            let last_number = sorted.iter().rposition(|cell| !cell.ends_with('x'));
+            if sort_descending {
+                assert!(first_text < last_number);
+            } else {
+                assert!(last_number < first_text);
+            }
+        }
+    }
+
+    #[test]
+    fn large_sorted_tables_do_not_panic() {
+        let table = Table {
+            header: vec!["n".to_string(), "name".to_string()],
+            rows: (0..5000)
+                .map(|i| vec![(i * 7919 % 5000).to_string(), format!("row {}", i)])
+                .collect(),
+            max_rows: Some(usize::MAX),
+            sort_by: Some(0),
+            ..Default::default()
+        };
+        let sorted = catch_unwind(|| column(&table)).expect("sorting panicked");
+        assert_eq!(sorted.len(), 5000);
+        assert_eq!(sorted[0], "0     row 0");
+
+        let table = Table {
+            sort_by: Some(usize::MAX),
+            max_rows: Some(0),
+            ..table
+        };
+        let rendered = catch_unwind(|| table.render(None)).expect("rendering panicked");
+        assert!(rendered.contains("… and 5000 more"));
+    }
+
+    #[test]
+    fn huge_column_gap_is_capped() {
+        let table = Table {
+            rows: vec![vec!["a".to_string(), "b".to_string()]],
+            column_gap: usize::MAX,
+            ..Default::default()
+        };
+        let rows = catch_unwind(|| column(&table)).expect("rendering panicked");
+        assert_eq!(rows, [format!("a{}b", " ".repeat(MAX_CONTENT_WIDTH))]);
+    }
+
+    #[test]
+    fn numbers_sort_before_text_and_missing_cells() {
+        let table = Table {
+            rows: vec![
+                vec!["b".to_string()],
+                vec!["10".to_string()],
+                vec![],
+                vec!["9".to_string()],
+                vec!["5x".to_string()],
+                vec!["nan".to_string()],
+            ],
+            sort_by: Some(0),
+            ..Default::default()
+        };
+
+        assert_eq!(column(&table), ["9", "10", "", "5x", "b", "nan"]);
+    }
+
+    #[test]
+    fn words_parsed_by_f64_are_not_numbers() {
+        for word in &[
+            "nan",
+            "NaN",
+            "inf",
+            "-inf",
+            "infinity",
+            "+Infinity",
+            "1e999",
+            "",
+        ] {
+            assert_eq!(number(word, '.'), None, "{:?}", word);
+        }
+        assert_eq!(number("1,234.5", '.'), Some(1234.5));
+        assert_eq!(number("1.234,5", ','), Some(1234.5));
+        assert_eq!(number("-2e3", '.'), Some(-2000.0));
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_columns_of_numbers_are_aligned_on_the_decimal_point() {
+        let table = Table::from_csv("a,b,c,d\n1.5,x,,1\n,2,,\n\"1,024\",3,,\n".as_bytes()).unwrap();
+
+        assert_eq!(
+            table.alignments,
+            [
+                ColumnAlignment::Decimal,
+                ColumnAlignment::Left,
+                ColumnAlignment::Left,
+                ColumnAlignment::Decimal,
+            ]
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_records_may_have_different_lengths() {
+        let table = Table::from_csv("a\n1,2,3\n\n4\n".as_bytes()).unwrap();
+
+        assert_eq!(table.header, ["a"]);
+        assert_eq!(table.rows, [vec!["1", "2", "3"], vec!["4"]]);
+        assert_eq!(table.alignments.len(), 3);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_without_records_is_an_empty_table() {
+        let table = Table::from_csv("".as_bytes()).unwrap();
+        assert!(table.header.is_empty() && table.rows.is_empty());
+
+        let table = Table::from_csv("a,b\n".as_bytes()).unwrap();
+        assert_eq!(table.header, ["a", "b"]);
+        assert!(table.rows.is_empty());
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_errors_are_returned() {
+        let error = Table::from_csv(&b"a\n\xff\n"[..]).unwrap_err();
+        assert!(matches!(error.kind(), csv::ErrorKind::Utf8 { .. }));
+
+        let error = Table::from_csv(&b"\xff,a\n"[..]).unwrap_err();
+        assert!(matches!(error.kind(), csv::ErrorKind::Utf8 { .. }));
+
+        struct Failing;
+        impl std::io::Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("unreadable"))
+            }
+        }
+        let error = Table::from_csv(Failing).unwrap_err();
+        assert!(error.is_io_error());
+    }
+
+    fn cells(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn columns_are_aligned_and_padded() {
+        let table = Table {
+            header: vec!["l".to_string(), "c".to_string(), "r".to_string()],
+            rows: cells(&[&["aaa", "b", "c"], &["a", "bbb", "ccc"]]),
+            alignments: vec![
+                ColumnAlignment::Left,
+                ColumnAlignment::Center,
+                ColumnAlignment::Right,
+            ],
+            column_gap: 1,
+            ..Default::default()
+        };
+        let (header, rows) = table.lines();
+
+        assert_eq!(header, ["l    c    r"]);
+        assert_eq!(rows, ["aaa  b    c", "a   bbb ccc"]);
+    }
+
+    #[test]
+    fn ragged_rows_and_line_breaks_in_cells_are_tidied() {
+        let table = Table {
+            rows: cells(&[&["a\nb", "c"], &["d"]]),
+            ..Default::default()
+        };
+        let (header, rows) = table.lines();
+
+        assert!(header.is_empty());
+        assert_eq!(rows, ["a b  c", "d     "]);
+    }
+
+    #[test]
+    fn an_empty_table_still_renders_a_box() {
+        assert_eq!(Table::default().render(None), "**\n**");
+    }
+
+    #[test]
+    fn thousands_separators_are_recognized() {
+        assert_eq!(number("1 234 567", '.'), Some(1234567.0));
+        assert_eq!(number("1\u{a0}234,5", ','), Some(1234.5));
+        assert_eq!(number("1'234.5", '.'), Some(1234.5));
+        assert_eq!(number("1_000", '.'), Some(1000.0));
+        assert_eq!(number(" 12 ", '.'), Some(12.0));
+        assert_eq!(number("1.2.3", ','), Some(123.0));
+        assert_eq!(number("1,2,3", ','), None);
+        assert_eq!(number("12 apples", '.'), None);
+    }
+
+    #[test]
+    fn decimal_columns_align_on_the_configured_separator() {
+        let table = Table {
+            rows: cells(&[&["1,5"], &["12,25"], &["3"]]),
+            alignments: vec![ColumnAlignment::Decimal],
+            decimal_separator: ',',
+            ..Default::default()
+        };
+
+        assert_eq!(table.lines().1, [" 1,5 ", "12,25", " 3   "]);
+    }
+
+    #[test]
+    fn rows_beyond_max_rows_are_counted() {
+        let table = Table {
+            rows: cells(&[&["3"], &["1"], &["2"]]),
+            max_rows: Some(1),
+            sort_by: Some(0),
+            sort_descending: true,
+            ..Default::default()
+        };
+        assert_eq!(column(&table), ["3", "… and 2 more"]);
+
+        let table = Table {
+            max_rows: Some(3),
+            ..table
+        };
+        assert_eq!(column(&table), ["3", "2", "1"]);
+    }
+
+    #[test]
+    fn sorting_is_stable_and_by_a_missing_column_keeps_the_order() {
+        let table = Table {
+            rows: cells(&[&["1", "b"], &["0", "a"], &["1", "a"]]),
+            sort_by: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(column(&table), ["0  a", "1  b", "1  a"]);
+
+        let table = Table {
+            sort_by: Some(5),
+            ..table
+        };
+        assert_eq!(column(&table), ["1  b", "0  a", "1  a"]);
+    }
+
+    #[test]
+    fn the_ambiguous_width_of_the_options_is_used() {
+        let table = Table {
+            header: vec!["a".to_string(), "b".to_string()],
+            rows: cells(&[&["±±", "x"]]),
+            ..Default::default()
+        };
+        let options = crate::TextBorderOptions {
+            ambiguous_width: crate::AmbiguousWidth::Wide,
+            ..Default::default()
+        };
+        assert_eq!(
+            table.render(Some(options)),
+            "*********\n*a     b*\n*********\n*±±  x*\n*********"
+        );
+    }
+}
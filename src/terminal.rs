@@ -0,0 +1,403 @@
+use std::env;
+use std::io::{self, IsTerminal};
+
+/// `ColorSupport` describes which colors a terminal can display.
+///
+/// # Variants
+///
+/// * `Basic` - The 16 standard ANSI colors.
+/// * `Ansi256` - The 256-color palette.
+/// * `TrueColor` - 24-bit RGB colors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorSupport {
+    Basic,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Guesses the color support of the terminal from the environment.
+    ///
+    /// `COLORTERM=truecolor` (or `24bit`) and Windows Terminal report `TrueColor`, a `TERM`
+    /// ending in `256color` reports `Ansi256`, and anything else is assumed to be `Basic`.
+    pub fn detect() -> ColorSupport {
+        let colorterm = env::var("COLORTERM").unwrap_or_default();
+        if colorterm.eq_ignore_ascii_case("truecolor")
+            || colorterm.eq_ignore_ascii_case("24bit")
+            || env::var_os("WT_SESSION").is_some()
+        {
+            return ColorSupport::TrueColor;
+        }
+
+        if env::var("TERM").is_ok_and(|term| term.ends_with("256color")) {
+            return ColorSupport::Ansi256;
+        }
+
+        ColorSupport::Basic
+    }
+}
+
+/// `ColorMode` controls whether colors and other escape sequences are written.
+///
+/// # Variants
+///
+/// * `Auto` - Write colors unless the `NO_COLOR` environment variable is set to a non-empty
+///   value or standard output is not a terminal, e.g. when it is piped to a file.
+/// * `Always` - Always write colors.
+/// * `Never` - Never write colors, and strip any escape sequences already in the message.
+///
+/// Colors are written with the `color` feature only. Without it, every mode behaves like
+/// `Never`, so the options keep the same fields whichever features are enabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColorMode {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    /// Returns whether colors should be written in this mode. Without the `color` feature,
+    /// colors are never written.
+    pub fn enabled(self) -> bool {
+        if !cfg!(feature = "color") {
+            return false;
+        }
+        match self {
+            ColorMode::Auto => {
+                env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                    && io::stdout().is_terminal()
+            }
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+        }
+    }
+}
+
+/// `Charset` selects the characters borders may be drawn with.
+///
+/// # Variants
+///
+/// * `Unicode` - Draw borders with any characters. This is the default, so output does not
+///   change with the environment unless asked to.
+/// * `Ascii` - Downgrade box-drawing and other Unicode border characters to `+`, `-` and `|`.
+/// * `Cp437` - Keep to the line-drawing and block characters of code page 437, which legacy
+///   Windows consoles can display: heavy, dashed and rounded lines are drawn light, and
+///   characters with no equivalent fall back to ASCII.
+/// * `DecGraphics` - Draw lines with the DEC Special Graphics character set of the VT100,
+///   switching to it with `ESC ( 0` and back with `ESC ( B` around each run of border
+///   characters, for serial consoles and old terminals without UTF-8. Double and heavy lines are
+///   drawn light, and characters with no equivalent fall back to ASCII.
+/// * `Auto` - Use `Ascii` or `Cp437` when the output target likely cannot display Unicode, as
+///   guessed by `Charset::detect`, so one code path works everywhere.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Charset {
+    #[default]
+    Unicode,
+    Ascii,
+    Cp437,
+    DecGraphics,
+    Auto,
+}
+
+impl Charset {
+    /// Guesses whether the terminal can display Unicode from the environment, returning
+    /// `Unicode`, `Ascii` or `Cp437`.
+    ///
+    /// A `TERM` of `dumb` reports `Ascii`. On Windows, Windows Terminal and terminals setting
+    /// `TERM_PROGRAM` report `Unicode`, and the legacy console `Cp437`. Elsewhere, the first of
+    /// `LC_ALL`, `LC_CTYPE` and `LANG` that is set decides: a UTF-8 locale reports `Unicode`,
+    /// and any other locale, or none, `Ascii`.
+    pub fn detect() -> Charset {
+        let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+
+        if var("TERM").is_some_and(|term| term == "dumb") {
+            return Charset::Ascii;
+        }
+
+        if cfg!(windows) {
+            return if var("WT_SESSION").is_some() || var("TERM_PROGRAM").is_some() {
+                Charset::Unicode
+            } else {
+                Charset::Cp437
+            };
+        }
+
+        let locale = var("LC_ALL")
+            .or_else(|| var("LC_CTYPE"))
+            .or_else(|| var("LANG"))
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if locale.contains("utf-8") || locale.contains("utf8") {
+            Charset::Unicode
+        } else {
+            Charset::Ascii
+        }
+    }
+
+    /// Returns this charset, detecting it with `Charset::detect` if it is `Auto`.
+    pub(crate) fn resolve(self) -> Charset {
+        match self {
+            Charset::Auto => Charset::detect(),
+            charset => charset,
+        }
+    }
+
+    /// Returns the character standing in for the border character `c` with this charset.
+    pub(crate) fn fallback(self, c: char) -> char {
+        match self {
+            Charset::Unicode | Charset::Auto => c,
+            Charset::Ascii => ascii_fallback(c),
+            Charset::Cp437 => cp437_fallback(c),
+            Charset::DecGraphics => dec_fallback(c),
+        }
+    }
+
+    /// Returns `border` as written to the terminal with this charset. For `DecGraphics`, runs of
+    /// line-drawing characters are switched to the DEC Special Graphics set and encoded.
+    pub(crate) fn encode(self, border: String) -> String {
+        if self != Charset::DecGraphics {
+            return border;
+        }
+
+        let mut encoded = String::with_capacity(border.len());
+        let mut graphics = false;
+        for c in border.chars() {
+            let code = dec_code(c);
+            if code.is_some() != graphics {
+                graphics = code.is_some();
+                encoded.push_str(if graphics { "\x1b(0" } else { "\x1b(B" });
+            }
+            encoded.push(code.unwrap_or(c));
+        }
+        if graphics {
+            encoded.push_str("\x1b(B");
+        }
+        encoded
+    }
+}
+
+/// Returns the ASCII character standing in for the border character `c`: `-` for horizontal
+/// lines, `|` for vertical lines, `+` for corners and joints, `#` for blocks and `.` for
+/// Braille dots. Other characters are returned as they are.
+fn ascii_fallback(c: char) -> char {
+    match c {
+        '─' | '━' | '═' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' | '╴' | '╶' | '╸' | '╺' => {
+            '-'
+        }
+        '│' | '┃' | '║' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' | '╵' | '╷' | '╹' | '╻' => {
+            '|'
+        }
+        '\u{2500}'..='\u{257f}' | '⌜' | '⌝' | '⌞' | '⌟' => '+',
+        '\u{2580}'..='\u{259f}' => '#',
+        '\u{2800}'..='\u{28ff}' => '.',
+        c => c,
+    }
+}
+
+/// Returns the character of code page 437 standing in for the border character `c`, or its
+/// ASCII stand-in if code page 437 has no equivalent.
+pub(crate) fn cp437_fallback(c: char) -> char {
+    match c {
+        '─' | '│' | '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' => c,
+        '\u{2550}'..='\u{256c}' => c,
+        '░' | '▒' | '▓' | '█' | '▀' | '▄' | '▌' | '▐' => c,
+        '━' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' | '╴' | '╶' | '╸' | '╺' => {
+            '─'
+        }
+        '┃' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' | '╵' | '╷' | '╹' | '╻' => {
+            '│'
+        }
+        '┍' | '┎' | '┏' | '╭' | '⌜' => '┌',
+        '┑' | '┒' | '┓' | '╮' | '⌝' => '┐',
+        '┕' | '┖' | '┗' | '╰' | '⌞' => '└',
+        '┙' | '┚' | '┛' | '╯' | '⌟' => '┘',
+        '┝'..='┣' => '├',
+        '┥'..='┫' => '┤',
+        '┭'..='┳' => '┬',
+        '┵'..='┻' => '┴',
+        '┽'..='╋' => '┼',
+        '▖' | '▗' => '▄',
+        '▘' | '▝' => '▀',
+        c => ascii_fallback(c),
+    }
+}
+
+/// Returns the character standing in for the border character `c` among those the DEC Special
+/// Graphics set can draw, or its ASCII stand-in if there is no equivalent.
+fn dec_fallback(c: char) -> char {
+    match cp437_fallback(c) {
+        '═' => '─',
+        '║' => '│',
+        '╒'..='╔' => '┌',
+        '╕'..='╗' => '┐',
+        '╘'..='╚' => '└',
+        '╛'..='╝' => '┘',
+        '╞'..='╠' => '├',
+        '╡'..='╣' => '┤',
+        '╤'..='╦' => '┬',
+        '╧'..='╩' => '┴',
+        '╪'..='╬' => '┼',
+        '░' | '▒' | '▓' => '▒',
+        '█' | '▀' | '▄' | '▌' | '▐' => '#',
+        c => c,
+    }
+}
+
+/// Returns the character the DEC Special Graphics set draws `c` with, if it has one.
+fn dec_code(c: char) -> Option<char> {
+    Some(match c {
+        '┘' => 'j',
+        '┐' => 'k',
+        '┌' => 'l',
+        '└' => 'm',
+        '┼' => 'n',
+        '─' => 'q',
+        '├' => 't',
+        '┤' => 'u',
+        '┴' => 'v',
+        '┬' => 'w',
+        '│' => 'x',
+        '▒' => 'a',
+        _ => return None,
+    })
+}
+
+/// Returns the width, in columns, of the terminal the output is written to.
+///
+/// The `COLUMNS` environment variable takes precedence when set to a positive number. Otherwise
+/// the size of the terminal attached to standard output is queried, and `None` is returned if
+/// standard output is not a terminal. Without the `terminal` feature, only `COLUMNS` is read.
+pub fn terminal_width() -> Option<usize> {
+    let columns = env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse::<usize>().ok())
+        .filter(|&columns| columns > 0);
+
+    #[cfg(feature = "terminal")]
+    let columns = columns.or_else(|| {
+        terminal_size::terminal_size().map(|(terminal_size::Width(width), _)| width as usize)
+    });
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_modes_always_and_never_ignore_the_environment() {
+        assert_eq!(ColorMode::Always.enabled(), cfg!(feature = "color"));
+        assert!(!ColorMode::Never.enabled());
+    }
+
+    #[cfg(not(feature = "color"))]
+    #[test]
+    fn colors_are_never_written_without_the_color_feature() {
+        use crate::{create_text_border, Color, TextBorderOptions};
+
+        assert!(!ColorMode::Auto.enabled());
+        let options = TextBorderOptions {
+            color_mode: ColorMode::Always,
+            border_color: Some(Color::Red),
+            ..Default::default()
+        };
+        assert_eq!(create_text_border("a", Some(options)), "***\n*a*\n***");
+    }
+
+    /// Box-drawing, block and Braille characters borders may be drawn with.
+    fn border_chars() -> impl Iterator<Item = char> {
+        ('\u{2500}'..='\u{259f}')
+            .chain('\u{2800}'..='\u{28ff}')
+            .chain(['⌜', '⌝', '⌞', '⌟'])
+    }
+
+    #[test]
+    fn ascii_stands_in_for_every_border_character() {
+        for c in border_chars() {
+            assert!(Charset::Ascii.fallback(c).is_ascii(), "{:?}", c);
+        }
+        assert_eq!(Charset::Ascii.fallback('═'), '-');
+        assert_eq!(Charset::Ascii.fallback('┃'), '|');
+        assert_eq!(Charset::Ascii.fallback('╬'), '+');
+        assert_eq!(Charset::Ascii.fallback('é'), 'é');
+    }
+
+    #[test]
+    fn ascii_boxes_are_ascii_in_every_style() {
+        use crate::{create_text_border, BorderStyle, LineStyle, Sides, TextBorderOptions};
+
+        let styles = [
+            BorderStyle::HalfBlock,
+            BorderStyle::Weighted,
+            BorderStyle::Braille,
+            BorderStyle::Brackets,
+            BorderStyle::Lines(Sides::all(Some(LineStyle::Double))),
+            BorderStyle::Lines(Sides::new(None, Some(LineStyle::Heavy), None, None)),
+        ];
+        for &border_style in &styles {
+            let options = TextBorderOptions {
+                border_style,
+                charset: Charset::Ascii,
+                title: Some("t".to_string()),
+                ..Default::default()
+            };
+            let bordered = create_text_border("message", Some(options));
+            assert!(bordered.is_ascii(), "{:?}: {}", border_style, bordered);
+        }
+    }
+
+    #[test]
+    fn only_auto_is_detected() {
+        for &charset in &[
+            Charset::Unicode,
+            Charset::Ascii,
+            Charset::Cp437,
+            Charset::DecGraphics,
+        ] {
+            assert_eq!(charset.resolve(), charset);
+        }
+        assert_ne!(Charset::Auto.resolve(), Charset::Auto);
+    }
+
+    #[test]
+    fn cp437_stands_in_for_every_border_character() {
+        let drawable = |c: char| {
+            c.is_ascii()
+                || "─│┌┐└┘├┤┬┴┼░▒▓█▀▄▌▐".contains(c)
+                || ('\u{2550}'..='\u{256c}').contains(&c)
+        };
+        for c in border_chars() {
+            let fallback = cp437_fallback(c);
+            assert!(drawable(fallback), "{:?} became {:?}", c, fallback);
+            assert_eq!(cp437_fallback(fallback), fallback);
+        }
+        assert_eq!(cp437_fallback('╭'), '┌');
+        assert_eq!(cp437_fallback('┳'), '┬');
+        assert_eq!(cp437_fallback('═'), '═');
+    }
+
+    #[test]
+    fn dec_graphics_stand_ins_can_all_be_encoded() {
+        for c in border_chars() {
+            let fallback = dec_fallback(c);
+            assert!(
+                fallback.is_ascii() || dec_code(fallback).is_some(),
+                "{:?} became {:?}",
+                c,
+                fallback
+            );
+        }
+    }
+
+    #[test]
+    fn dec_graphics_switch_sets_only_between_runs() {
+        let encode = |border: &str| Charset::DecGraphics.encode(border.to_string());
+
+        assert_eq!(encode("┌─┐"), "\x1b(0lqk\x1b(B");
+        assert_eq!(encode("─ a ─"), "\x1b(0q\x1b(B a \x1b(0q\x1b(B");
+        assert_eq!(encode("ab"), "ab");
+        assert_eq!(encode(""), "");
+        assert_eq!(Charset::Ascii.encode("┌".to_string()), "┌");
+    }
+}
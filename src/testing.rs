@@ -0,0 +1,131 @@
+use crate::Grid;
+
+/// Asserts that two rendered boxes are equal, like `assert_eq!`, printing the differing rows
+/// aligned by column, with carets under the differing cells, when they are not.
+///
+/// Both arguments may be anything implementing `AsRef<str>`, such as a `&str` or a `String`.
+/// Escape sequences are not compared.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate unicode_border;
+///
+/// use unicode_border::create_text_border;
+///
+/// fn main() {
+///     assert_bordered_eq!(create_text_border("Hi", None), "****\n*Hi*\n****");
+/// }
+/// ```
+///
+/// A mismatch panics with a message listing the expected row after `-` and the actual row
+/// after `+`, such as:
+///
+/// ```text
+/// assertion failed: bordered output differs
+///  row 1 - *Ho*
+///  row 1 + *Hi*
+///            ^
+/// ```
+#[macro_export]
+macro_rules! assert_bordered_eq {
+    ($actual:expr, $expected:expr $(,)?) => {
+        if let Some(diff) = $crate::bordered_diff(&$actual, &$expected) {
+            panic!("assertion failed: bordered output differs\n{}", diff);
+        }
+    };
+}
+
+/// Returns the rows of `actual` that differ from `expected`, aligned by column with carets
+/// under the differing cells, or `None` if they are equal. Used by `assert_bordered_eq!`.
+#[doc(hidden)]
+pub fn bordered_diff<A, E>(actual: &A, expected: &E) -> Option<String>
+where
+    A: AsRef<str> + ?Sized,
+    E: AsRef<str> + ?Sized,
+{
+    let actual_lines: Vec<&str> = actual.as_ref().split('\n').collect();
+    let expected_lines: Vec<&str> = expected.as_ref().split('\n').collect();
+    let (actual_grid, expected_grid) = (
+        Grid::from_lines(&actual_lines),
+        Grid::from_lines(&expected_lines),
+    );
+    if actual_grid == expected_grid && actual_lines.len() == expected_lines.len() {
+        return None;
+    }
+
+    let rows = actual_lines.len().max(expected_lines.len());
+    let label_width = (rows.saturating_sub(1)).to_string().len();
+    let mut diff = String::new();
+    for y in 0..rows {
+        let row = |grid: &Grid| grid.rows().get(y).cloned().unwrap_or_default();
+        let (actual_row, expected_row) = (row(&actual_grid), row(&expected_grid));
+        let differs: Vec<bool> = (0..actual_row.len().max(expected_row.len()))
+            .map(|x| actual_row.get(x) != expected_row.get(x))
+            .collect();
+        if !differs.contains(&true) {
+            continue;
+        }
+
+        let text =
+            |row: &[char]| -> String { row.iter().filter(|&&c| c != Grid::CONTINUATION).collect() };
+        let carets: String = differs
+            .iter()
+            .map(|&differs| if differs { '^' } else { ' ' })
+            .collect();
+        diff.push_str(&format!(
+            " row {:>width$} - {}\n row {:>width$} + {}\n {} {}\n",
+            y,
+            text(&expected_row),
+            y,
+            text(&actual_row),
+            " ".repeat(4 + label_width + 2),
+            carets.trim_end(),
+            width = label_width
+        ));
+    }
+    Some(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_boxes_have_no_diff() {
+        assert_eq!(bordered_diff("**\n**", &String::from("**\n**")), None);
+        assert_eq!(bordered_diff("\x1b[31m*\x1b[0m", "*"), None);
+    }
+
+    #[test]
+    fn differing_cells_are_marked_with_carets() {
+        assert_eq!(
+            bordered_diff("*Hi*\n*ok*", "*Ho*\n*ok*").unwrap(),
+            " row 0 - *Ho*\n row 0 + *Hi*\n           ^\n"
+        );
+    }
+
+    #[test]
+    fn wide_characters_keep_the_carets_aligned() {
+        assert_eq!(
+            bordered_diff("字a", "字b").unwrap(),
+            " row 0 - 字b\n row 0 + 字a\n           ^\n"
+        );
+    }
+
+    #[test]
+    fn missing_rows_are_shown_empty() {
+        assert_eq!(
+            bordered_diff("a\nb", "a").unwrap(),
+            " row 1 - \n row 1 + b\n         ^\n"
+        );
+        assert!(bordered_diff("a\n", "a").is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "bordered output differs")]
+    fn the_assertion_panics_on_a_mismatch() {
+        assert_bordered_eq!("*a*", "*b*");
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{Color, ColorSupport, Style};
+
+/// `Theme` maps the semantic roles of a box to styles, so an application can switch palettes,
+/// such as between a light and a dark terminal, without touching its call sites.
+///
+/// Set it as the `theme` of `TextBorderOptions`. The border, title, content and accent roles
+/// style the parts of the box they name, unless a color set directly in the options overrides
+/// them, and the success, warning and error roles color the borders of `Diagnostic` reports.
+///
+/// # Fields
+///
+/// * `border` - The style of the border. Only its foreground is used.
+/// * `title` - The style of the title.
+/// * `content` - The style of the message. Its background is painted behind the margins too.
+/// * `accent` - The style of highlights such as the title badge. Only its foreground is used.
+/// * `success` - The style of successful outcomes.
+/// * `warning` - The style of warnings.
+/// * `error` - The style of errors.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "color")]
+/// # {
+/// use unicode_border::{create_text_border, Color, ColorMode, Style, TextBorderOptions, Theme};
+///
+/// let options = TextBorderOptions {
+///     color_mode: ColorMode::Always,
+///     theme: Some(Theme {
+///         border: Style {
+///             foreground: Some(Color::Blue),
+///             ..Default::default()
+///         },
+///         ..Theme::dark()
+///     }),
+///     ..Default::default()
+/// };
+///
+/// assert!(create_text_border("Hi", Some(options)).starts_with("\x1b[34m****\x1b[39m"));
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Theme {
+    pub border: Style,
+    pub title: Style,
+    pub content: Style,
+    pub accent: Style,
+    pub success: Style,
+    pub warning: Style,
+    pub error: Style,
+}
+
+impl Theme {
+    /// Returns a palette for terminals with a dark background, in bright colors.
+    pub fn dark() -> Self {
+        let foreground = |color| Style {
+            foreground: Some(color),
+            ..Default::default()
+        };
+
+        Self {
+            border: foreground(Color::BrightBlack),
+            title: foreground(Color::BrightWhite),
+            content: Style::default(),
+            accent: foreground(Color::BrightCyan),
+            success: foreground(Color::BrightGreen),
+            warning: foreground(Color::BrightYellow),
+            error: foreground(Color::BrightRed),
+        }
+    }
+
+    /// Returns a palette for terminals with a light background, in dark colors.
+    pub fn light() -> Self {
+        let foreground = |color| Style {
+            foreground: Some(color),
+            ..Default::default()
+        };
+
+        Self {
+            border: foreground(Color::Blue),
+            title: foreground(Color::Black),
+            content: Style::default(),
+            accent: foreground(Color::Magenta),
+            success: foreground(Color::Green),
+            warning: foreground(Color::Ansi256(130)),
+            error: foreground(Color::Red),
+        }
+    }
+
+    /// Returns the theme with its colors downgraded to what `support` can display.
+    pub(crate) fn downgrade(self, support: ColorSupport) -> Theme {
+        Theme {
+            border: self.border.downgrade(support),
+            title: self.title.downgrade(support),
+            content: self.content.downgrade(support),
+            accent: self.accent.downgrade(support),
+            success: self.success.downgrade(support),
+            warning: self.warning.downgrade(support),
+            error: self.error.downgrade(support),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downgrading_keeps_what_the_terminal_can_display() {
+        assert_eq!(
+            Theme::light().downgrade(ColorSupport::TrueColor),
+            Theme::light()
+        );
+        assert_eq!(Theme::dark().downgrade(ColorSupport::Basic), Theme::dark());
+
+        let warning = Theme::light().downgrade(ColorSupport::Basic).warning;
+        assert!(!matches!(
+            warning.foreground,
+            Some(Color::Ansi256(_)) | None
+        ));
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn explicit_colors_win_over_the_theme() {
+        use crate::{create_text_border, ColorMode, TextBorderOptions};
+
+        let options = TextBorderOptions {
+            theme: Some(Theme::dark()),
+            color_mode: ColorMode::Always,
+            ..Default::default()
+        };
+        assert!(create_text_border("Hi", Some(options.clone())).contains("\x1b[90m"));
+
+        let options = TextBorderOptions {
+            border_color: Some(Color::Red),
+            ..options
+        };
+        let bordered = create_text_border("Hi", Some(options));
+        assert!(bordered.contains("\x1b[31m") && !bordered.contains("\x1b[90m"));
+    }
+}
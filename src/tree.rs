@@ -0,0 +1,151 @@
+use crate::{create_text_border, default_options, Charset, TextBorderOptions};
+
+/// The guides drawn before a node that has more siblings after it, before its last sibling,
+/// and before the lines below a node with and without more siblings after it.
+const GUIDES: [&str; 4] = ["├── ", "└── ", "│   ", "    "];
+const ASCII_GUIDES: [&str; 4] = ["|-- ", "`-- ", "|   ", "    "];
+
+/// `Tree` is a node of a tree, such as a dependency or a directory, with the nodes below it.
+///
+/// # Fields
+///
+/// * `label` - The text of the node. Line breaks in the label start new lines, indented under
+///   the first.
+/// * `children` - The nodes below this node, in order.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{BorderStyle, TextBorderOptions, Tree};
+///
+/// let tree = Tree::new("src")
+///     .child(Tree::new("bin").child(Tree::new("main.rs")))
+///     .child(Tree::new("lib.rs"));
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     tree.render(Some(options)),
+///     "┌───────────────┐\n\
+///      │src            │\n\
+///      │├── bin        │\n\
+///      ││   └── main.rs│\n\
+///      │└── lib.rs     │\n\
+///      └───────────────┘"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Tree {
+    pub label: String,
+    pub children: Vec<Tree>,
+}
+
+impl Tree {
+    /// Creates a node without children.
+    pub fn new(label: impl Into<String>) -> Self {
+        Tree {
+            label: label.into(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Adds `child` below the node, after its other children.
+    pub fn child(mut self, child: Tree) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Returns the lines of the tree, with the guides drawn before each node.
+    ///
+    /// # Arguments
+    ///
+    /// * `charset` - The `Charset` the guides are drawn with. `Charset::Ascii` and
+    ///   `Charset::DecGraphics` draw them with `|`, `` ` `` and `-`.
+    pub fn lines(&self, charset: Charset) -> Vec<String> {
+        let guides = match charset.resolve() {
+            Charset::Ascii | Charset::DecGraphics => ASCII_GUIDES,
+            _ => GUIDES,
+        };
+        let mut lines: Vec<String> = self.label.split('\n').map(str::to_string).collect();
+        self.push_children(&guides, "", &mut lines);
+        lines
+    }
+
+    /// Renders the tree inside a border.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+    ///   configurations. If `None`, the options returned by `default_options` are used. The
+    ///   guides are drawn with their `charset`.
+    ///
+    /// # Returns
+    ///
+    /// * A `String` containing the tree surrounded by the specified border and margin.
+    pub fn render(&self, options: Option<TextBorderOptions>) -> String {
+        let options = options.unwrap_or_else(default_options);
+        let lines = self.lines(options.charset);
+        create_text_border(lines.join("\n"), Some(options))
+    }
+
+    /// Appends the lines of the children of the node, each line starting with `prefix`.
+    fn push_children(&self, guides: &[&str; 4], prefix: &str, lines: &mut Vec<String>) {
+        for (index, child) in self.children.iter().enumerate() {
+            let last = index + 1 == self.children.len();
+            let (guide, under) = if last {
+                (guides[1], guides[3])
+            } else {
+                (guides[0], guides[2])
+            };
+            let nested = format!("{}{}", prefix, under);
+
+            let mut label = child.label.split('\n');
+            lines.push(format!("{}{}{}", prefix, guide, label.next().unwrap_or("")));
+            lines.extend(label.map(|line| format!("{}{}", nested, line)));
+            child.push_children(guides, &nested, lines);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> Tree {
+        Tree::new("root")
+            .child(Tree::new("a").child(Tree::new("a1")).child(Tree::new("a2")))
+            .child(Tree::new("b\nmore").child(Tree::new("b1")))
+    }
+
+    #[test]
+    fn guides_join_siblings_and_stop_after_the_last() {
+        assert_eq!(
+            tree().lines(Charset::Unicode),
+            [
+                "root",
+                "├── a",
+                "│   ├── a1",
+                "│   └── a2",
+                "└── b",
+                "    more",
+                "    └── b1",
+            ]
+        );
+    }
+
+    #[test]
+    fn ascii_charsets_use_ascii_guides() {
+        let expected = ["root", "|-- a", "|   |-- a1", "|   `-- a2"];
+        for &charset in &[Charset::Ascii, Charset::DecGraphics] {
+            assert_eq!(tree().lines(charset)[..4], expected);
+        }
+    }
+
+    #[test]
+    fn a_leaf_is_its_label() {
+        assert_eq!(Tree::new("only").lines(Charset::Unicode), ["only"]);
+        assert_eq!(Tree::default().lines(Charset::Unicode), [""]);
+    }
+}
@@ -0,0 +1,373 @@
+use crate::width::str_width;
+use crate::{
+    create_text_border, strip_ansi_codes, BorderStyle, LineStyle, Sides, TextBorderOptions, Width,
+};
+
+/// `Unboxed` is a box taken apart by `unbox`.
+///
+/// # Fields
+///
+/// * `message` - The message of the box, without its margins and the padding after each line.
+/// * `options` - Options drawing the same border, title, footer and margins. Colors, title
+///   badges and footer statuses are not recovered.
+#[derive(Clone, Debug)]
+pub struct Unboxed {
+    pub message: String,
+    pub options: TextBorderOptions,
+}
+
+/// Takes apart a box rendered by this crate, recovering its message and the options it was
+/// most likely rendered with.
+///
+/// Boxes of `BorderStyle::Char`, `HalfBlock`, `Braille`, `Brackets` and box-drawing lines are
+/// recognized, as long as every side of the border is one character thick. Escape sequences
+/// are ignored.
+///
+/// # Arguments
+///
+/// * `bordered` - The box, such as returned by `create_text_border`.
+///
+/// # Returns
+///
+/// * `Some` with the `Unboxed` message and options, or `None` if `bordered` is not a box.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, unbox, BorderStyle, Sides, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     margin_thickness: Sides::new(1, 0, 1, 0),
+///     title: Some("Note".to_string()),
+///     ..Default::default()
+/// };
+/// let bordered = create_text_border("a short\nmessage", Some(options));
+/// let unboxed = unbox(&bordered).unwrap();
+///
+/// assert_eq!(unboxed.message, "a short\nmessage");
+/// assert_eq!(unboxed.options.title.as_deref(), Some("Note"));
+/// assert_eq!(create_text_border(unboxed.message, Some(unboxed.options)), bordered);
+/// ```
+pub fn unbox(bordered: &str) -> Option<Unboxed> {
+    let stripped = strip_ansi_codes(bordered);
+    let lines: Vec<Vec<char>> = stripped
+        .split('\n')
+        .map(|line| line.chars().collect())
+        .collect();
+    if lines.len() < 2 {
+        return None;
+    }
+    let (top, bottom) = (&lines[0], &lines[lines.len() - 1]);
+    let middle = &lines[1..lines.len() - 1];
+    if top.len() < 2 || bottom.len() < 2 || middle.iter().any(|line| line.len() < 2) {
+        return None;
+    }
+
+    let side = |column: fn(&[char]) -> char| {
+        let mut chars = middle.iter().map(|line| column(line));
+        let first = chars.next();
+        match first {
+            Some(c) if chars.all(|other| other == c) => Some(Some(c)),
+            Some(_) => None,
+            None => Some(None),
+        }
+    };
+    let left = side(|line| line[0])?;
+    let right = side(|line| line[line.len() - 1])?;
+    let top_fill = top[1];
+
+    let mut options = TextBorderOptions::default();
+    let mut delimiters = ['\0'; 4];
+    match top[0] {
+        '▗' => options.border_style = BorderStyle::HalfBlock,
+        '⢀' => options.border_style = BorderStyle::Braille,
+        '⌜' => options.border_style = BorderStyle::Brackets,
+        corner if left.unwrap_or(corner) == corner && top_fill == corner => {
+            options.border_char = corner;
+        }
+        _ => {
+            let top_style = line_style(top_fill)?;
+            let bottom_style = line_style(bottom[1])?;
+            let vertical = |c: Option<char>| c.map_or(Some(LineStyle::Light), line_style);
+            options.border_style = BorderStyle::Lines(Sides::new(
+                Some(vertical(left)?),
+                Some(top_style),
+                Some(vertical(right)?),
+                Some(bottom_style),
+            ));
+            let (open, close) = top_style.label_delimiters();
+            let (bottom_open, bottom_close) = bottom_style.label_delimiters();
+            delimiters = [open, close, bottom_open, bottom_close];
+        }
+    }
+
+    // Every cell of the border has to be drawn with the characters of the detected style, and
+    // every row has to be as wide as the top edge.
+    let chars = options
+        .border_style
+        .chars(options.border_char, Sides::all(1));
+    let width = |line: &[char]| str_width(&line.iter().collect::<String>());
+    let framed = |line: &[char], left: char, right: char| {
+        line[0] == left && line[line.len() - 1] == right && width(line) == width(top)
+    };
+    if !framed(top, chars.top_left, chars.top_right)
+        || !framed(bottom, chars.bottom_left, chars.bottom_right)
+        || !edge(top, chars.top)
+        || !edge(bottom, chars.bottom)
+        || !middle
+            .iter()
+            .all(|line| framed(line, chars.left, chars.right))
+    {
+        return None;
+    }
+
+    options.title = label(top, chars.top, &delimiters[..2]);
+    options.footer = label(bottom, chars.bottom, &delimiters[2..]);
+
+    let interior: Vec<String> = middle
+        .iter()
+        .map(|line| line[1..line.len() - 1].iter().collect())
+        .collect();
+    let blank = |line: &String| line.trim().is_empty();
+    let top_margin = interior.iter().take_while(|line| blank(line)).count();
+    let bottom_margin = if top_margin == interior.len() {
+        0
+    } else {
+        interior.iter().rev().take_while(|line| blank(line)).count()
+    };
+    let rows = &interior[top_margin..interior.len() - bottom_margin];
+
+    let text = rows.iter().filter(|line| !blank(line));
+    let left_margin = text
+        .clone()
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let right_margin = text
+        .map(|line| line.len() - line.trim_end().len())
+        .min()
+        .unwrap_or(0);
+    options.margin_thickness = Sides::new(left_margin, top_margin, right_margin, bottom_margin);
+
+    let message = rows
+        .iter()
+        .map(|line| line.get(left_margin..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Some(Unboxed { message, options })
+}
+
+/// Re-renders a box rendered by this crate at a new width, such as when the terminal is
+/// resized.
+///
+/// The box is taken apart with `unbox`, the lines of each paragraph of its message are joined
+/// and wrapped again, and the result is framed with the border that was detected.
+///
+/// # Arguments
+///
+/// * `bordered` - The box, such as returned by `create_text_border`.
+/// * `new_width` - The maximum width, in columns, of the new box.
+///
+/// # Returns
+///
+/// * `Some` with the new box, or `None` if `bordered` is not a box.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, reflow, BorderStyle, TextBorderOptions, Width};
+///
+/// let options = TextBorderOptions {
+///     border_style: BorderStyle::Weighted,
+///     max_width: Some(Width::Columns(12)),
+///     ..Default::default()
+/// };
+/// let narrow = create_text_border("one two three four", Some(options));
+///
+/// assert_eq!(narrow, "┌──────────┐\n│one two   │\n│three four│\n└──────────┘");
+/// assert_eq!(
+///     reflow(&narrow, 40).unwrap(),
+///     "┌──────────────────┐\n│one two three four│\n└──────────────────┘"
+/// );
+/// ```
+pub fn reflow(bordered: &str, new_width: usize) -> Option<String> {
+    let Unboxed {
+        message,
+        mut options,
+    } = unbox(bordered)?;
+
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    for line in message.lines() {
+        if line.trim().is_empty() {
+            if !paragraph.is_empty() {
+                paragraphs.push(paragraph.join(" "));
+                paragraph.clear();
+            }
+            paragraphs.push(String::new());
+        } else {
+            paragraph.push(line.trim());
+        }
+    }
+    if !paragraph.is_empty() {
+        paragraphs.push(paragraph.join(" "));
+    }
+
+    options.max_width = Some(Width::Columns(new_width));
+    Some(create_text_border(paragraphs.join("\n"), Some(options)))
+}
+
+/// Returns the line style of a box-drawing line character.
+fn line_style(c: char) -> Option<LineStyle> {
+    match c {
+        '─' | '│' => Some(LineStyle::Light),
+        '═' | '║' => Some(LineStyle::Double),
+        '━' | '┃' => Some(LineStyle::Heavy),
+        _ => None,
+    }
+}
+
+/// Returns whether the border row `edge` is drawn with `fill` between its corners, around any
+/// labels set into it.
+fn edge(edge: &[char], fill: char) -> bool {
+    let interior = &edge[1..edge.len() - 1];
+    match (interior.first(), interior.last()) {
+        (Some(&first), Some(&last)) => first == fill && last == fill,
+        _ => true,
+    }
+}
+
+/// Returns the label set into the border row `edge`, drawn with `fill` between its corners,
+/// if there is one.
+fn label(edge: &[char], fill: char, delimiters: &[char]) -> Option<String> {
+    let interior: String = edge[1..edge.len() - 1].iter().collect();
+    let label = interior
+        .trim_matches(|c: char| c == fill || delimiters.contains(&c))
+        .trim();
+    if label.is_empty() {
+        None
+    } else {
+        Some(label.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(message: &str, options: TextBorderOptions) {
+        let bordered = create_text_border(message, Some(options));
+        let unboxed = unbox(&bordered).unwrap_or_else(|| panic!("not unboxed:\n{}", bordered));
+
+        assert_eq!(unboxed.message, message);
+        assert_eq!(
+            create_text_border(unboxed.message, Some(unboxed.options)),
+            bordered
+        );
+    }
+
+    #[test]
+    fn every_recognized_style_round_trips() {
+        let styles = [
+            BorderStyle::Char,
+            BorderStyle::HalfBlock,
+            BorderStyle::Braille,
+            BorderStyle::Brackets,
+            BorderStyle::Weighted,
+            BorderStyle::Lines(Sides::all(Some(LineStyle::Double))),
+            BorderStyle::Lines(Sides::new(
+                Some(LineStyle::Heavy),
+                Some(LineStyle::Double),
+                Some(LineStyle::Light),
+                Some(LineStyle::Heavy),
+            )),
+        ];
+        for &border_style in &styles {
+            round_trip(
+                "one\ntwo three",
+                TextBorderOptions {
+                    border_style,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn margins_titles_and_footers_are_recovered() {
+        let options = TextBorderOptions {
+            border_char: '#',
+            margin_thickness: Sides::new(2, 1, 1, 1),
+            title: Some("Title".to_string()),
+            footer: Some("end".to_string()),
+            ..Default::default()
+        };
+        let bordered = create_text_border("a\n\nbcdefgh", Some(options));
+        let unboxed = unbox(&bordered).unwrap();
+
+        assert_eq!(unboxed.message, "a\n\nbcdefgh");
+        assert_eq!(unboxed.options.border_char, '#');
+        assert_eq!(unboxed.options.margin_thickness, Sides::new(2, 1, 1, 1));
+        assert_eq!(unboxed.options.title.as_deref(), Some("Title"));
+        assert_eq!(unboxed.options.footer.as_deref(), Some("end"));
+    }
+
+    #[test]
+    fn wide_edges_with_labels_are_recovered() {
+        let options = TextBorderOptions {
+            border_char: '＃',
+            footer: Some("ft".to_string()),
+            ..Default::default()
+        };
+        let bordered = create_text_border("hello world", Some(options));
+        let unboxed = unbox(&bordered).unwrap();
+
+        assert_eq!(unboxed.message, "hello world");
+        assert_eq!(unboxed.options.footer.as_deref(), Some("ft"));
+    }
+
+    #[test]
+    fn escape_sequences_are_ignored() {
+        let unboxed = unbox("\x1b[31m***\x1b[0m\n*\x1b[1mx\x1b[22m*\n***").unwrap();
+        assert_eq!(unboxed.message, "x");
+    }
+
+    #[test]
+    fn text_that_is_not_a_box_is_rejected() {
+        let cases = [
+            "",
+            "***",
+            "*\n*",
+            "****\n*ab*\n***",
+            "****\n*ab*\n*cd|\n****",
+            "****\n*ab*\n-cd*\n****",
+            "+--+\n|ab|\n+--+",
+            "xx\nyy",
+            "****\n*ab*\n*-**",
+            "****\n*ab*\n+**+",
+            "****\n*ab *\n*cd*\n****",
+            "┌──┐\n│ab│\n└══┘",
+            "▗▄▄▖\n▐ab▌\n▝▄▄▘",
+        ];
+        for &case in &cases {
+            assert!(unbox(case).is_none(), "{:?} was unboxed", case);
+            assert!(reflow(case, 10).is_none());
+        }
+    }
+
+    #[test]
+    fn reflow_keeps_paragraphs_apart() {
+        let options = TextBorderOptions {
+            max_width: Some(Width::Columns(7)),
+            ..Default::default()
+        };
+        let narrow = create_text_border("aa bb cc\n\ndd", Some(options));
+
+        assert_eq!(
+            reflow(&narrow, 20).unwrap(),
+            "**********\n*aa bb cc*\n*        *\n*dd      *\n**********"
+        );
+    }
+}
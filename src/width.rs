@@ -0,0 +1,228 @@
+use std::cell::Cell;
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{strip_ansi_codes, terminal_width};
+
+/// `AmbiguousWidth` selects how many columns East Asian Ambiguous characters, such as `①`,
+/// `○` or Greek and Cyrillic letters, are assumed to occupy.
+///
+/// # Variants
+///
+/// * `Narrow` - One column, as in most Western terminal configurations.
+/// * `Wide` - Two columns, matching CJK terminals configured with ambiguous=wide.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::{create_text_border, AmbiguousWidth, TextBorderOptions};
+///
+/// let options = TextBorderOptions {
+///     ambiguous_width: AmbiguousWidth::Wide,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(create_text_border("①②", Some(options)), "******\n*①②*\n******");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AmbiguousWidth {
+    #[default]
+    Narrow,
+    Wide,
+}
+
+/// `Width` is a width, in columns, that a box should fit in.
+///
+/// # Variants
+///
+/// * `Columns` - A fixed number of columns.
+/// * `Terminal` - The width of the terminal, as returned by `terminal_width`. If there is no
+///   terminal, for instance because the output is piped, no width applies.
+/// * `Percent` - A percentage of the width of the terminal, rounded down. Like `Terminal`, no
+///   width applies without a terminal.
+/// * `PercentOf` - A percentage of the given container width, rounded down, for boxes laid out
+///   inside a panel or a window of known size.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_border::Width;
+///
+/// assert_eq!(Width::PercentOf(80, 120).resolve(), Some(96));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Width {
+    Columns(usize),
+    Terminal,
+    Percent(u8),
+    PercentOf(u8, usize),
+}
+
+impl Width {
+    /// Returns the number of columns this width stands for, or `None` if it cannot be determined.
+    pub fn resolve(self) -> Option<usize> {
+        // Dividing first keeps huge widths from overflowing and still rounds down exactly.
+        let percent_of = |percent: u8, width: usize| {
+            let percent = percent as usize;
+            (width / 100)
+                .saturating_mul(percent)
+                .saturating_add(width % 100 * percent / 100)
+        };
+
+        match self {
+            Width::Columns(columns) => Some(columns),
+            Width::Terminal => terminal_width(),
+            Width::Percent(percent) => terminal_width().map(|width| percent_of(percent, width)),
+            Width::PercentOf(percent, container) => Some(percent_of(percent, container)),
+        }
+    }
+}
+
+thread_local! {
+    static AMBIGUOUS_WIDTH: Cell<AmbiguousWidth> = const { Cell::new(AmbiguousWidth::Narrow) };
+}
+
+/// Restores the ambiguous width in effect before `with_ambiguous_width` when dropped.
+struct RestoreAmbiguousWidth(AmbiguousWidth);
+
+impl Drop for RestoreAmbiguousWidth {
+    fn drop(&mut self) {
+        AMBIGUOUS_WIDTH.with(|width| width.set(self.0));
+    }
+}
+
+/// Runs `f` with every width measured on this thread treating East Asian Ambiguous characters
+/// as `ambiguous_width`.
+pub(crate) fn with_ambiguous_width<R>(ambiguous_width: AmbiguousWidth, f: impl FnOnce() -> R) -> R {
+    let _restore =
+        RestoreAmbiguousWidth(AMBIGUOUS_WIDTH.with(|width| width.replace(ambiguous_width)));
+    f()
+}
+
+fn ambiguous_width() -> AmbiguousWidth {
+    AMBIGUOUS_WIDTH.with(Cell::get)
+}
+
+/// Returns the number of terminal columns `c` occupies when used as a border character.
+///
+/// Characters without a defined width, such as control characters, count as one column.
+pub(crate) fn char_width(c: char) -> usize {
+    if is_printable_ascii(c as u32) {
+        return 1;
+    }
+
+    let width = match ambiguous_width() {
+        AmbiguousWidth::Narrow => c.width(),
+        AmbiguousWidth::Wide => c.width_cjk(),
+    };
+
+    width.unwrap_or(1).max(1)
+}
+
+/// Returns the number of terminal columns `s` occupies. Escape sequences take up no columns.
+pub(crate) fn str_width(s: &str) -> usize {
+    // Printable ASCII is one column per byte in every width table, so the common case skips
+    // the lookups.
+    if s.bytes().all(|b| is_printable_ascii(u32::from(b))) {
+        return s.len();
+    }
+
+    let stripped;
+    let s = if s.contains('\x1b') {
+        stripped = strip_ansi_codes(s);
+        &stripped
+    } else {
+        s
+    };
+
+    match ambiguous_width() {
+        AmbiguousWidth::Narrow => s.width(),
+        AmbiguousWidth::Wide => s.width_cjk(),
+    }
+}
+
+/// Returns whether `c` takes up at least one column when printed, unlike control characters
+/// and zero-width characters such as combining marks.
+pub(crate) fn is_printable(c: char) -> bool {
+    !c.is_control() && c.width().is_some_and(|width| width > 0)
+}
+
+/// Returns whether the code point `c` is a printable ASCII character, from space to `~`.
+fn is_printable_ascii(c: u32) -> bool {
+    (0x20..0x7f).contains(&c)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::catch_unwind;
+
+    use super::*;
+
+    #[test]
+    fn ambiguous_characters_follow_the_setting() {
+        assert_eq!(str_width("①②"), 2);
+        assert_eq!(char_width('①'), 1);
+        with_ambiguous_width(AmbiguousWidth::Wide, || {
+            assert_eq!(str_width("①②"), 4);
+            assert_eq!(char_width('①'), 2);
+            assert_eq!(str_width("abc"), 3);
+        });
+    }
+
+    #[test]
+    fn the_setting_is_restored_after_nesting_and_panics() {
+        with_ambiguous_width(AmbiguousWidth::Wide, || {
+            with_ambiguous_width(AmbiguousWidth::Narrow, || {
+                assert_eq!(ambiguous_width(), AmbiguousWidth::Narrow);
+            });
+            assert_eq!(ambiguous_width(), AmbiguousWidth::Wide);
+        });
+        assert_eq!(ambiguous_width(), AmbiguousWidth::Narrow);
+
+        let panicked = catch_unwind(|| {
+            with_ambiguous_width(AmbiguousWidth::Wide, || panic!("measuring failed"))
+        });
+        assert!(panicked.is_err());
+        assert_eq!(ambiguous_width(), AmbiguousWidth::Narrow);
+    }
+
+    #[test]
+    fn characters_without_a_width_count_as_one_column() {
+        assert_eq!(char_width('\t'), 1);
+        assert_eq!(char_width('\u{301}'), 1);
+        assert_eq!(char_width('中'), 2);
+    }
+
+    #[test]
+    fn percentages_of_a_container_round_down() {
+        assert_eq!(Width::PercentOf(50, 9).resolve(), Some(4));
+        assert_eq!(Width::PercentOf(0, 80).resolve(), Some(0));
+        assert_eq!(Width::PercentOf(150, 80).resolve(), Some(120));
+        assert_eq!(
+            Width::PercentOf(100, usize::MAX).resolve(),
+            Some(usize::MAX)
+        );
+        assert_eq!(
+            Width::PercentOf(50, usize::MAX).resolve(),
+            Some(usize::MAX / 2)
+        );
+        assert_eq!(
+            Width::PercentOf(255, usize::MAX).resolve(),
+            Some(usize::MAX)
+        );
+        assert_eq!(Width::Columns(7).resolve(), Some(7));
+    }
+
+    #[test]
+    fn the_ascii_fast_path_agrees_with_the_width_tables() {
+        for c in (0x20u8..0x7f).map(char::from) {
+            assert_eq!(char_width(c), c.width().unwrap(), "{:?}", c);
+            assert_eq!(
+                str_width(c.encode_utf8(&mut [0; 4])),
+                c.width_cjk().unwrap()
+            );
+        }
+        assert_eq!(str_width("ab\x1b[1mc\x1b[0m"), 3);
+        assert_eq!(str_width("a\u{e9}b"), 3);
+    }
+}
@@ -0,0 +1,303 @@
+use std::ops::Range;
+
+use unicode_linebreak::linebreaks;
+
+use crate::width::str_width;
+
+/// Wraps `line` into lines at most `width` columns wide, breaking only where the Unicode Line
+/// Breaking Algorithm (UAX #14) allows: never after an opening bracket or around a no-break
+/// space, and between most CJK characters.
+///
+/// URLs are never broken, so they stay clickable, unless they are wider than `width` on their
+/// own, in which case they are split after a `/` where possible. Other words wider than `width`
+/// are split at character boundaries. Whitespace at a break is dropped.
+///
+/// Lines that look like list items, starting with a bullet such as `-` or `•` or a number such
+/// as `1.` followed by a space, have their continuation lines indented under the text of the
+/// item, past the marker, so wrapped lists stay readable.
+pub(crate) fn wrap_line(line: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let marker = list_marker(line);
+    let indent = str_width(marker);
+    if marker.is_empty() || indent >= width || str_width(line) <= width {
+        return wrap_text(line, width);
+    }
+
+    let mut wrapped = wrap_text(&line[marker.len()..], width - indent);
+    for (i, row) in wrapped.iter_mut().enumerate() {
+        let prefix = if i == 0 {
+            marker.to_string()
+        } else {
+            " ".repeat(indent)
+        };
+        row.insert_str(0, &prefix);
+    }
+    wrapped
+}
+
+/// Returns the list marker `line` starts with, with the whitespace around it, or an empty
+/// string if it does not look like a list item.
+fn list_marker(line: &str) -> &str {
+    let text = line.trim_start();
+    let bullet = text
+        .chars()
+        .next()
+        .filter(|c| "-*+•‣◦".contains(*c))
+        .map(char::len_utf8);
+    let number = || {
+        let digits = text.bytes().take_while(u8::is_ascii_digit).count();
+        let delimited = (1..=9).contains(&digits)
+            && matches!(text.as_bytes().get(digits), Some(b'.') | Some(b')'));
+        Some(digits + 1).filter(|_| delimited)
+    };
+
+    match bullet.or_else(number) {
+        Some(len) if text[len..].starts_with(' ') => {
+            let rest = &text[len..];
+            let end = line.len() - rest.len() + (rest.len() - rest.trim_start().len());
+            &line[..end]
+        }
+        _ => "",
+    }
+}
+
+/// Wraps `line` as `wrap_line` does, without special handling of list items.
+fn wrap_text(line: &str, width: usize) -> Vec<String> {
+    let mut wrapped = Vec::new();
+    let mut current = String::new();
+    let mut start = 0;
+
+    let urls = url_spans(line);
+    let inside_url = |index: usize| urls.iter().any(|url| url.start < index && index < url.end);
+
+    for (end, _) in linebreaks(line).filter(|&(end, _)| !inside_url(end)) {
+        let segment = &line[start..end];
+        start = end;
+
+        if !current.is_empty() && str_width(&current) + str_width(segment.trim_end()) > width {
+            wrapped.push(current.trim_end().to_string());
+            current.clear();
+        }
+
+        if current.is_empty() && str_width(segment.trim_end()) > width {
+            let word = segment.trim_end();
+            let mut pieces = if urls
+                .iter()
+                .any(|url| url.end > end - segment.len() && url.start < end)
+            {
+                split_url(word, width)
+            } else {
+                split_to_width(word, width)
+            };
+            current = pieces.pop().unwrap_or_default();
+            current.push_str(&segment[word.len()..]);
+            wrapped.extend(pieces);
+            continue;
+        }
+
+        current.push_str(segment);
+    }
+
+    if !current.trim_end().is_empty() || wrapped.is_empty() {
+        wrapped.push(current.trim_end().to_string());
+    }
+
+    wrapped
+}
+
+/// Returns the byte ranges of the URLs in `line`, recognized by a scheme followed by `://`.
+///
+/// A URL runs until the next whitespace, leaving out trailing punctuation such as a full stop
+/// or a closing bracket without a matching opening bracket in the URL.
+fn url_spans(line: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut search = 0;
+
+    while let Some(found) = line[search..].find("://") {
+        let separator = search + found;
+        search = separator + 3;
+
+        let scheme_start = line[..separator]
+            .rfind(|c: char| !(c.is_ascii_alphanumeric() || "+-.".contains(c)))
+            .map_or(0, |index| index + 1);
+        let scheme = &line[scheme_start..separator];
+        if !scheme.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            continue;
+        }
+
+        let mut end = line[search..]
+            .find(char::is_whitespace)
+            .map_or(line.len(), |index| search + index);
+        while let Some(last) = line[search..end].chars().last() {
+            let url = &line[scheme_start..end];
+            let unmatched = match last {
+                ')' => url.matches('(').count() < url.matches(')').count(),
+                ']' => url.matches('[').count() < url.matches(']').count(),
+                '.' | ',' | ';' | ':' | '!' | '?' | '\'' | '"' | '>' => true,
+                _ => false,
+            };
+            if !unmatched {
+                break;
+            }
+            end -= last.len_utf8();
+        }
+
+        if end > search {
+            spans.push(scheme_start..end);
+        }
+        search = end.max(search);
+    }
+
+    spans
+}
+
+/// Splits `text`, containing a URL, into pieces at most `width` columns wide, each ending just
+/// after a `/` where possible.
+fn split_url(text: &str, width: usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+
+    for part in text.split_inclusive('/') {
+        if !current.is_empty() && str_width(&current) + str_width(part) > width {
+            pieces.push(std::mem::take(&mut current));
+        }
+
+        if current.is_empty() && str_width(part) > width {
+            let mut split = split_to_width(part, width);
+            current = split.pop().unwrap_or_default();
+            pieces.extend(split);
+            continue;
+        }
+
+        current.push_str(part);
+    }
+
+    pieces.push(current);
+    pieces
+}
+
+/// Splits `text` at character boundaries into pieces at most `width` columns wide.
+fn split_to_width(text: &str, width: usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+
+    for c in text.chars() {
+        let mut buffer = [0; 4];
+        let c_width = str_width(c.encode_utf8(&mut buffer));
+        if !current.is_empty() && str_width(&current) + c_width > width {
+            pieces.push(current.clone());
+            current.clear();
+        }
+        current.push(c);
+    }
+
+    pieces.push(current);
+    pieces
+}
+
+/// Returns the longest prefix of `text`, cut at a character boundary, at most `width` columns
+/// wide.
+pub(crate) fn truncate_to_width(text: &str, width: usize) -> String {
+    let mut truncated = String::new();
+    let mut truncated_width = 0;
+
+    for c in text.chars() {
+        let mut buffer = [0; 4];
+        let c_width = str_width(c.encode_utf8(&mut buffer));
+        if truncated_width + c_width > width {
+            break;
+        }
+        truncated.push(c);
+        truncated_width += c_width;
+    }
+
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breaks_only_where_allowed() {
+        assert_eq!(wrap_line("one two three", 7), ["one two", "three"]);
+        assert_eq!(wrap_line("a (bc", 3), ["a", "(bc"]);
+        assert_eq!(wrap_line("10\u{a0}km away", 6), ["10\u{a0}km", "away"]);
+        assert_eq!(wrap_line("日本語です", 4), ["日本", "語で", "す"]);
+    }
+
+    #[test]
+    fn splits_words_wider_than_the_width() {
+        assert_eq!(wrap_line("abcdefg hi", 3), ["abc", "def", "g", "hi"]);
+        assert_eq!(wrap_line("日本語", 1), ["日", "本", "語"]);
+    }
+
+    #[test]
+    fn handles_degenerate_widths_and_lines() {
+        assert_eq!(wrap_line("", 5), [""]);
+        assert_eq!(wrap_line("   ", 2), [""]);
+        assert_eq!(wrap_line("ab", 0), ["a", "b"]);
+        assert_eq!(wrap_line("a    b", 2), ["a", "b"]);
+    }
+
+    #[test]
+    fn truncates_at_character_boundaries() {
+        assert_eq!(truncate_to_width("日本語", 5), "日本");
+        assert_eq!(truncate_to_width("abc", 0), "");
+        assert_eq!(truncate_to_width("abc", 9), "abc");
+    }
+
+    #[test]
+    fn urls_are_kept_whole() {
+        assert_eq!(
+            wrap_line("see https://example.com/a-b/c?d=e now", 30),
+            ["see", "https://example.com/a-b/c?d=e", "now"]
+        );
+    }
+
+    #[test]
+    fn urls_wider_than_the_width_are_split_after_slashes() {
+        assert_eq!(
+            wrap_line("https://example.com/path/to/page", 12),
+            ["https://", "example.com/", "path/to/page"]
+        );
+        assert_eq!(
+            wrap_line("ftp://averyveryverylonghost", 10),
+            ["ftp://", "averyveryv", "erylonghos", "t"]
+        );
+    }
+
+    #[test]
+    fn url_spans_leave_out_trailing_punctuation() {
+        let spans = |line: &str| -> Vec<String> {
+            url_spans(line)
+                .into_iter()
+                .map(|span| line[span].to_string())
+                .collect()
+        };
+
+        assert_eq!(spans("(see http://a.b/c)."), ["http://a.b/c"]);
+        assert_eq!(spans("http://w.org/A_(b)!"), ["http://w.org/A_(b)"]);
+        assert_eq!(spans("git+ssh://h/r, x://y"), ["git+ssh://h/r", "x://y"]);
+        assert!(spans("://nothing 1a://no http://").is_empty());
+    }
+
+    #[test]
+    fn list_items_are_indented_past_their_marker() {
+        assert_eq!(wrap_line("- one two", 6), ["- one", "  two"]);
+        assert_eq!(wrap_line("  • one two", 8), ["  • one", "    two"]);
+        assert_eq!(wrap_line("12) one two", 8), ["12) one", "    two"]);
+    }
+
+    #[test]
+    fn lines_that_are_not_list_items_are_not_indented() {
+        assert_eq!(wrap_line("-one two", 5), ["-one", "two"]);
+        assert_eq!(wrap_line("1234567890. a b", 12), ["1234567890.", "a b"]);
+        assert_eq!(wrap_line("3.5 is a b", 6), ["3.5 is", "a b"]);
+    }
+
+    #[test]
+    fn markers_as_wide_as_the_box_are_not_kept_apart() {
+        assert_eq!(wrap_line("- ab", 2), ["-", "ab"]);
+    }
+}
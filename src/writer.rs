@@ -0,0 +1,350 @@
+use std::io::{self, Write};
+
+use crate::render::{Layout, Renderer};
+use crate::stream::{write_lines, write_message_line};
+use crate::width::with_ambiguous_width;
+use crate::{default_options, AmbiguousWidth, TextBorderOptions, MAX_CONTENT_WIDTH};
+
+/// `BorderedWriter` is an `io::Write` adapter that surrounds everything written through it with
+/// a border and margin before passing it on to the wrapped writer.
+///
+/// The top border is written along with the first line, each line is written as soon as its
+/// newline arrives, and the bottom border is written on `flush`, `finish` or drop. Writing
+/// again after a flush starts a new box. As with `stream_text_border`, the width of the box is
+/// fixed up front: lines are wrapped to `content_width` columns and narrower lines are padded.
+///
+/// When the wrapped writer fails, `write` returns the number of bytes whose lines were written,
+/// and the line that failed is kept, so writing or flushing again retries it. Errors from the
+/// wrapped writer while the box is closed on drop are ignored; call `finish` to handle them.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use unicode_border::BorderedWriter;
+///
+/// let mut writer = BorderedWriter::new(Vec::new(), 6, None);
+/// writeln!(writer, "Hello,").unwrap();
+/// write!(writer, "world").unwrap();
+///
+/// let output = writer.finish().unwrap();
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "********\n*Hello,*\n*world *\n********\n"
+/// );
+/// ```
+pub struct BorderedWriter<W: Write> {
+    inner: Option<W>,
+    renderer: Renderer,
+    layout: Layout,
+    content_width: usize,
+    ambiguous_width: AmbiguousWidth,
+    pending: Vec<u8>,
+    open: bool,
+    row: usize,
+    previous_blank: bool,
+}
+
+impl<W: Write> BorderedWriter<W> {
+    /// Creates a writer that boxes its output into `inner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The writer to which the bordered lines are written.
+    /// * `content_width` - The width, in columns, of the message area of each box, at most
+    ///   `MAX_CONTENT_WIDTH`.
+    /// * `options` - An optional `TextBorderOptions` instance specifying the border and margin
+    ///   configurations. If `None`, the options returned by `default_options` are used.
+    pub fn new(inner: W, content_width: usize, options: Option<TextBorderOptions>) -> Self {
+        let opts = options.unwrap_or_else(default_options);
+        let ambiguous_width = opts.ambiguous_width;
+        let content_width = content_width.clamp(1, MAX_CONTENT_WIDTH);
+
+        let (renderer, layout) = with_ambiguous_width(ambiguous_width, || {
+            let renderer = Renderer::new(opts);
+            let layout = renderer.layout(content_width);
+            (renderer, layout)
+        });
+
+        Self {
+            inner: Some(inner),
+            renderer,
+            layout,
+            content_width,
+            ambiguous_width,
+            pending: Vec::new(),
+            open: false,
+            row: 0,
+            previous_blank: false,
+        }
+    }
+
+    /// Returns a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner
+            .as_ref()
+            .expect("inner writer is only taken by finish")
+    }
+
+    /// Closes the current box, if any, and returns the wrapped writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.close()?;
+        Ok(self
+            .inner
+            .take()
+            .expect("inner writer is only taken by finish"))
+    }
+
+    /// Writes any buffered partial line and the bottom border of the current box.
+    fn close(&mut self) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            let line = self.pending.clone();
+            self.write_row(&line)?;
+            self.pending.clear();
+        }
+        if self.open {
+            self.open = false;
+            let bottom = with_ambiguous_width(self.ambiguous_width, || {
+                self.renderer.bottom_lines(&self.layout, self.row)
+            });
+            write_lines(self.writer(), bottom)?;
+        }
+        Ok(())
+    }
+
+    /// Writes one line of input, opening a new box first if needed.
+    fn write_row(&mut self, line: &[u8]) -> io::Result<()> {
+        let line = String::from_utf8_lossy(line);
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+        if !self.open {
+            self.previous_blank = false;
+        }
+        let previous_blank = self.previous_blank;
+        if self.renderer.skip_line(line, &mut self.previous_blank) {
+            return Ok(());
+        }
+
+        let result = self.write_message_row(line);
+        if result.is_err() {
+            // The line is written again on retry, so it must not count as the line before it.
+            self.previous_blank = previous_blank;
+        }
+        result
+    }
+
+    /// Writes `line` into the current box, opening a new box first if needed. The box counts as
+    /// open as soon as its top border is written.
+    fn write_message_row(&mut self, line: &str) -> io::Result<()> {
+        let (renderer, layout) = (&self.renderer, &self.layout);
+        let (ambiguous_width, content_width) = (self.ambiguous_width, self.content_width);
+        let writer = self
+            .inner
+            .as_mut()
+            .expect("inner writer is only taken by finish");
+
+        if !self.open {
+            with_ambiguous_width(ambiguous_width, || {
+                write_lines(writer, renderer.top_lines(layout))
+            })?;
+            self.open = true;
+            self.row = renderer.first_message_row();
+        }
+        let row = self.row;
+        self.row += with_ambiguous_width(ambiguous_width, || {
+            write_message_line(writer, renderer, layout, row, line, content_width)
+        })?;
+        Ok(())
+    }
+
+    fn writer(&mut self) -> &mut W {
+        self.inner
+            .as_mut()
+            .expect("inner writer is only taken by finish")
+    }
+}
+
+impl<W: Write> Write for BorderedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut accepted = 0;
+        while let Some(newline) = buf[accepted..].iter().position(|&b| b == b'\n') {
+            let end = accepted + newline;
+            let mut line = self.pending.clone();
+            line.extend_from_slice(&buf[accepted..end]);
+            match self.write_row(&line) {
+                Ok(()) => self.pending.clear(),
+                Err(err) if accepted == 0 => return Err(err),
+                Err(_) => return Ok(accepted),
+            }
+            accepted = end + 1;
+        }
+        self.pending.extend_from_slice(&buf[accepted..]);
+
+        Ok(buf.len())
+    }
+
+    /// Closes the current box, writing its bottom border, and flushes the wrapped writer.
+    fn flush(&mut self) -> io::Result<()> {
+        self.close()?;
+        self.writer().flush()
+    }
+}
+
+impl<W: Write> Drop for BorderedWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.close();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Write};
+
+    use super::BorderedWriter;
+
+    /// A writer that accepts `capacity` bytes and then fails.
+    struct FailingWriter {
+        written: Vec<u8>,
+        capacity: usize,
+    }
+
+    impl FailingWriter {
+        fn new(capacity: usize) -> Self {
+            FailingWriter {
+                written: Vec::new(),
+                capacity,
+            }
+        }
+    }
+
+    impl Write for FailingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.written.len() + buf.len() > self.capacity {
+                return Err(io::Error::new(io::ErrorKind::WriteZero, "full"));
+            }
+            self.written.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn boxed(content_width: usize, input: &[u8]) -> String {
+        let mut writer = BorderedWriter::new(Vec::new(), content_width, None);
+        writer.write_all(input).unwrap();
+        String::from_utf8(writer.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn errors_while_writing_a_line_are_returned() {
+        let mut writer = BorderedWriter::new(FailingWriter::new(4), 2, None);
+        let error = writer.write_all(b"ab\n").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn errors_while_closing_the_box_are_returned_by_finish() {
+        let mut writer = BorderedWriter::new(FailingWriter::new(10), 2, None);
+        writer.write_all(b"ab\n").unwrap();
+        assert_eq!(writer.get_ref().written, b"****\n*ab*\n");
+
+        let error = writer.finish().err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn errors_while_closing_the_box_are_returned_by_flush() {
+        let mut writer = BorderedWriter::new(FailingWriter::new(10), 2, None);
+        writer.write_all(b"ab").unwrap();
+        assert_eq!(writer.flush().unwrap_err().kind(), io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn lines_written_before_an_error_are_accepted() {
+        let mut writer = BorderedWriter::new(FailingWriter::new(10), 2, None);
+        assert_eq!(writer.write(b"ab\ncd\nef").unwrap(), 3);
+        assert_eq!(writer.get_ref().written, b"****\n*ab*\n");
+
+        writer.inner.as_mut().unwrap().capacity = usize::MAX;
+        writer.write_all(b"cd\nef").unwrap();
+        assert_eq!(
+            writer.finish().unwrap().written,
+            b"****\n*ab*\n*cd*\n*ef*\n****\n"
+        );
+    }
+
+    #[test]
+    fn lines_that_fail_are_kept_for_a_retry() {
+        let mut writer = BorderedWriter::new(FailingWriter::new(5), 2, None);
+        writer.write_all(b"ab").unwrap();
+        assert!(writer.flush().is_err());
+        assert_eq!(writer.get_ref().written, b"****\n");
+
+        writer.inner.as_mut().unwrap().capacity = usize::MAX;
+        assert_eq!(writer.finish().unwrap().written, b"****\n*ab*\n****\n");
+    }
+
+    #[test]
+    fn errors_on_drop_are_ignored() {
+        let mut writer = BorderedWriter::new(FailingWriter::new(10), 2, None);
+        writer.write_all(b"ab\n").unwrap();
+        drop(writer);
+    }
+
+    #[test]
+    fn dropping_the_writer_closes_the_box() {
+        let mut output = Vec::new();
+        {
+            let mut writer = BorderedWriter::new(&mut output, 2, None);
+            writer.write_all(b"ab").unwrap();
+        }
+        assert_eq!(output, b"****\n*ab*\n****\n");
+    }
+
+    #[test]
+    fn flushing_starts_a_new_box() {
+        let mut writer = BorderedWriter::new(Vec::new(), 1, None);
+        writer.write_all(b"a\n").unwrap();
+        writer.flush().unwrap();
+        writer.flush().unwrap();
+        writer.write_all(b"b").unwrap();
+
+        assert_eq!(
+            String::from_utf8(writer.finish().unwrap()).unwrap(),
+            "***\n*a*\n***\n***\n*b*\n***\n"
+        );
+    }
+
+    #[test]
+    fn writing_nothing_writes_no_box() {
+        assert_eq!(boxed(4, b""), "");
+    }
+
+    #[test]
+    fn lines_split_across_writes_are_joined() {
+        let mut writer = BorderedWriter::new(Vec::new(), 4, None);
+        writer.write_all(b"ab").unwrap();
+        writer.write_all(b"cd\r\n").unwrap();
+
+        assert_eq!(
+            String::from_utf8(writer.finish().unwrap()).unwrap(),
+            "******\n*abcd*\n******\n"
+        );
+    }
+
+    #[test]
+    fn long_lines_are_wrapped_and_invalid_utf8_replaced() {
+        assert_eq!(
+            boxed(3, b"abcdef\n\xff"),
+            "*****\n*abc*\n*def*\n*\u{fffd}  *\n*****\n"
+        );
+    }
+
+    #[test]
+    fn content_width_is_at_least_one_column() {
+        assert_eq!(boxed(0, b"a"), "***\n*a*\n***\n");
+    }
+}